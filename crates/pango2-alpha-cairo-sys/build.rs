@@ -14,16 +14,17 @@ fn main() {
     let freetype_includes = freetype.include_paths
                 .iter()
                 .map(|path| format!("-I{}", path.to_string_lossy()));
-    let cairo_includes = cairo.include_paths
+    let cairo_includes: Vec<_> = cairo.include_paths
                 .iter()
-                .map(|path| format!("-I{}", path.to_string_lossy()));
+                .map(|path| format!("-I{}", path.to_string_lossy()))
+                .collect();
 
     println!("cargo:include={}", cairo.include_paths[0].to_string_lossy());
 
     let bindings_path = base_path.join("src/bindings.rs");
     let bindings = bindgen::Builder::default()
         .clang_args(freetype_includes)
-        .clang_args(cairo_includes)
+        .clang_args(&cairo_includes)
         .clang_arg(format!("-L{}", freetype.link_paths[0].to_string_lossy()))
         .clang_arg(format!("-L{}", cairo.link_paths[0].to_string_lossy()))
         .header(wrapper_h_path.to_string_lossy().to_string())
@@ -35,6 +36,26 @@ fn main() {
         .write_to_file(bindings_path)
         .expect("Couldn't write bindings!");
 
+    // the optional backends get a file each, generated only with their
+    // feature, so the committed bindings stay the same for every feature set
+    for backend in ["pdf", "svg", "ps"] {
+        if env::var_os(format!("CARGO_FEATURE_{}", backend.to_uppercase())).is_none() {
+            continue;
+        }
+        let backend_bindings = bindgen::Builder::default()
+            .clang_args(&cairo_includes)
+            .header_contents("backend.h", &format!("#include <cairo-{}.h>", backend))
+            .allowlist_file(format!(".*cairo-{}\\.h", backend))
+            .allowlist_recursively(false)
+            .parse_callbacks(Box::new(bindgen::CargoCallbacks))
+            .generate()
+            .expect("Unable to generate backend bindings");
+
+        backend_bindings
+            .write_to_file(base_path.join(format!("src/{}.rs", backend)))
+            .expect("Couldn't write backend bindings!");
+    }
+
     for lib in &cairo.libs {
         println!("cargo:rustc-link-lib=dylib={}", lib);
     }
@@ -4,3 +4,10 @@
 #![allow(clippy::all)]
 
 include!("./bindings.rs");
+
+#[cfg(feature = "pdf")]
+include!("./pdf.rs");
+#[cfg(feature = "svg")]
+include!("./svg.rs");
+#[cfg(feature = "ps")]
+include!("./ps.rs");
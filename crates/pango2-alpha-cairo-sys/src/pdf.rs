@@ -0,0 +1,98 @@
+/* automatically generated by rust-bindgen 0.66.1 */
+
+pub const CAIRO_PDF_OUTLINE_ROOT: u32 = 0;
+pub const _cairo_pdf_version_CAIRO_PDF_VERSION_1_4: _cairo_pdf_version = 0;
+pub const _cairo_pdf_version_CAIRO_PDF_VERSION_1_5: _cairo_pdf_version = 1;
+pub const _cairo_pdf_version_CAIRO_PDF_VERSION_1_6: _cairo_pdf_version = 2;
+pub const _cairo_pdf_version_CAIRO_PDF_VERSION_1_7: _cairo_pdf_version = 3;
+pub type _cairo_pdf_version = ::std::os::raw::c_uint;
+pub use self::_cairo_pdf_version as cairo_pdf_version_t;
+extern "C" {
+    pub fn cairo_pdf_surface_create(
+        filename: *const ::std::os::raw::c_char,
+        width_in_points: f64,
+        height_in_points: f64,
+    ) -> *mut cairo_surface_t;
+}
+extern "C" {
+    pub fn cairo_pdf_surface_create_for_stream(
+        write_func: cairo_write_func_t,
+        closure: *mut ::std::os::raw::c_void,
+        width_in_points: f64,
+        height_in_points: f64,
+    ) -> *mut cairo_surface_t;
+}
+extern "C" {
+    pub fn cairo_pdf_surface_restrict_to_version(
+        surface: *mut cairo_surface_t,
+        version: cairo_pdf_version_t,
+    );
+}
+extern "C" {
+    pub fn cairo_pdf_get_versions(
+        versions: *mut *const cairo_pdf_version_t,
+        num_versions: *mut ::std::os::raw::c_int,
+    );
+}
+extern "C" {
+    pub fn cairo_pdf_version_to_string(
+        version: cairo_pdf_version_t,
+    ) -> *const ::std::os::raw::c_char;
+}
+extern "C" {
+    pub fn cairo_pdf_surface_set_size(
+        surface: *mut cairo_surface_t,
+        width_in_points: f64,
+        height_in_points: f64,
+    );
+}
+pub const _cairo_pdf_outline_flags_CAIRO_PDF_OUTLINE_FLAG_OPEN: _cairo_pdf_outline_flags = 1;
+pub const _cairo_pdf_outline_flags_CAIRO_PDF_OUTLINE_FLAG_BOLD: _cairo_pdf_outline_flags = 2;
+pub const _cairo_pdf_outline_flags_CAIRO_PDF_OUTLINE_FLAG_ITALIC: _cairo_pdf_outline_flags = 4;
+pub type _cairo_pdf_outline_flags = ::std::os::raw::c_uint;
+pub use self::_cairo_pdf_outline_flags as cairo_pdf_outline_flags_t;
+extern "C" {
+    pub fn cairo_pdf_surface_add_outline(
+        surface: *mut cairo_surface_t,
+        parent_id: ::std::os::raw::c_int,
+        utf8: *const ::std::os::raw::c_char,
+        link_attribs: *const ::std::os::raw::c_char,
+        flags: cairo_pdf_outline_flags_t,
+    ) -> ::std::os::raw::c_int;
+}
+pub const _cairo_pdf_metadata_CAIRO_PDF_METADATA_TITLE: _cairo_pdf_metadata = 0;
+pub const _cairo_pdf_metadata_CAIRO_PDF_METADATA_AUTHOR: _cairo_pdf_metadata = 1;
+pub const _cairo_pdf_metadata_CAIRO_PDF_METADATA_SUBJECT: _cairo_pdf_metadata = 2;
+pub const _cairo_pdf_metadata_CAIRO_PDF_METADATA_KEYWORDS: _cairo_pdf_metadata = 3;
+pub const _cairo_pdf_metadata_CAIRO_PDF_METADATA_CREATOR: _cairo_pdf_metadata = 4;
+pub const _cairo_pdf_metadata_CAIRO_PDF_METADATA_CREATE_DATE: _cairo_pdf_metadata = 5;
+pub const _cairo_pdf_metadata_CAIRO_PDF_METADATA_MOD_DATE: _cairo_pdf_metadata = 6;
+pub type _cairo_pdf_metadata = ::std::os::raw::c_uint;
+pub use self::_cairo_pdf_metadata as cairo_pdf_metadata_t;
+extern "C" {
+    pub fn cairo_pdf_surface_set_metadata(
+        surface: *mut cairo_surface_t,
+        metadata: cairo_pdf_metadata_t,
+        utf8: *const ::std::os::raw::c_char,
+    );
+}
+extern "C" {
+    pub fn cairo_pdf_surface_set_custom_metadata(
+        surface: *mut cairo_surface_t,
+        name: *const ::std::os::raw::c_char,
+        value: *const ::std::os::raw::c_char,
+    );
+}
+extern "C" {
+    pub fn cairo_pdf_surface_set_page_label(
+        surface: *mut cairo_surface_t,
+        utf8: *const ::std::os::raw::c_char,
+    );
+}
+extern "C" {
+    pub fn cairo_pdf_surface_set_thumbnail_size(
+        surface: *mut cairo_surface_t,
+        width: ::std::os::raw::c_int,
+        height: ::std::os::raw::c_int,
+    );
+}
@@ -0,0 +1,58 @@
+/* automatically generated by rust-bindgen 0.66.1 */
+
+pub const _cairo_ps_level_CAIRO_PS_LEVEL_2: _cairo_ps_level = 0;
+pub const _cairo_ps_level_CAIRO_PS_LEVEL_3: _cairo_ps_level = 1;
+pub type _cairo_ps_level = ::std::os::raw::c_uint;
+pub use self::_cairo_ps_level as cairo_ps_level_t;
+extern "C" {
+    pub fn cairo_ps_surface_create(
+        filename: *const ::std::os::raw::c_char,
+        width_in_points: f64,
+        height_in_points: f64,
+    ) -> *mut cairo_surface_t;
+}
+extern "C" {
+    pub fn cairo_ps_surface_create_for_stream(
+        write_func: cairo_write_func_t,
+        closure: *mut ::std::os::raw::c_void,
+        width_in_points: f64,
+        height_in_points: f64,
+    ) -> *mut cairo_surface_t;
+}
+extern "C" {
+    pub fn cairo_ps_surface_restrict_to_level(surface: *mut cairo_surface_t, level: cairo_ps_level_t);
+}
+extern "C" {
+    pub fn cairo_ps_get_levels(
+        levels: *mut *const cairo_ps_level_t,
+        num_levels: *mut ::std::os::raw::c_int,
+    );
+}
+extern "C" {
+    pub fn cairo_ps_level_to_string(level: cairo_ps_level_t) -> *const ::std::os::raw::c_char;
+}
+extern "C" {
+    pub fn cairo_ps_surface_set_eps(surface: *mut cairo_surface_t, eps: cairo_bool_t);
+}
+extern "C" {
+    pub fn cairo_ps_surface_get_eps(surface: *mut cairo_surface_t) -> cairo_bool_t;
+}
+extern "C" {
+    pub fn cairo_ps_surface_set_size(
+        surface: *mut cairo_surface_t,
+        width_in_points: f64,
+        height_in_points: f64,
+    );
+}
+extern "C" {
+    pub fn cairo_ps_surface_dsc_comment(
+        surface: *mut cairo_surface_t,
+        comment: *const ::std::os::raw::c_char,
+    );
+}
+extern "C" {
+    pub fn cairo_ps_surface_dsc_begin_setup(surface: *mut cairo_surface_t);
+}
+extern "C" {
+    pub fn cairo_ps_surface_dsc_begin_page_setup(surface: *mut cairo_surface_t);
+}
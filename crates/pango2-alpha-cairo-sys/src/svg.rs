@@ -0,0 +1,59 @@
+/* automatically generated by rust-bindgen 0.66.1 */
+
+pub const _cairo_svg_version_CAIRO_SVG_VERSION_1_1: _cairo_svg_version = 0;
+pub const _cairo_svg_version_CAIRO_SVG_VERSION_1_2: _cairo_svg_version = 1;
+pub type _cairo_svg_version = ::std::os::raw::c_uint;
+pub use self::_cairo_svg_version as cairo_svg_version_t;
+pub const _cairo_svg_unit_CAIRO_SVG_UNIT_USER: _cairo_svg_unit = 0;
+pub const _cairo_svg_unit_CAIRO_SVG_UNIT_EM: _cairo_svg_unit = 1;
+pub const _cairo_svg_unit_CAIRO_SVG_UNIT_EX: _cairo_svg_unit = 2;
+pub const _cairo_svg_unit_CAIRO_SVG_UNIT_PX: _cairo_svg_unit = 3;
+pub const _cairo_svg_unit_CAIRO_SVG_UNIT_IN: _cairo_svg_unit = 4;
+pub const _cairo_svg_unit_CAIRO_SVG_UNIT_CM: _cairo_svg_unit = 5;
+pub const _cairo_svg_unit_CAIRO_SVG_UNIT_MM: _cairo_svg_unit = 6;
+pub const _cairo_svg_unit_CAIRO_SVG_UNIT_PT: _cairo_svg_unit = 7;
+pub const _cairo_svg_unit_CAIRO_SVG_UNIT_PC: _cairo_svg_unit = 8;
+pub const _cairo_svg_unit_CAIRO_SVG_UNIT_PERCENT: _cairo_svg_unit = 9;
+pub type _cairo_svg_unit = ::std::os::raw::c_uint;
+pub use self::_cairo_svg_unit as cairo_svg_unit_t;
+extern "C" {
+    pub fn cairo_svg_surface_create(
+        filename: *const ::std::os::raw::c_char,
+        width_in_points: f64,
+        height_in_points: f64,
+    ) -> *mut cairo_surface_t;
+}
+extern "C" {
+    pub fn cairo_svg_surface_create_for_stream(
+        write_func: cairo_write_func_t,
+        closure: *mut ::std::os::raw::c_void,
+        width_in_points: f64,
+        height_in_points: f64,
+    ) -> *mut cairo_surface_t;
+}
+extern "C" {
+    pub fn cairo_svg_surface_restrict_to_version(
+        surface: *mut cairo_surface_t,
+        version: cairo_svg_version_t,
+    );
+}
+extern "C" {
+    pub fn cairo_svg_get_versions(
+        versions: *mut *const cairo_svg_version_t,
+        num_versions: *mut ::std::os::raw::c_int,
+    );
+}
+extern "C" {
+    pub fn cairo_svg_version_to_string(
+        version: cairo_svg_version_t,
+    ) -> *const ::std::os::raw::c_char;
+}
+extern "C" {
+    pub fn cairo_svg_surface_set_document_unit(
+        surface: *mut cairo_surface_t,
+        unit: cairo_svg_unit_t,
+    );
+}
+extern "C" {
+    pub fn cairo_svg_surface_get_document_unit(surface: *mut cairo_surface_t) -> cairo_svg_unit_t;
+}
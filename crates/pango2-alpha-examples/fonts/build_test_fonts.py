@@ -0,0 +1,762 @@
+#!/usr/bin/env python3
+"""Builds the small test fonts bundled with the examples.
+
+The fonts are drawn on a 16x16 pixel grid and only cover the characters the
+examples use. They are written from scratch with the standard library so they
+can be regenerated without any font tooling:
+
+    python3 fonts/build_test_fonts.py
+"""
+
+import os
+import struct
+
+UPEM = 1024
+CELL = 64
+ASCENDER = 896
+DESCENDER = -128
+
+HERE = os.path.dirname(os.path.abspath(__file__))
+COPYRIGHT = "Copyright 2026 The pango2-alpha-sys Authors"
+LICENSE = ("This Font Software is licensed under the SIL Open Font License, "
+           "Version 1.1. This license is available with a FAQ at: "
+           "https://openfontlicense.org")
+LICENSE_URL = "https://openfontlicense.org"
+
+
+# Outlines
+
+
+def trace(bitmap):
+    """Traces the filled pixels of `bitmap` into clockwise TrueType contours.
+
+    Row 0 is the top of the em box, every pixel is CELL units wide. Edges
+    shared by two pixels cancel out, so adjacent pixels form one contour and
+    holes come out counter-clockwise.
+    """
+    edges = {}
+    rows = len(bitmap)
+    for r, line in enumerate(bitmap):
+        for c, pixel in enumerate(line):
+            if pixel != "#":
+                continue
+            y = rows - 1 - r
+            square = [(c, y), (c, y + 1), (c + 1, y + 1), (c + 1, y)]
+            for i in range(4):
+                start, end = square[i], square[(i + 1) % 4]
+                if (end, start) in edges:
+                    del edges[(end, start)]
+                else:
+                    edges[(start, end)] = True
+
+    outgoing = {}
+    for start, end in edges:
+        outgoing.setdefault(start, []).append(end)
+
+    def turn(prev, point, candidate):
+        # prefer turning right so that pixels touching at a corner stay apart
+        dx1, dy1 = point[0] - prev[0], point[1] - prev[1]
+        dx2, dy2 = candidate[0] - point[0], candidate[1] - point[1]
+        return dx1 * dy2 - dy1 * dx2
+
+    contours = []
+    while outgoing:
+        start = min(outgoing)
+        points = [start]
+        prev, point = None, start
+        while True:
+            ends = outgoing[point]
+            if prev is not None and len(ends) > 1:
+                ends.sort(key=lambda end: turn(prev, point, end))
+            end = ends.pop(0)
+            if not ends:
+                del outgoing[point]
+            prev, point = point, end
+            if point == start:
+                break
+            points.append(point)
+
+        # drop the points in the middle of straight lines
+        simplified = []
+        for i, p in enumerate(points):
+            a, b = points[i - 1], points[(i + 1) % len(points)]
+            if (p[0] - a[0]) * (b[1] - p[1]) != (p[1] - a[1]) * (b[0] - p[0]):
+                simplified.append(p)
+        y0 = DESCENDER // CELL
+        contours.append([(x * CELL, (y + y0) * CELL) for x, y in simplified])
+    return contours
+
+
+def rotate(bitmap):
+    """Rotates a square bitmap by 90 degrees clockwise."""
+    size = len(bitmap)
+    return ["".join(bitmap[size - 1 - c][r] for c in range(size)) for r in range(size)]
+
+
+def shift(bitmap, dx, dy):
+    """Moves the pixels of a bitmap right by `dx` and down by `dy`."""
+    size = len(bitmap)
+    width = len(bitmap[0])
+    result = []
+    for r in range(size):
+        source = r - dy
+        line = bitmap[source] if 0 <= source < size else "." * width
+        result.append(("." * dx + line)[:width] if dx >= 0 else (line[-dx:] + "." * -dx))
+    return result
+
+
+class Glyph:
+    def __init__(self, name, bitmap, advance=UPEM, codepoint=None):
+        self.name = name
+        self.contours = trace(bitmap) if bitmap else []
+        self.advance = advance
+        self.codepoint = codepoint
+
+    def bounds(self):
+        points = [p for contour in self.contours for p in contour]
+        if not points:
+            return (0, 0, 0, 0)
+        xs = [p[0] for p in points]
+        ys = [p[1] for p in points]
+        return (min(xs), min(ys), max(xs), max(ys))
+
+    def compile(self):
+        if not self.contours:
+            return b""
+        x_min, y_min, x_max, y_max = self.bounds()
+        data = struct.pack(">hhhhh", len(self.contours), x_min, y_min, x_max, y_max)
+        end = -1
+        for contour in self.contours:
+            end += len(contour)
+            data += struct.pack(">H", end)
+        data += struct.pack(">H", 0)
+        points = [p for contour in self.contours for p in contour]
+        data += bytes([0x01] * len(points))
+        x = y = 0
+        for p in points:
+            data += struct.pack(">h", p[0] - x)
+            x = p[0]
+        for p in points:
+            data += struct.pack(">h", p[1] - y)
+            y = p[1]
+        return data + b"\0" * (-len(data) % 4)
+
+
+# Tables
+
+
+def utf16(text):
+    return text.encode("utf-16-be")
+
+
+def checksum(data):
+    data += b"\0" * (-len(data) % 4)
+    return sum(struct.unpack(">%dI" % (len(data) // 4), data)) & 0xFFFFFFFF
+
+
+def build_glyf(glyphs):
+    glyf = b""
+    loca = []
+    for glyph in glyphs:
+        loca.append(len(glyf))
+        glyf += glyph.compile()
+    loca.append(len(glyf))
+    return glyf, struct.pack(">%dI" % len(loca), *loca)
+
+
+def build_head(glyphs):
+    bounds = [g.bounds() for g in glyphs if g.contours]
+    return struct.pack(
+        ">IIIIHHqqhhhhHHhhh",
+        0x00010000, 0x00010000, 0, 0x5F0F3CF5, 0x0003, UPEM, 0, 0,
+        min(b[0] for b in bounds), min(b[1] for b in bounds),
+        max(b[2] for b in bounds), max(b[3] for b in bounds),
+        0, 8, 2, 1, 0,
+    )
+
+
+def build_hhea(glyphs):
+    bounds = [g.bounds() for g in glyphs]
+    return struct.pack(
+        ">IhhhHhhhhhhhhhhhH",
+        0x00010000, ASCENDER, DESCENDER, 0,
+        max(g.advance for g in glyphs),
+        min(b[0] for b in bounds),
+        min(g.advance - b[2] for g, b in zip(glyphs, bounds)),
+        max(b[2] for b in bounds),
+        1, 0, 0, 0, 0, 0, 0, 0, len(glyphs),
+    )
+
+
+def build_hmtx(glyphs):
+    return b"".join(struct.pack(">Hh", g.advance, g.bounds()[0]) for g in glyphs)
+
+
+def build_vhea(glyphs):
+    bounds = [g.bounds() for g in glyphs]
+    return struct.pack(
+        ">IhhhHhhhhhhhhhhhH",
+        0x00011000, UPEM // 2, -UPEM // 2, 0, UPEM,
+        min(ASCENDER - b[3] for b in bounds),
+        min(b[1] - DESCENDER for b in bounds),
+        max(ASCENDER - b[1] for b in bounds),
+        0, 1, 0, 0, 0, 0, 0, 0, len(glyphs),
+    )
+
+
+def build_vmtx(glyphs):
+    return b"".join(struct.pack(">Hh", UPEM, ASCENDER - g.bounds()[3] if g.contours else 0) for g in glyphs)
+
+
+def build_maxp(glyphs):
+    points = max(sum(len(c) for c in g.contours) for g in glyphs)
+    contours = max(len(g.contours) for g in glyphs)
+    return struct.pack(">IHHHHHHHHHHHHHH", 0x00010000, len(glyphs), points, contours, 0, 0, 2, 0, 0, 0, 0, 0, 0, 0, 0)
+
+
+def build_os2(glyphs, unicode_ranges):
+    codepoints = [g.codepoint for g in glyphs if g.codepoint is not None]
+    average = sum(g.advance for g in glyphs) // len(glyphs)
+    return struct.pack(
+        ">HhHHHhhhhhhhhhhh10sIIII4sHHHhhhHHIIhhHHH",
+        4, average, 400, 5, 0,
+        512, 512, 0, 128, 512, 512, 0, 384, 64, 320, 0,
+        b"\0" * 10, *unicode_ranges, b"NONE",
+        0x00C0, min(codepoints), min(max(codepoints), 0xFFFF),
+        ASCENDER, DESCENDER, 0, ASCENDER, -DESCENDER,
+        1 | 1 << 17, 0, 448, 704, 0, 32, 1,
+    )
+
+
+def build_name(family, extra=()):
+    names = [
+        (0, COPYRIGHT),
+        (1, family),
+        (2, "Regular"),
+        (3, "1.000;NONE;" + family.replace(" ", "") + "-Regular"),
+        (4, family + " Regular"),
+        (5, "Version 1.000"),
+        (6, family.replace(" ", "") + "-Regular"),
+        (13, LICENSE),
+        (14, LICENSE_URL),
+    ] + list(extra)
+    header = struct.pack(">HHH", 0, len(names), 6 + 12 * len(names))
+    records = b""
+    strings = b""
+    for name_id, text in names:
+        data = utf16(text)
+        records += struct.pack(">HHHHHH", 3, 1, 0x409, name_id, len(data), len(strings))
+        strings += data
+    return header + records + strings
+
+
+def build_post():
+    return struct.pack(">IIhhIIIII", 0x00030000, 0, -96, 64, 0, 0, 0, 0, 0)
+
+
+def build_cmap(glyphs):
+    mapping = sorted((g.codepoint, gid) for gid, g in enumerate(glyphs) if g.codepoint is not None)
+
+    # runs of consecutive code points mapped to consecutive glyphs
+    groups = []
+    for codepoint, gid in mapping:
+        if groups and groups[-1][1] + 1 == codepoint and groups[-1][2] + codepoint - groups[-1][0] == gid:
+            groups[-1][1] = codepoint
+        else:
+            groups.append([codepoint, codepoint, gid])
+
+    segments = [g for g in groups if g[1] <= 0xFFFF] + [[0xFFFF, 0xFFFF, 0]]
+    count = len(segments)
+    search = 2 ** (count.bit_length() - 1)
+    format4 = struct.pack(">HHHH", count * 2, search * 2, search.bit_length() - 1, count * 2 - search * 2)
+    format4 += b"".join(struct.pack(">H", s[1]) for s in segments) + struct.pack(">H", 0)
+    format4 += b"".join(struct.pack(">H", s[0]) for s in segments)
+    format4 += b"".join(struct.pack(">H", (s[2] - s[0]) & 0xFFFF if s[2] else 1) for s in segments)
+    format4 += b"\0\0" * count
+    format4 = struct.pack(">HHH", 4, 6 + len(format4), 0) + format4
+
+    format12 = struct.pack(">HHIII", 12, 0, 16 + 12 * len(groups), 0, len(groups))
+    format12 += b"".join(struct.pack(">III", *g) for g in groups)
+
+    header = struct.pack(">HH", 0, 2)
+    header += struct.pack(">HHI", 3, 1, 4 + 16)
+    header += struct.pack(">HHI", 3, 10, 4 + 16 + len(format4))
+    return header + format4 + format12
+
+
+def build_gsub(scripts, features, substitutions):
+    """Builds a GSUB table with a single substitution lookup that every
+    feature in `features` uses for every script in `scripts`."""
+    lang_sys = struct.pack(">HHH", 0, 0xFFFF, len(features)) + b"".join(struct.pack(">H", i) for i in range(len(features)))
+    script = struct.pack(">HH", 4, 0) + lang_sys
+    script_list = struct.pack(">H", len(scripts))
+    offset = 2 + 6 * len(scripts)
+    for tag in scripts:
+        script_list += struct.pack(">4sH", tag, offset + len(script) * scripts.index(tag))
+    script_list += script * len(scripts)
+
+    feature = struct.pack(">HHH", 0, 1, 0)
+    feature_list = struct.pack(">H", len(features))
+    offset = 2 + 6 * len(features)
+    for i, tag in enumerate(features):
+        feature_list += struct.pack(">4sH", tag, offset + len(feature) * i)
+    feature_list += feature * len(features)
+
+    covered = sorted(substitutions)
+    coverage = struct.pack(">HH", 1, len(covered)) + b"".join(struct.pack(">H", g) for g in covered)
+    subtable = struct.pack(">HHH", 2, 6 + 2 * len(covered), len(covered))
+    subtable += b"".join(struct.pack(">H", substitutions[g]) for g in covered) + coverage
+    lookup = struct.pack(">HHHH", 1, 0, 1, 8) + subtable
+    lookup_list = struct.pack(">HH", 1, 4) + lookup
+
+    header_size = 10
+    return struct.pack(
+        ">HHHHH", 1, 0, header_size, header_size + len(script_list),
+        header_size + len(script_list) + len(feature_list),
+    ) + script_list + feature_list + lookup_list
+
+
+def write_font(path, tables):
+    tags = sorted(tables)
+    count = len(tags)
+    search = 2 ** (count.bit_length() - 1)
+    header = struct.pack(">IHHHH", 0x00010000, count, search * 16, search.bit_length() - 1, count * 16 - search * 16)
+    offset = len(header) + 16 * count
+    directory = b""
+    data = b""
+    for tag in tags:
+        table = tables[tag]
+        directory += struct.pack(">4sIII", tag, checksum(table), offset + len(data), len(table))
+        data += table + b"\0" * (-len(table) % 4)
+    font = bytearray(header + directory + data)
+    head = offset + sum(len(tables[t]) + (-len(tables[t]) % 4) for t in tags[:tags.index(b"head")])
+    struct.pack_into(">I", font, head + 8, (0xB1B0AFBA - checksum(bytes(font))) & 0xFFFFFFFF)
+    os.makedirs(os.path.dirname(path), exist_ok=True)
+    with open(path, "wb") as f:
+        f.write(font)
+
+
+def common_tables(family, glyphs, unicode_ranges, extra_names=()):
+    glyf, loca = build_glyf(glyphs)
+    return {
+        b"head": build_head(glyphs),
+        b"hhea": build_hhea(glyphs),
+        b"hmtx": build_hmtx(glyphs),
+        b"maxp": build_maxp(glyphs),
+        b"OS/2": build_os2(glyphs, unicode_ranges),
+        b"name": build_name(family, extra_names),
+        b"cmap": build_cmap(glyphs),
+        b"post": build_post(),
+        b"glyf": glyf,
+        b"loca": loca,
+    }
+
+
+# Tategaki Test: a CJK font with vertical alternates
+
+NOTDEF = [
+    "................",
+    "..############..",
+    "..#..........#..",
+    "..#..........#..",
+    "..#..........#..",
+    "..#..........#..",
+    "..#..........#..",
+    "..#..........#..",
+    "..#..........#..",
+    "..#..........#..",
+    "..#..........#..",
+    "..#..........#..",
+    "..#..........#..",
+    "..#..........#..",
+    "..############..",
+    "................",
+]
+
+IDEOGRAPHS = {
+    0x65E5: [  # 日
+        "................",
+        "...##########...",
+        "...##......##...",
+        "...##......##...",
+        "...##......##...",
+        "...##......##...",
+        "...##......##...",
+        "...##########...",
+        "...##......##...",
+        "...##......##...",
+        "...##......##...",
+        "...##......##...",
+        "...##......##...",
+        "...##########...",
+        "................",
+        "................",
+    ],
+    0x672C: [  # 本
+        ".......##.......",
+        ".......##.......",
+        ".##############.",
+        ".......##.......",
+        "......####......",
+        ".....#.##.#.....",
+        "....#..##..#....",
+        "...#...##...#...",
+        "..#....##....#..",
+        ".#.....##.....#.",
+        "....########....",
+        ".......##.......",
+        ".......##.......",
+        ".......##.......",
+        ".......##.......",
+        "................",
+    ],
+    0x4E00: [  # 一
+        "................",
+        "................",
+        "................",
+        "................",
+        "................",
+        "................",
+        "................",
+        ".##############.",
+        ".##############.",
+        "................",
+        "................",
+        "................",
+        "................",
+        "................",
+        "................",
+        "................",
+    ],
+    0x306E: [  # の
+        "................",
+        "................",
+        ".....######.....",
+        "...##..##..##...",
+        "..##...##...##..",
+        ".##....##....##.",
+        ".##....##....##.",
+        ".##...##.....##.",
+        ".##...##.....##.",
+        ".##..##......##.",
+        "..####......##..",
+        "...##......##...",
+        "..........##....",
+        "................",
+        "................",
+        "................",
+    ],
+    0x5C71: [  # 山
+        ".......##.......",
+        ".......##.......",
+        ".......##.......",
+        ".##....##....##.",
+        ".##....##....##.",
+        ".##....##....##.",
+        ".##....##....##.",
+        ".##....##....##.",
+        ".##....##....##.",
+        ".##....##....##.",
+        ".##############.",
+        ".##############.",
+        "................",
+        "................",
+        "................",
+        "................",
+    ],
+}
+
+# punctuation whose vertical form is the horizontal one turned by 90 degrees
+BRACKETS = {
+    0x300C: [  # 「
+        "................",
+        "........######..",
+        "........##......",
+        "........##......",
+        "........##......",
+        "........##......",
+        "........##......",
+        "........##......",
+        "................",
+        "................",
+        "................",
+        "................",
+        "................",
+        "................",
+        "................",
+        "................",
+    ],
+    0x300D: [  # 」
+        "................",
+        "................",
+        "................",
+        "................",
+        "................",
+        "................",
+        "................",
+        "......##........",
+        "......##........",
+        "......##........",
+        "......##........",
+        "......##........",
+        "......##........",
+        "..######........",
+        "................",
+        "................",
+    ],
+}
+
+# punctuation whose vertical form moves to the upper right of the em box
+MARKS = {
+    0x3001: [  # 、
+        "................",
+        "................",
+        "................",
+        "................",
+        "................",
+        "................",
+        "................",
+        "................",
+        "................",
+        "................",
+        "................",
+        "..##............",
+        "...##...........",
+        "....##..........",
+        "................",
+        "................",
+    ],
+    0x3002: [  # 。
+        "................",
+        "................",
+        "................",
+        "................",
+        "................",
+        "................",
+        "................",
+        "................",
+        "................",
+        "................",
+        "..####..........",
+        ".##..##.........",
+        ".##..##.........",
+        "..####..........",
+        "................",
+        "................",
+    ],
+}
+
+LATIN = {
+    ord("P"): [
+        "........", "........", "........",
+        ".#####..", ".##..##.", ".##..##.", ".##..##.", ".#####..",
+        ".##.....", ".##.....", ".##.....", ".##.....", ".##.....", ".##.....",
+        "........", "........",
+    ],
+    ord("a"): [
+        "........", "........", "........", "........", "........", "........", "........",
+        "..####..", ".....##.", "..#####.", ".##..##.", ".##..##.", ".##..##.", "..###.##",
+        "........", "........",
+    ],
+    ord("n"): [
+        "........", "........", "........", "........", "........", "........", "........",
+        ".#.###..", ".##..##.", ".##..##.", ".##..##.", ".##..##.", ".##..##.", ".##..##.",
+        "........", "........",
+    ],
+    ord("g"): [
+        "........", "........", "........", "........", "........", "........", "........",
+        "..#####.", ".##..##.", ".##..##.", ".##..##.", ".##..##.", "..#####.", ".....##.",
+        ".##..##.", "..####..",
+    ],
+    ord("o"): [
+        "........", "........", "........", "........", "........", "........", "........",
+        "..####..", ".##..##.", ".##..##.", ".##..##.", ".##..##.", ".##..##.", "..####..",
+        "........", "........",
+    ],
+    ord("2"): [
+        "........", "........", "........",
+        "..####..", ".##..##.", ".....##.", ".....##.", "....##..", "...##...",
+        "..##....", ".##.....", ".##.....", ".##.....", ".######.",
+        "........", "........",
+    ],
+}
+
+
+def build_tategaki():
+    glyphs = [Glyph(".notdef", NOTDEF), Glyph("space", None, UPEM // 2, 0x20)]
+    for codepoint, bitmap in sorted(LATIN.items()):
+        glyphs.append(Glyph("uni%04X" % codepoint, bitmap, UPEM // 2, codepoint))
+    for codepoint, bitmap in sorted(IDEOGRAPHS.items()):
+        glyphs.append(Glyph("uni%04X" % codepoint, bitmap, UPEM, codepoint))
+
+    vertical = {}
+    for codepoint, bitmap in sorted(list(BRACKETS.items()) + list(MARKS.items())):
+        glyphs.append(Glyph("uni%04X" % codepoint, bitmap, UPEM, codepoint))
+        vertical[len(glyphs) - 1] = rotate(bitmap) if codepoint in BRACKETS else shift(bitmap, 9, -9)
+    substitutions = {}
+    for gid, bitmap in sorted(vertical.items()):
+        glyphs.append(Glyph(glyphs[gid].name + ".vert", bitmap, UPEM))
+        substitutions[gid] = len(glyphs) - 1
+
+    # Basic Latin, CJK Symbols and Punctuation, Hiragana, CJK Unified Ideographs
+    tables = common_tables("Tategaki Test", glyphs, (1 << 0, 1 << 16 | 1 << 17 | 1 << 27, 0, 0))
+    tables[b"vhea"] = build_vhea(glyphs)
+    tables[b"vmtx"] = build_vmtx(glyphs)
+    tables[b"GSUB"] = build_gsub([b"DFLT", b"hani", b"kana"], [b"vert", b"vrt2"], substitutions)
+    write_font(os.path.join(HERE, "TategakiTest", "TategakiTest-Regular.ttf"), tables)
+
+
+# Color Test: a color font with COLRv0 and COLRv1 glyphs and two palettes
+
+
+def pixels(inside):
+    """Draws a 16x16 bitmap of the pixels whose center satisfies `inside`."""
+    return ["".join("#" if inside(c + 0.5, r + 0.5) else "." for c in range(16)) for r in range(16)]
+
+
+def union(bitmaps):
+    return ["".join("#" if any(b[r][c] == "#" for b in bitmaps) else "." for c in range(16)) for r in range(16)]
+
+
+def ring(cx, cy, inner, outer):
+    return lambda x, y: y <= cy and inner ** 2 <= (x - cx) ** 2 + (y - cy) ** 2 < outer ** 2
+
+
+PALETTE_ENTRIES = ["Face", "Ink", "Accent", "Green", "Blue", "Violet"]
+PALETTES = [
+    # name, flags (1: usable with light backgrounds, 2: with dark backgrounds), colors
+    ("Light", 1, [0xFFCC33, 0x4A2C0A, 0xE63946, 0x2A9D8F, 0x3A86FF, 0x8338EC]),
+    ("Dark", 2, [0xE0A800, 0x2B1A05, 0xFF6B6B, 0x52B788, 0x74A7FF, 0xB185F5]),
+]
+
+# the layers of every color glyph from bottom to top as (bitmap, palette index)
+COLOR_GLYPHS = {
+    0x1F600: [  # 😀
+        (pixels(lambda x, y: (x - 8) ** 2 + (y - 8) ** 2 < 7.5 ** 2), 0),
+        (pixels(lambda x, y: 4 <= y < 7 and (5 <= x < 7 or 9 <= x < 11)), 1),
+        (pixels(lambda x, y: y >= 9 and 3 ** 2 <= (x - 8) ** 2 + (y - 8) ** 2 < 5 ** 2), 1),
+    ],
+    0x1F389: [  # 🎉
+        (pixels(lambda x, y: x >= 1 and y < 15 and x - 1 < 15 - y and y - x > 2), 2),
+        (pixels(lambda x, y: (x - 9) ** 2 + (y - 3) ** 2 < 2), 3),
+        (pixels(lambda x, y: (x - 13) ** 2 + (y - 6) ** 2 < 2), 4),
+        (pixels(lambda x, y: (x - 12) ** 2 + (y - 11) ** 2 < 2), 5),
+    ],
+    0x1F308: [  # 🌈
+        (pixels(ring(8, 13, 6, 8)), 2),
+        (pixels(ring(8, 13, 4.5, 6)), 0),
+        (pixels(ring(8, 13, 3, 4.5)), 3),
+        (pixels(ring(8, 13, 1.5, 3)), 4),
+    ],
+}
+
+# the glyphs that also have a COLRv1 paint graph
+PAINTED = {0x1F308}
+
+
+def build_colr(base_glyphs, painted):
+    """Builds a COLR version 1 table.
+
+    `base_glyphs` maps every color glyph to its layers as (glyph, palette
+    index) pairs, which are written as COLRv0 layer records. The glyphs in
+    `painted` also get a PaintColrLayers graph of PaintGlyph and PaintSolid
+    paints, which renderers with COLRv1 support use instead.
+    """
+    v0 = sorted(base_glyphs)
+    v1 = sorted(g for g in base_glyphs if g in painted)
+
+    header_size = 34
+    base_records = b""
+    layer_records = b""
+    layer_count = 0
+    for glyph in v0:
+        base_records += struct.pack(">HHH", glyph, layer_count, len(base_glyphs[glyph]))
+        for layer, palette_index in base_glyphs[glyph]:
+            layer_records += struct.pack(">HH", layer, palette_index)
+            layer_count += 1
+
+    # every layer is a PaintGlyph followed by its PaintSolid
+    paints = []
+    colr_layers = b""
+    for glyph in v1:
+        colr_layers += struct.pack(">BBI", 1, len(base_glyphs[glyph]), len(paints))
+        for layer, palette_index in base_glyphs[glyph]:
+            paint_glyph = struct.pack(">B", 10) + (6).to_bytes(3, "big") + struct.pack(">H", layer)
+            paints.append(paint_glyph + struct.pack(">BHh", 2, palette_index, 0x4000))
+    layer_list = struct.pack(">I", len(paints))
+    offset = 4 + 4 * len(paints)
+    for paint in paints:
+        layer_list += struct.pack(">I", offset)
+        offset += len(paint)
+    layer_list += b"".join(paints)
+
+    base_glyph_list = struct.pack(">I", len(v1))
+    offset = 4 + 6 * len(v1)
+    for i, glyph in enumerate(v1):
+        base_glyph_list += struct.pack(">HI", glyph, offset + 6 * i)
+    base_glyph_list += colr_layers
+
+    base_records_offset = header_size
+    layer_records_offset = base_records_offset + len(base_records)
+    base_glyph_list_offset = layer_records_offset + len(layer_records)
+    layer_list_offset = base_glyph_list_offset + len(base_glyph_list)
+    return struct.pack(
+        ">HHIIHIIIII", 1, len(v0), base_records_offset, layer_records_offset, layer_count,
+        base_glyph_list_offset, layer_list_offset, 0, 0, 0,
+    ) + base_records + layer_records + base_glyph_list + layer_list
+
+
+def build_cpal(palettes, first_name_id):
+    """Builds a CPAL version 1 table with named palettes and entries.
+
+    The palette names get the name IDs from `first_name_id` on, followed by
+    the names of the entries."""
+    entries = len(PALETTE_ENTRIES)
+    header_size = 12 + 2 * len(palettes) + 12
+    records = b""
+    for _, _, colors in palettes:
+        for color in colors:
+            records += struct.pack(">BBBB", color & 0xFF, (color >> 8) & 0xFF, color >> 16, 0xFF)
+    types = b"".join(struct.pack(">I", flags) for _, flags, _ in palettes)
+    labels = b"".join(struct.pack(">H", first_name_id + i) for i in range(len(palettes)))
+    entry_labels = b"".join(struct.pack(">H", first_name_id + len(palettes) + i) for i in range(entries))
+
+    types_offset = header_size + len(records)
+    labels_offset = types_offset + len(types)
+    entry_labels_offset = labels_offset + len(labels)
+    header = struct.pack(">HHHHI", 1, entries, len(palettes), entries * len(palettes), header_size)
+    header += b"".join(struct.pack(">H", entries * i) for i in range(len(palettes)))
+    header += struct.pack(">III", types_offset, labels_offset, entry_labels_offset)
+    return header + records + types + labels + entry_labels
+
+
+def build_color():
+    glyphs = [Glyph(".notdef", NOTDEF), Glyph("space", None, UPEM // 2, 0x20)]
+    for codepoint, layers in sorted(COLOR_GLYPHS.items()):
+        glyphs.append(Glyph("u%X" % codepoint, union([bitmap for bitmap, _ in layers]), UPEM, codepoint))
+
+    base_glyphs = {}
+    painted = set()
+    for codepoint, layers in sorted(COLOR_GLYPHS.items()):
+        base = next(gid for gid, g in enumerate(glyphs) if g.codepoint == codepoint)
+        base_glyphs[base] = []
+        for i, (bitmap, palette_index) in enumerate(layers):
+            glyphs.append(Glyph("u%X.layer%d" % (codepoint, i), bitmap, UPEM))
+            base_glyphs[base].append((len(glyphs) - 1, palette_index))
+        if codepoint in PAINTED:
+            painted.add(base)
+
+    first_name_id = 256
+    names = [(first_name_id + i, name) for i, (name, _, _) in enumerate(PALETTES)]
+    names += [(first_name_id + len(PALETTES) + i, name) for i, name in enumerate(PALETTE_ENTRIES)]
+
+    # Basic Latin, Non-Plane 0
+    tables = common_tables("Color Test", glyphs, (1 << 0, 1 << 25, 0, 0), names)
+    tables[b"COLR"] = build_colr(base_glyphs, painted)
+    tables[b"CPAL"] = build_cpal(PALETTES, first_name_id)
+    write_font(os.path.join(HERE, "ColorTest", "ColorTest-Regular.ttf"), tables)
+
+
+if __name__ == "__main__":
+    build_tategaki()
+    build_color()
@@ -0,0 +1,63 @@
+use std::{env, path::Path, process};
+use pango2_sys_examples::{freetype, harfbuzz, pango2, cairo};
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    let filename = if args.len() > 1 {
+        &args[1]
+    } else {
+        "./fonts/NotoSerifDisplay/NotoSerifDisplay-VariableFont_wdth,wght.ttf"
+    };
+
+    if !Path::new(filename).exists() {
+        eprintln!("Error: provided font file '{}' does not exist, specify the location to the font as an argument", filename);
+        process::exit(1);
+    }
+
+    let lib = freetype::Library::init().unwrap();
+    let face = lib.face_from_file(filename, 0).unwrap();
+    let hb_face = harfbuzz::Face::from_ft(&face);
+    let font_map = pango2::Pango2FontMap::new();
+    font_map.add_face(&pango2::Pango2HbFace::from_hb_face(&hb_face, -2));
+    let pango_context = pango2::Pango2Context::from_font_map(&font_map);
+
+    let (width, height) = (650, 150);
+    let format = cairo::Format::A8;
+    println!("A8 stride for {} pixels: {:?}", width, format.stride_for_width(width));
+
+    let mut cairo_surface = cairo::CairoSurface::new_image_surface_with_format(format, width, height).unwrap();
+    let cairo_context = cairo::CairoContext::create(&cairo_surface);
+    pango_context.update_cairo_context(&cairo_context);
+
+    let layout = pango2::Pango2Layout::new(&pango_context);
+    layout.set_text("Hola, Pango2!");
+    layout.set_font_description_string(format!("{} Regular 64", face.face_name()).as_str());
+    layout.paint(&cairo_context);
+    drop(cairo_context);
+
+    let stride = cairo_surface.stride();
+    let data = cairo_surface.data().unwrap();
+    let coverage: Vec<u8> = data
+        .chunks(stride)
+        .flat_map(|row| row[..width as usize].iter().copied())
+        .collect();
+    let covered = coverage.iter().filter(|alpha| **alpha > 0).count();
+    let solid = coverage.iter().filter(|alpha| **alpha == 0xff).count();
+    let ratio = covered as f32 / coverage.len() as f32;
+    println!("Covered pixels: {} ({:.1}%), fully covered: {}", covered, ratio * 100.0, solid);
+
+    let output = "a8_mask_test.png";
+    println!("Write glyph mask to file: {}", output);
+    cairo_surface.write_to_png(output).unwrap();
+
+    if cairo_surface.format() != cairo::Format::A8 {
+        eprintln!("Error: the surface is not an A8 surface");
+        process::exit(1);
+    }
+    // the text covers a part of the surface, with solid stems and
+    // antialiased edges
+    if !(0.02..0.6).contains(&ratio) || solid == 0 || solid == covered {
+        eprintln!("Error: unexpected coverage of the rendered text");
+        process::exit(1);
+    }
+}
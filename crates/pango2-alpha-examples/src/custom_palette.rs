@@ -0,0 +1,90 @@
+use std::{env, path::Path, process};
+use pango2_sys_examples::{freetype, harfbuzz, pango2, cairo};
+
+/// The color replacing palette entry 2 ("Accent") of the test font.
+const BRAND_BLUE: harfbuzz::Color = harfbuzz::Color { red: 0x00, green: 0x52, blue: 0xcc, alpha: 0xff };
+
+/// Draws `text` at 64 pixels on a white surface.
+fn render(context: &pango2::Pango2Context, family: &str, text: &str, attributes: &pango2::AttrList) -> cairo::CairoSurface {
+    let cairo_surface = cairo::CairoSurface::new_image_surface(200, 100).unwrap();
+    let cairo_context = cairo::CairoContext::create(&cairo_surface);
+
+    context.update_cairo_context(&cairo_context);
+
+    cairo_context.set_source_rgb(1.0, 1.0, 1.0);
+    cairo_context.paint();
+
+    let layout = pango2::Pango2Layout::new(context);
+    layout.set_text(text);
+    layout.set_font_description_string(format!("{} 64", family).as_str());
+    layout.set_attributes(attributes);
+
+    cairo_context.set_source_rgb(0.0, 0.0, 0.0);
+    layout.paint(&cairo_context);
+    cairo_surface
+}
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    let filename = if args.len() > 1 {
+        &args[1]
+    } else {
+        "./fonts/ColorTest/ColorTest-Regular.ttf"
+    };
+
+    if !Path::new(filename).exists() {
+        eprintln!("Error: provided font file '{}' does not exist", filename);
+        process::exit(1);
+    }
+
+    println!("Loading font file: {}", filename);
+
+    let lib = freetype::Library::init().unwrap();
+    let face = lib.face_from_file(filename, 0).unwrap();
+    let family = face.face_name();
+
+    println!("Family name: {}", family);
+
+    let hb_face = harfbuzz::Face::from_ft(&face);
+    let accent = hb_face.palette_colors(0)[2];
+    println!("Palette entry 2: {:?}", accent);
+
+    // add a copy of the first palette with entry 2 in the brand color, the
+    // themed face replaces the original one in the font map
+    let brand_palette = hb_face.palettes().len() as u32;
+    let themed_face = hb_face
+        .with_custom_palettes(&[harfbuzz::CustomPalette { base: 0, colors: vec![(2, BRAND_BLUE)] }])
+        .unwrap();
+    let pango2_face = pango2::Pango2HbFace::from_hb_face(&themed_face, -2);
+    pango2_face.set_palette_name("brand", brand_palette);
+
+    let font_map = pango2::Pango2FontMap::new();
+    font_map.add_face(&pango2_face);
+
+    let pango_context = pango2::Pango2Context::from_font_map(&font_map);
+
+    // per range: only the second rainbow uses the brand palette
+    let attributes = pango2::AttrList::new();
+    attributes.insert(pango2::Attribute::palette("brand").with_range(4, 8));
+    let surface = render(&pango_context, &family, "🌈🌈", &attributes);
+
+    let output = "custom_palette_test.png";
+    println!("Write rendered text to file: {}", output);
+    surface.write_to_png(output).unwrap();
+
+    // per context: the override applies to everything drawn with the context
+    let options = cairo::FontOptions::new();
+    options.set_custom_palette_color(
+        2,
+        BRAND_BLUE.red as f32 / 255.0,
+        BRAND_BLUE.green as f32 / 255.0,
+        BRAND_BLUE.blue as f32 / 255.0,
+        1.0,
+    );
+    pango_context.set_font_options(&options);
+    let surface = render(&pango_context, &family, "🌈", &pango2::AttrList::new());
+
+    let output = "custom_palette_context_test.png";
+    println!("Write rendered text to file: {}", output);
+    surface.write_to_png(output).unwrap();
+}
@@ -0,0 +1,85 @@
+use std::{env, path::Path, process};
+use pango2_sys_examples::{freetype, harfbuzz, pango2};
+
+/// A call received by the recording renderer.
+#[derive(Debug, Clone, PartialEq)]
+enum Call {
+    PrepareRun { foreground: Option<pango2::Color>, glyphs: usize },
+    DrawGlyphs { glyphs: Vec<pango2::GlyphInfo>, x: i32, y: i32 },
+    DrawRectangle { part: pango2::RenderPart, rect: pango2::Rectangle },
+    DrawStyledLine { part: pango2::RenderPart, style: pango2::LineStyle, rect: pango2::Rectangle },
+    DrawTrapezoid { part: pango2::RenderPart, trapezoid: pango2::Trapezoid },
+}
+
+/// Records every call, e.g. to build a scene graph from it later, the
+/// calls are checked in tests/custom_renderer.rs.
+#[derive(Default)]
+struct RecordingRenderer {
+    calls: Vec<Call>,
+}
+
+impl pango2::Renderer for RecordingRenderer {
+    fn prepare_run(&mut self, run: &pango2::RenderRun<'_>) {
+        self.calls.push(Call::PrepareRun {
+            foreground: run.color(pango2::RenderPart::Foreground),
+            glyphs: run.glyphs().len(),
+        });
+    }
+
+    fn draw_glyphs(&mut self, _font: &harfbuzz::Font, glyphs: &[pango2::GlyphInfo], x: i32, y: i32) {
+        self.calls.push(Call::DrawGlyphs { glyphs: glyphs.to_vec(), x, y });
+    }
+
+    fn draw_rectangle(&mut self, part: pango2::RenderPart, rect: pango2::Rectangle) {
+        self.calls.push(Call::DrawRectangle { part, rect });
+    }
+
+    fn draw_styled_line(&mut self, part: pango2::RenderPart, style: pango2::LineStyle, rect: pango2::Rectangle) {
+        self.calls.push(Call::DrawStyledLine { part, style, rect });
+    }
+
+    fn draw_trapezoid(&mut self, part: pango2::RenderPart, trapezoid: pango2::Trapezoid) {
+        self.calls.push(Call::DrawTrapezoid { part, trapezoid });
+    }
+}
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    let filename = if args.len() > 1 {
+        &args[1]
+    } else {
+        "./fonts/NotoSerifDisplay/NotoSerifDisplay-VariableFont_wdth,wght.ttf"
+    };
+
+    if !Path::new(filename).exists() {
+        eprintln!("Error: provided font file '{}' does not exist, specify the location to the font as an argument", filename);
+        process::exit(1);
+    }
+
+    let lib = freetype::Library::init().unwrap();
+    let face = lib.face_from_file(filename, 0).unwrap();
+    let hb_face = harfbuzz::Face::from_ft(&face);
+    let font_map = pango2::Pango2FontMap::new();
+    font_map.add_face(&pango2::Pango2HbFace::from_hb_face(&hb_face, -2));
+    let pango_context = pango2::Pango2Context::from_font_map(&font_map);
+
+    // underlined "Hola", a red "Pango2" on a yellow background
+    let red = pango2::Color::parse("#c01c28").unwrap();
+    let yellow = pango2::Color::parse("#f6d32d").unwrap();
+    let layout = pango2::Pango2Layout::new(&pango_context);
+    layout.set_text("Hola, Pango2!");
+    layout.set_font_description_string(format!("{} Regular 32", face.face_name()).as_str());
+    let attributes = pango2::AttrList::new();
+    attributes.insert(pango2::Attribute::underline(pango2::LineStyle::Solid).with_range(0, 4));
+    attributes.insert(pango2::Attribute::foreground(red).with_range(6, 12));
+    attributes.insert(pango2::Attribute::background(yellow).with_range(6, 12));
+    layout.set_attributes(&attributes);
+
+    let mut renderer = pango2::CustomRenderer::new(RecordingRenderer::default());
+    renderer.draw_layout(&layout, 0, 0);
+    let calls = &renderer.renderer().calls;
+    println!("Recorded {} calls", calls.len());
+    for call in calls {
+        println!("  {:?}", call);
+    }
+}
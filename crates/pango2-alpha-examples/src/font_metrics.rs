@@ -0,0 +1,90 @@
+use std::{env, path::Path, process};
+use pango2_sys_examples::{freetype, harfbuzz, pango2, cairo};
+
+fn to_pixels(units: i32) -> f32 {
+    units as f32 / pango2::SCALE as f32
+}
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    let filename = if args.len() > 1 {
+        &args[1]
+    } else {
+        "./fonts/NotoSerifDisplay/NotoSerifDisplay-VariableFont_wdth,wght.ttf"
+    };
+
+    if !Path::new(filename).exists() {
+        eprintln!("Error: provided font file '{}' does not exist, specify the location to the font as an argument", filename);
+        process::exit(1);
+    }
+
+    let lib = freetype::Library::init().unwrap();
+    let face = lib.face_from_file(filename, 0).unwrap();
+    let hb_face = harfbuzz::Face::from_ft(&face);
+    let font_map = pango2::Pango2FontMap::new();
+    font_map.add_face(&pango2::Pango2HbFace::from_hb_face(&hb_face, -2));
+    let pango_context = pango2::Pango2Context::from_font_map(&font_map);
+
+    let description = pango2::FontDescription::from_string(&format!("{} Regular 24", face.face_name()));
+    let metrics = pango_context.metrics(&description, pango2::Language::from_string("en"));
+    println!("Metrics of {}: {:#?}", description, metrics);
+
+    let font = match pango_context.load_font(&description) {
+        Some(font) => font,
+        None => {
+            eprintln!("Error: no font loaded for {}", description);
+            process::exit(1);
+        }
+    };
+    println!("Loaded font: {}, size {}", font.describe(), to_pixels(font.size()));
+
+    let layout = pango2::Pango2Layout::new(&pango_context);
+    layout.set_text("Hxg aligned");
+    layout.set_font_description_string(&description.to_string());
+
+    let runs = layout.glyph_runs();
+    let (run, glyph) = match runs.first().and_then(|run| Some((run, run.glyphs.first()?))) {
+        Some(first) => first,
+        None => {
+            eprintln!("Error: the layout has no glyphs");
+            process::exit(1);
+        }
+    };
+    let (ink, logical) = font.glyph_extents(glyph.glyph);
+    println!("Extents of the 'H' glyph: ink {:?}, logical {:?}", ink, logical);
+
+    let (_, layout_logical) = layout.extents();
+    let (width, height) = (to_pixels(layout_logical.width) as i32 + 80, 80);
+    let cairo_surface = cairo::CairoSurface::new_image_surface(width, height).unwrap();
+    let cairo_context = cairo::CairoContext::create(&cairo_surface);
+    pango_context.update_cairo_context(&cairo_context);
+    cairo_context.set_source_rgb(1.0, 1.0, 1.0);
+    cairo_context.paint();
+
+    let (x0, y0) = (10.0, 10.0);
+    cairo_context.set_source_rgb(0.0, 0.0, 0.0);
+    cairo_context.move_to(x0, y0);
+    layout.paint(&cairo_context);
+
+    // an icon as tall as the capitals, standing on the baseline after the text
+    let baseline = y0 + to_pixels(run.baseline);
+    let cap_height = to_pixels(-ink.y);
+    let icon_x = x0 + to_pixels(layout_logical.width) + to_pixels(metrics.approximate_char_width) / 2.0;
+    cairo_context.set_source_rgb(0.2, 0.4, 0.9);
+    cairo_context.rectangle(icon_x, baseline - cap_height, cap_height, cap_height);
+    cairo_context.fill();
+
+    // underline the text where the font wants it
+    cairo_context.set_source_rgb(0.9, 0.2, 0.2);
+    cairo_context.rectangle(
+        x0,
+        baseline - to_pixels(metrics.underline_position),
+        to_pixels(layout_logical.width),
+        to_pixels(metrics.underline_thickness),
+    );
+    cairo_context.fill();
+
+    let output = "font_metrics_test.png";
+    println!("Write aligned text to file: {}", output);
+    cairo_surface.write_to_png(output).unwrap();
+}
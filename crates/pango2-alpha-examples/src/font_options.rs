@@ -0,0 +1,124 @@
+use std::{env, path::Path, process};
+use pango2_sys_examples::{freetype, harfbuzz, pango2, cairo};
+
+// Counts pixels of the row band [y0, y1) that are partially covered, and
+// pixels that are covered at all.
+fn coverage(surface: &mut cairo::CairoSurface, width: usize, y0: usize, y1: usize) -> (usize, usize) {
+    let stride = surface.stride();
+    let data = surface.data().unwrap();
+    let band = data[y0 * stride..y1 * stride]
+        .chunks(stride)
+        .flat_map(|row| row[..width].iter().copied());
+    band.fold((0, 0), |(partial, covered), alpha| {
+        (
+            partial + (alpha > 0 && alpha < 0xff) as usize,
+            covered + (alpha > 0) as usize,
+        )
+    })
+}
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    let filename = if args.len() > 1 {
+        &args[1]
+    } else {
+        "./fonts/NotoSerifDisplay/NotoSerifDisplay-VariableFont_wdth,wght.ttf"
+    };
+
+    if !Path::new(filename).exists() {
+        eprintln!("Error: provided font file '{}' does not exist, specify the location to the font as an argument", filename);
+        process::exit(1);
+    }
+
+    let lib = freetype::Library::init().unwrap();
+    let face = lib.face_from_file(filename, 0).unwrap();
+    let hb_face = harfbuzz::Face::from_ft(&face);
+    let font_map = pango2::Pango2FontMap::new();
+    font_map.add_face(&pango2::Pango2HbFace::from_hb_face(&hb_face, -2));
+    let pango_context = pango2::Pango2Context::from_font_map(&font_map);
+
+    // unhinted outlines with linear metrics for print
+    let print = cairo::FontOptions::new();
+    print.set_antialias(cairo::Antialias::Gray);
+    print.set_hint_style(cairo::HintStyle::None);
+    print.set_hint_metrics(cairo::HintMetrics::Off);
+
+    // full hinting for small UI text
+    let ui = cairo::FontOptions::new();
+    ui.set_antialias(cairo::Antialias::Subpixel);
+    ui.set_subpixel_order(cairo::SubpixelOrder::Rgb);
+    ui.set_hint_style(cairo::HintStyle::Full);
+    ui.set_hint_metrics(cairo::HintMetrics::On);
+
+    // one-bit rendering, a heavier weight selected through the variations
+    let mono = ui.clone();
+    mono.set_antialias(cairo::Antialias::None);
+    mono.set_color_mode(cairo::ColorMode::NoColor);
+    mono.set_variations(Some("wght=700"));
+
+    if print.hint_style() != cairo::HintStyle::None
+        || print.hint_metrics() != cairo::HintMetrics::Off
+        || ui.antialias() != cairo::Antialias::Subpixel
+        || ui.subpixel_order() != cairo::SubpixelOrder::Rgb
+        || mono.hint_style() != cairo::HintStyle::Full
+        || mono.color_mode() != cairo::ColorMode::NoColor
+    {
+        eprintln!("Error: font options do not keep the values they were set to");
+        process::exit(1);
+    }
+    println!("Mono variations: {:?}", mono.variations());
+    if mono.variations().as_deref() != Some("wght=700") || ui.variations().is_some() {
+        eprintln!("Error: unexpected font variations");
+        process::exit(1);
+    }
+    if mono == ui {
+        eprintln!("Error: modified copy of font options still equals the original");
+        process::exit(1);
+    }
+
+    let (width, line_height) = (400, 40);
+    let configs = [("print", &print), ("ui", &ui), ("mono", &mono)];
+    let format = cairo::Format::A8;
+    let mut cairo_surface =
+        cairo::CairoSurface::new_image_surface_with_format(format, width, line_height * configs.len() as i32)
+            .unwrap();
+
+    let mut results = Vec::new();
+    for (i, (name, options)) in configs.iter().enumerate() {
+        pango_context.set_font_options(options);
+        if pango_context.font_options().as_ref() != Some(*options) {
+            eprintln!("Error: context does not return the '{}' font options", name);
+            process::exit(1);
+        }
+        let cairo_context = cairo::CairoContext::create(&cairo_surface);
+        pango_context.update_cairo_context(&cairo_context);
+
+        let layout = pango2::Pango2Layout::new(&pango_context);
+        layout.set_text("Hinting: Hamburgefonstiv 0123");
+        layout.set_font_description_string(format!("{} Regular 11", face.face_name()).as_str());
+        cairo_context.move_to(4.0, (i as i32 * line_height + 8) as f32);
+        layout.paint(&cairo_context);
+        // the pixels of a surface can only be read once its context is gone
+        drop(cairo_context);
+
+        let y0 = i * line_height as usize;
+        let (partial, covered) = coverage(&mut cairo_surface, width as usize, y0, y0 + line_height as usize);
+        println!("{:>5}: {} covered pixels, {} partially covered", name, covered, partial);
+        results.push((partial, covered));
+    }
+
+    let output = "font_options_test.png";
+    println!("Write renderings to file: {}", output);
+    cairo_surface.write_to_png(output).unwrap();
+
+    if results.iter().any(|(_, covered)| *covered == 0) {
+        eprintln!("Error: a configuration did not render any text");
+        process::exit(1);
+    }
+    // without antialiasing every pixel is either set or not
+    let (mono_partial, _) = results[2];
+    if mono_partial != 0 {
+        eprintln!("Error: antialiasing disabled but {} pixels are partially covered", mono_partial);
+        process::exit(1);
+    }
+}
@@ -0,0 +1,71 @@
+use std::{env, path::Path, process};
+use pango2_sys_examples::{atlas, freetype, harfbuzz, pango2, cairo};
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    let filename = if args.len() > 1 {
+        &args[1]
+    } else {
+        "./fonts/NotoSerifDisplay/NotoSerifDisplay-VariableFont_wdth,wght.ttf"
+    };
+
+    if !Path::new(filename).exists() {
+        eprintln!("Error: provided font file '{}' does not exist, specify the location to the font as an argument", filename);
+        process::exit(1);
+    }
+
+    let lib = freetype::Library::init().unwrap();
+    let face = lib.face_from_file(filename, 0).unwrap();
+    let hb_face = harfbuzz::Face::from_ft(&face);
+    let font_map = pango2::Pango2FontMap::new();
+    font_map.add_face(&pango2::Pango2HbFace::from_hb_face(&hb_face, -2));
+    let pango_context = pango2::Pango2Context::from_font_map(&font_map);
+
+    let text = "Hola, Pango2!";
+    let layout = pango2::Pango2Layout::new(&pango_context);
+    layout.set_text(text);
+    layout.set_font_description_string(format!("{} Regular 48px", face.face_name()).as_str());
+
+    let mut glyph_atlas = atlas::GlyphAtlas::new(&hb_face, 48.0, cairo::Format::A8, 256).unwrap();
+    glyph_atlas.set_subpixel_positions(4);
+    let quads = glyph_atlas.layout_quads(&layout).unwrap();
+    println!("Placed {} quads on {} pages", quads.len(), glyph_atlas.page_count());
+
+    // every visible character is a glyph of its own in this font
+    let visible = text.chars().filter(|c| !c.is_whitespace()).count();
+    if quads.len() != visible {
+        eprintln!("Error: expected {} quads", visible);
+        process::exit(1);
+    }
+
+    for quad in &quads {
+        let page = glyph_atlas.page(quad.page).unwrap();
+        let stride = page.stride();
+        let x = (quad.uv.u0 * page.width() as f32).round() as usize;
+        let y = (quad.uv.v0 * page.height() as f32).round() as usize;
+        let data = glyph_atlas.page_data(quad.page).unwrap();
+        let inked = (y..y + quad.height as usize)
+            .any(|row| data[row * stride + x..row * stride + x + quad.width as usize].iter().any(|alpha| *alpha > 0));
+        if !inked {
+            eprintln!("Error: empty bitmap for quad {:?}", quad);
+            process::exit(1);
+        }
+    }
+
+    // quads are placed left to right on a single line
+    if quads.windows(2).any(|pair| pair[1].x < pair[0].x) {
+        eprintln!("Error: quads are not ordered along the line");
+        process::exit(1);
+    }
+
+    // glyphs at the same subpixel position are rasterized once
+    let glyph = glyph_atlas.glyph(quads.len() as u32, 0.1).unwrap();
+    if glyph_atlas.glyph(quads.len() as u32, 0.2).unwrap() != glyph {
+        eprintln!("Error: glyph was rasterized twice");
+        process::exit(1);
+    }
+
+    let output = "glyph_atlas_test.png";
+    println!("Write first atlas page to file: {}", output);
+    glyph_atlas.page(0).unwrap().write_to_png(output).unwrap();
+}
@@ -121,15 +121,101 @@ pub mod freetype {
 }
 
 pub mod harfbuzz {
-    use std::ffi::{c_char, c_uint, CStr};
+    use std::{
+        any::Any,
+        ffi::{c_char, c_int, c_uint, c_void, CStr},
+        panic::{self, AssertUnwindSafe},
+        ptr,
+    };
 
     use super::freetype;
     use harfbuzz_sys::{
-        hb_face_destroy, hb_face_make_immutable, hb_face_t, hb_ft_face_create_referenced,
-        hb_language_t, hb_ot_name_get_utf8, hb_ot_var_get_named_instance_count, hb_ot_var_has_data,
-        hb_ot_var_named_instance_get_subfamily_name_id,
+        hb_blob_create, hb_blob_destroy, hb_blob_get_data, hb_blob_t, hb_bool_t, hb_codepoint_t,
+        hb_color_line_get_color_stops, hb_color_line_get_extend, hb_color_line_t, hb_color_stop_t,
+        hb_color_t, hb_draw_funcs_create, hb_draw_funcs_destroy, hb_draw_funcs_make_immutable,
+        hb_draw_funcs_set_close_path_func, hb_draw_funcs_set_cubic_to_func,
+        hb_draw_funcs_set_line_to_func, hb_draw_funcs_set_move_to_func,
+        hb_draw_funcs_set_quadratic_to_func, hb_draw_funcs_t, hb_draw_state_t,
+        hb_face_builder_add_table, hb_face_builder_create, hb_face_create, hb_face_destroy,
+        hb_face_get_index, hb_face_get_table_tags, hb_face_make_immutable, hb_face_reference_blob,
+        hb_face_reference_table, hb_face_t, hb_font_create, hb_font_destroy, hb_font_draw_glyph,
+        hb_font_get_face, hb_font_get_glyph_extents, hb_font_paint_glyph, hb_font_reference,
+        hb_font_set_scale, hb_font_set_synthetic_bold, hb_font_set_synthetic_slant,
+        hb_font_set_variations, hb_font_t, hb_ft_face_create_referenced, hb_glyph_extents_t,
+        hb_language_t, hb_ot_color_has_layers, hb_ot_color_has_paint, hb_ot_color_has_palettes,
+        hb_ot_color_has_png, hb_ot_color_has_svg, hb_ot_color_palette_color_get_name_id,
+        hb_ot_color_palette_get_colors, hb_ot_color_palette_get_count,
+        hb_ot_color_palette_get_flags, hb_ot_color_palette_get_name_id, hb_ot_metrics_get_position,
+        hb_ot_metrics_get_position_with_fallback, hb_ot_metrics_get_variation, hb_ot_metrics_tag_t,
+        hb_ot_name_get_utf8, hb_ot_name_id_t, hb_ot_var_get_named_instance_count,
+        hb_ot_var_has_data, hb_ot_var_named_instance_get_subfamily_name_id,
+        hb_paint_composite_mode_t, hb_paint_funcs_create, hb_paint_funcs_destroy,
+        hb_paint_funcs_make_immutable, hb_paint_funcs_set_color_func,
+        hb_paint_funcs_set_custom_palette_color_func, hb_paint_funcs_set_image_func,
+        hb_paint_funcs_set_linear_gradient_func, hb_paint_funcs_set_pop_clip_func,
+        hb_paint_funcs_set_pop_group_func, hb_paint_funcs_set_pop_transform_func,
+        hb_paint_funcs_set_push_clip_glyph_func, hb_paint_funcs_set_push_clip_rectangle_func,
+        hb_paint_funcs_set_push_group_func, hb_paint_funcs_set_push_transform_func,
+        hb_paint_funcs_set_radial_gradient_func, hb_paint_funcs_set_sweep_gradient_func,
+        hb_paint_funcs_t, hb_style_get_value, hb_style_tag_t_HB_STYLE_TAG_ITALIC,
+        hb_style_tag_t_HB_STYLE_TAG_SLANT_ANGLE, hb_style_tag_t_HB_STYLE_TAG_WEIGHT,
+        hb_style_tag_t_HB_STYLE_TAG_WIDTH, hb_tag_t, hb_variation_from_string, hb_variation_t,
+    };
+
+    use harfbuzz_sys::{
+        hb_ot_color_palette_flags_t_HB_OT_COLOR_PALETTE_FLAG_USABLE_WITH_DARK_BACKGROUND as USABLE_WITH_DARK_BACKGROUND,
+        hb_ot_color_palette_flags_t_HB_OT_COLOR_PALETTE_FLAG_USABLE_WITH_LIGHT_BACKGROUND as USABLE_WITH_LIGHT_BACKGROUND,
     };
 
+    /// An 8 bit RGBA color as stored in font tables.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct Color {
+        pub red: u8,
+        pub green: u8,
+        pub blue: u8,
+        pub alpha: u8,
+    }
+
+    impl From<hb_color_t> for Color {
+        /// Unpacks the BGRA layout of `hb_color_t`.
+        fn from(color: hb_color_t) -> Self {
+            Color {
+                red: (color >> 8) as u8,
+                green: (color >> 16) as u8,
+                blue: (color >> 24) as u8,
+                alpha: color as u8,
+            }
+        }
+    }
+
+    impl From<Color> for hb_color_t {
+        fn from(color: Color) -> Self {
+            (color.blue as hb_color_t) << 24
+                | (color.green as hb_color_t) << 16
+                | (color.red as hb_color_t) << 8
+                | color.alpha as hb_color_t
+        }
+    }
+
+    /// A color palette of the CPAL table.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct Palette {
+        pub index: u32,
+        pub name: Option<String>,
+        pub usable_with_light_background: bool,
+        pub usable_with_dark_background: bool,
+    }
+
+    /// Colors replacing entries of one of the font's palettes, see
+    /// [`Face::with_custom_palettes`].
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct CustomPalette {
+        /// The palette the entries without a replacement are taken from.
+        pub base: u32,
+        /// Palette entry indices with the colors replacing them.
+        pub colors: Vec<(u32, Color)>,
+    }
+
     pub struct Face {
         raw: *mut hb_face_t,
     }
@@ -184,300 +270,7795 @@ pub mod harfbuzz {
                 names
             }
         }
-    }
 
-    impl Drop for Face {
-        fn drop(&mut self) {
+        /// Looks up an entry of the name table in the default language.
+        pub fn name(&self, name_id: hb_ot_name_id_t) -> Option<String> {
             unsafe {
-                hb_face_destroy(self.raw);
+                const MAX_NAME_LENGTH: usize = 255;
+                let mut buffer = [0 as c_char; MAX_NAME_LENGTH];
+                let mut buffer_length: c_uint = MAX_NAME_LENGTH as c_uint;
+                let length = hb_ot_name_get_utf8(
+                    self.raw,
+                    name_id,
+                    0 as hb_language_t,
+                    &mut buffer_length,
+                    buffer.as_mut_ptr(),
+                );
+                if length == 0 {
+                    None
+                } else {
+                    Some(
+                        CStr::from_ptr(buffer.as_ptr())
+                            .to_string_lossy()
+                            .to_string(),
+                    )
+                }
             }
         }
-    }
-}
-
-pub mod pango2 {
-    use super::cairo;
-    use super::harfbuzz;
-    use gobject_sys::{g_object_unref, GObject};
-    use pango2_sys::{pango2_font_description_free, pango2_hb_face_new_from_hb_face};
-    use std::{
-        ffi::{c_int, CString},
-        ptr,
-    };
 
-    pub struct Pango2HbFace {
-        raw: *mut pango2_sys::Pango2HbFace,
-    }
+        /// Returns true if the face has a CPAL table with color palettes.
+        pub fn has_color_palettes(&self) -> bool {
+            unsafe { hb_ot_color_has_palettes(self.raw) != 0 }
+        }
 
-    impl Pango2HbFace {
-        pub fn from_hb_face(face: &harfbuzz::Face, instance_id: i32) -> Self {
-            unsafe {
-                let raw = pango2_hb_face_new_from_hb_face(
-                    *face.raw(),
-                    instance_id as c_int,
-                    ptr::null(),
-                    ptr::null(),
-                );
-                Self { raw }
-            }
+        /// Returns true if the face has COLRv0 color layers.
+        pub fn has_color_layers(&self) -> bool {
+            unsafe { hb_ot_color_has_layers(self.raw) != 0 }
         }
-    }
 
-    /* 
-    TODO This crashes / segfaults
+        /// Returns true if the face has COLRv1 paint graphs.
+        pub fn has_color_paint(&self) -> bool {
+            unsafe { hb_ot_color_has_paint(self.raw) != 0 }
+        }
 
-        impl Drop for Pango2HbFace {
-            fn drop(&mut self) {
-                unsafe {
-                    g_object_unref(self.raw as *mut GObject);
-                }
-            }
+        /// Returns true if the face has bitmap color glyphs (CBDT or sbix).
+        pub fn has_color_png(&self) -> bool {
+            unsafe { hb_ot_color_has_png(self.raw) != 0 }
         }
-    */
 
-    pub struct Pango2FontMap {
-        raw: *mut pango2_sys::Pango2FontMap,
-    }
+        pub fn has_color_svg(&self) -> bool {
+            unsafe { hb_ot_color_has_svg(self.raw) != 0 }
+        }
 
-    impl Pango2FontMap {
-        pub fn new() -> Self {
+        pub fn palettes(&self) -> Vec<Palette> {
             unsafe {
-                Pango2FontMap {
-                    raw: pango2_sys::pango2_font_map_new(),
-                }
+                let count = hb_ot_color_palette_get_count(self.raw);
+                (0..count)
+                    .map(|index| {
+                        let flags = hb_ot_color_palette_get_flags(self.raw, index);
+                        Palette {
+                            index,
+                            name: self.name(hb_ot_color_palette_get_name_id(self.raw, index)),
+                            usable_with_light_background: flags & USABLE_WITH_LIGHT_BACKGROUND != 0,
+                            usable_with_dark_background: flags & USABLE_WITH_DARK_BACKGROUND != 0,
+                        }
+                    })
+                    .collect()
             }
         }
 
-        pub fn add_face(&self, face: &Pango2HbFace) {
+        /// Returns the colors of the palette at `index`, the position in
+        /// the returned list is the palette entry index.
+        pub fn palette_colors(&self, index: u32) -> Vec<Color> {
             unsafe {
-                pango2_sys::pango2_font_map_add_face(
+                let count = hb_ot_color_palette_get_colors(
                     self.raw,
-                    face.raw as *mut pango2_sys::Pango2FontFace,
+                    index,
+                    0,
+                    std::ptr::null_mut(),
+                    std::ptr::null_mut(),
                 );
+                let mut colors: Vec<hb_color_t> = vec![0; count as usize];
+                let mut color_count = count;
+                hb_ot_color_palette_get_colors(
+                    self.raw,
+                    index,
+                    0,
+                    &mut color_count,
+                    colors.as_mut_ptr(),
+                );
+                colors.truncate(color_count as usize);
+                colors.into_iter().map(Color::from).collect()
             }
         }
-    }
 
-    impl Default for Pango2FontMap {
-        fn default() -> Self {
-            Self::new()
-        }
-    }
+        /// Returns a copy of the face with the `palettes` appended to its
+        /// CPAL table, the first one gets the index `self.palettes().len()`.
+        /// Unlike the custom palette colors of `cairo::FontOptions`, which
+        /// apply to everything drawn with a context, the new palettes can be
+        /// named with `Pango2HbFace::set_palette_name` and selected for a
+        /// range of text with `Attribute::palette`.
+        ///
+        /// Returns `None` if the face has no palettes, a base palette or
+        /// entry index is out of range or the palettes don't fit into a CPAL
+        /// table, which counts palettes and colors in 16 bits.
+        pub fn with_custom_palettes(&self, palettes: &[CustomPalette]) -> Option<Face> {
+            const CPAL: hb_tag_t = u32::from_be_bytes(*b"CPAL");
 
-    impl Drop for Pango2FontMap {
-        fn drop(&mut self) {
             unsafe {
-                g_object_unref(self.raw as *mut GObject);
-            }
-        }
-    }
+                let count = hb_ot_color_palette_get_count(self.raw);
+                let entries = hb_ot_color_palette_get_colors(
+                    self.raw,
+                    0,
+                    0,
+                    ptr::null_mut(),
+                    ptr::null_mut(),
+                );
+                let palette_count = count as usize + palettes.len();
+                if count == 0
+                    || palette_count > u16::MAX as usize
+                    || palette_count * entries as usize > u16::MAX as usize
+                    || palettes.iter().any(|palette| {
+                        palette.base >= count
+                            || palette.colors.iter().any(|&(index, _)| index >= entries)
+                    })
+                {
+                    return None;
+                }
 
-    pub struct Pango2Context {
-        raw: *mut pango2_sys::Pango2Context,
-    }
+                let mut colors: Vec<Color> = (0..count)
+                    .flat_map(|index| self.palette_colors(index))
+                    .collect();
+                for palette in palettes {
+                    let mut custom = self.palette_colors(palette.base);
+                    for &(index, color) in &palette.colors {
+                        custom[index as usize] = color;
+                    }
+                    colors.extend(custom);
+                }
+                let flags: Vec<u32> = (0..count)
+                    .map(|index| hb_ot_color_palette_get_flags(self.raw, index))
+                    .chain(palettes.iter().map(|_| 0))
+                    .collect();
+                let labels: Vec<u16> = (0..count)
+                    .map(|index| hb_ot_color_palette_get_name_id(self.raw, index) as u16)
+                    .chain(palettes.iter().map(|_| 0xffff))
+                    .collect();
+                let entry_labels: Vec<u16> = (0..entries)
+                    .map(|index| hb_ot_color_palette_color_get_name_id(self.raw, index) as u16)
+                    .collect();
+                let table = cpal_table(entries as u16, &colors, &flags, &labels, &entry_labels)?;
 
-    impl Pango2Context {
-        pub fn from_font_map(font_map: &Pango2FontMap) -> Self {
-            unsafe {
-                Pango2Context {
-                    raw: pango2_sys::pango2_context_new_with_font_map(font_map.raw),
+                // table tags can only be listed for faces created from the
+                // font data, not for faces backed by FreeType
+                let blob = hb_face_reference_blob(self.raw);
+                let source = hb_face_create(blob, hb_face_get_index(self.raw));
+                hb_blob_destroy(blob);
+
+                let builder = hb_face_builder_create();
+                let mut tag_count = 0;
+                let total = hb_face_get_table_tags(source, 0, &mut tag_count, ptr::null_mut());
+                let mut tags: Vec<hb_tag_t> = vec![0; total as usize];
+                tag_count = total;
+                hb_face_get_table_tags(source, 0, &mut tag_count, tags.as_mut_ptr());
+                for &tag in tags
+                    .iter()
+                    .take(tag_count as usize)
+                    .filter(|&&tag| tag != CPAL)
+                {
+                    let blob = hb_face_reference_table(source, tag);
+                    hb_face_builder_add_table(builder, tag, blob);
+                    hb_blob_destroy(blob);
                 }
+                hb_face_destroy(source);
+
+                let cpal = hb_blob_create(
+                    table.as_ptr() as *const c_char,
+                    table.len() as c_uint,
+                    harfbuzz_sys::hb_memory_mode_t_HB_MEMORY_MODE_DUPLICATE,
+                    ptr::null_mut(),
+                    None,
+                );
+                hb_face_builder_add_table(builder, CPAL, cpal);
+                hb_blob_destroy(cpal);
+
+                let blob = hb_face_reference_blob(builder);
+                hb_face_destroy(builder);
+                let face = hb_face_create(blob, 0);
+                hb_blob_destroy(blob);
+                hb_face_make_immutable(face);
+                Some(Face { raw: face })
             }
         }
+    }
 
-        pub fn update_cairo_context(&self, cairo_context: &cairo::CairoContext) {
-            unsafe {
-                pango2_sys::pango2_cairo_update_context(*cairo_context.raw(), self.raw);
-            }
+    /// Serializes a CPAL version 1 table, `colors` holds the entries of all
+    /// palettes one after the other. Returns `None` if there are more
+    /// palettes or colors than the 16 bit counts of the table can hold.
+    fn cpal_table(
+        entries: u16,
+        colors: &[Color],
+        flags: &[u32],
+        labels: &[u16],
+        entry_labels: &[u16],
+    ) -> Option<Vec<u8>> {
+        let palettes = flags.len();
+        let palette_count = u16::try_from(palettes).ok()?;
+        let color_count = u16::try_from(colors.len()).ok()?;
+        let header_size = 12 + 2 * palettes + 12;
+        let types_offset = header_size + 4 * colors.len();
+        let labels_offset = types_offset + 4 * palettes;
+        let entry_labels_offset = labels_offset + 2 * palettes;
+
+        let mut table = Vec::with_capacity(entry_labels_offset + 2 * entry_labels.len());
+        table.extend(1u16.to_be_bytes());
+        table.extend(entries.to_be_bytes());
+        table.extend(palette_count.to_be_bytes());
+        table.extend(color_count.to_be_bytes());
+        table.extend((header_size as u32).to_be_bytes());
+        for index in 0..palettes {
+            let first = u16::try_from(index * entries as usize).ok()?;
+            table.extend(first.to_be_bytes());
+        }
+        table.extend((types_offset as u32).to_be_bytes());
+        table.extend((labels_offset as u32).to_be_bytes());
+        table.extend((entry_labels_offset as u32).to_be_bytes());
+        for color in colors {
+            table.extend([color.blue, color.green, color.red, color.alpha]);
+        }
+        for flag in flags {
+            table.extend(flag.to_be_bytes());
+        }
+        for label in labels.iter().chain(entry_labels) {
+            table.extend(label.to_be_bytes());
         }
+        Some(table)
     }
 
-    impl Drop for Pango2Context {
+    impl Drop for Face {
         fn drop(&mut self) {
             unsafe {
-                g_object_unref(self.raw as *mut GObject);
+                hb_face_destroy(self.raw);
             }
         }
     }
 
-    pub struct Pango2Layout {
-        raw: *mut pango2_sys::Pango2Layout,
+    /// A face at a specific size and variation, coordinates reported by the
+    /// font are in font units scaled by [`Font::set_scale`].
+    pub struct Font {
+        raw: *mut hb_font_t,
     }
 
-    impl Pango2Layout {
-        pub fn new(context: &Pango2Context) -> Self {
+    impl Font {
+        /// Creates a font for the face, the scale defaults to the units per em
+        /// of the face.
+        pub fn new(face: &Face) -> Font {
             unsafe {
-                Pango2Layout {
-                    raw: pango2_sys::pango2_layout_new(context.raw),
+                Font {
+                    raw: hb_font_create(face.raw),
                 }
             }
         }
 
-        pub fn set_font_description_string(&self, font_description: &str) {
-            let description = FontDescription::from_string(font_description);
-            self.set_font_description(&description);
+        /// Takes a new reference to a font owned by someone else.
+        pub(crate) unsafe fn from_raw(raw: *mut hb_font_t) -> Font {
+            Font {
+                raw: hb_font_reference(raw),
+            }
         }
 
-        pub fn set_font_description(&self, font_description: &FontDescription) {
-            unsafe {
-                pango2_sys::pango2_layout_set_font_description(self.raw, font_description.raw);
-            }
+        pub fn raw(&self) -> &*mut hb_font_t {
+            &self.raw
         }
 
-        pub fn set_text(&self, text: &str) {
-            unsafe {
-                let ctext = CString::new(text).unwrap();
-                pango2_sys::pango2_layout_set_text(self.raw, ctext.as_ptr(), -1);
-            }
+        /// Whether both fonts are instances of the same face.
+        pub fn same_face(&self, other: &Font) -> bool {
+            unsafe { hb_font_get_face(self.raw) == hb_font_get_face(other.raw) }
         }
 
-        pub fn paint(&self, cairo_context: &cairo::CairoContext) {
+        /// Returns the ink extents of a glyph in font units, y grows upwards
+        /// so the height is negative.
+        pub fn glyph_extents(&self, glyph: u32) -> Option<GlyphExtents> {
             unsafe {
-                pango2_sys::pango2_cairo_show_layout(*cairo_context.raw(), self.raw);
+                let mut extents = hb_glyph_extents_t {
+                    x_bearing: 0,
+                    y_bearing: 0,
+                    width: 0,
+                    height: 0,
+                };
+                if hb_font_get_glyph_extents(self.raw, glyph, &mut extents) != 0 {
+                    Some(extents.into())
+                } else {
+                    None
+                }
             }
         }
-    }
 
-    impl Drop for Pango2Layout {
-        fn drop(&mut self) {
+        pub fn set_scale(&self, x_scale: i32, y_scale: i32) {
             unsafe {
-                g_object_unref(self.raw as *mut GObject);
+                hb_font_set_scale(self.raw, x_scale as c_int, y_scale as c_int);
             }
         }
-    }
-
-    pub struct FontDescription {
-        raw: *mut pango2_sys::Pango2FontDescription,
-    }
 
-    impl FontDescription {
-        pub fn from_string(string: &str) -> Self {
+        /// Sets the variation axes from a comma separated list such as
+        /// `"wght=700,wdth=80"`, entries that fail to parse are skipped.
+        pub fn set_variations(&self, variations: &str) {
+            let variations: Vec<hb_variation_t> = variations
+                .split(',')
+                .filter_map(|variation| {
+                    let mut parsed = hb_variation_t { tag: 0, value: 0.0 };
+                    let ok = unsafe {
+                        hb_variation_from_string(
+                            variation.as_ptr() as *const c_char,
+                            variation.len() as c_int,
+                            &mut parsed,
+                        )
+                    };
+                    (ok != 0).then_some(parsed)
+                })
+                .collect();
             unsafe {
-                let cstring = CString::new(string).unwrap();
-                FontDescription {
-                    raw: pango2_sys::pango2_font_description_from_string(cstring.as_ptr()),
-                }
+                hb_font_set_variations(self.raw, variations.as_ptr(), variations.len() as c_uint);
             }
         }
-    }
 
-    impl Drop for FontDescription {
-        fn drop(&mut self) {
+        /// Emboldens outlines by the given amounts in font units, with
+        /// `in_place` the advances are left unchanged.
+        pub fn set_synthetic_bold(&self, x_embolden: f32, y_embolden: f32, in_place: bool) {
             unsafe {
-                pango2_font_description_free(self.raw);
+                hb_font_set_synthetic_bold(self.raw, x_embolden, y_embolden, in_place as hb_bool_t);
             }
         }
-    }
-}
-
-pub mod cairo {
-    use std::ffi::{c_double, c_int, CString};
-
-    #[derive(Debug)]
-    pub enum CairoError {
-        SurfaceCreateError
-    }
-
-    pub struct CairoSurface {
-        raw: *mut cairo_sys::cairo_surface_t,
-    }
 
-    impl CairoSurface {
-        pub fn new_image_surface(width: i32, height: i32) -> Result<Self, CairoError> {
+        /// Slants outlines by `slant` units horizontally per unit vertically.
+        pub fn set_synthetic_slant(&self, slant: f32) {
             unsafe {
-                let raw = cairo_sys::cairo_image_surface_create(
-                    cairo_sys::_cairo_format_CAIRO_FORMAT_ARGB32,
-                    width as c_int,
-                    height as c_int,
-                );
-                let status = cairo_sys::cairo_surface_status(raw);
-                if status == cairo_sys::_cairo_status_CAIRO_STATUS_SUCCESS {
-                    Ok(Self { raw })
-                } else {
-                    Err(CairoError::SurfaceCreateError)
-                }
+                hb_font_set_synthetic_slant(self.raw, slant);
             }
         }
 
-        pub fn write_to_png(&self, path: &str) -> Result<(), CairoError> {
+        /// Returns an OpenType metric in the units of the font scale, `None`
+        /// if the font does not have it.
+        pub fn metrics_position(&self, tag: MetricsTag) -> Option<i32> {
             unsafe {
-                let cpath = CString::new(path).unwrap();
-                let status = cairo_sys::cairo_surface_write_to_png(self.raw, cpath.as_ptr());
-                if status == cairo_sys::_cairo_status_CAIRO_STATUS_SUCCESS {
-                    Ok(())
+                let mut position = 0;
+                if hb_ot_metrics_get_position(self.raw, tag.into(), &mut position) != 0 {
+                    Some(position)
                 } else {
-                    Err(CairoError::SurfaceCreateError)
+                    None
                 }
             }
         }
-    }
 
-    impl Drop for CairoSurface {
-        fn drop(&mut self) {
+        /// Like [`Font::metrics_position`], but metrics missing from the font
+        /// are synthesized from other tables, e.g. the cap height from the
+        /// extents of the 'H' glyph.
+        pub fn metrics_position_with_fallback(&self, tag: MetricsTag) -> i32 {
             unsafe {
-                cairo_sys::cairo_surface_destroy(self.raw);
+                let mut position = 0;
+                hb_ot_metrics_get_position_with_fallback(self.raw, tag.into(), &mut position);
+                position
             }
         }
-    }
 
-    pub struct CairoContext {
-        raw: *mut cairo_sys::cairo_t,
-    }
+        /// Returns how much the variations of the font change a metric, in
+        /// unscaled font units.
+        pub fn metrics_variation(&self, tag: MetricsTag) -> f32 {
+            unsafe { hb_ot_metrics_get_variation(self.raw, tag.into()) }
+        }
 
-    impl CairoContext {
-        pub fn create(surface: &CairoSurface) -> Self {
+        /// Returns the style of the font with its variations applied, values
+        /// not set by variations come from the OS/2 and post tables.
+        pub fn style(&self) -> Style {
             unsafe {
-                Self {
-                    raw: cairo_sys::cairo_create(surface.raw),
+                Style {
+                    weight: hb_style_get_value(self.raw, hb_style_tag_t_HB_STYLE_TAG_WEIGHT),
+                    width: hb_style_get_value(self.raw, hb_style_tag_t_HB_STYLE_TAG_WIDTH),
+                    italic: hb_style_get_value(self.raw, hb_style_tag_t_HB_STYLE_TAG_ITALIC),
+                    slant: hb_style_get_value(self.raw, hb_style_tag_t_HB_STYLE_TAG_SLANT_ANGLE),
                 }
             }
         }
 
-        pub fn raw(&self) -> &*mut cairo_sys::cairo_t {
-            &self.raw
-        }
-
-        pub fn set_source_rgb(&self, r: f32, g: f32, b: f32) {
+        /// Draws the outline of a glyph into the sink, the variations,
+        /// synthetic bold and slant of the font are applied.
+        pub fn draw_glyph<S: OutlineSink>(&self, glyph: u32, sink: &mut S) {
+            let mut draw_data = CallbackData::new(sink);
             unsafe {
-                cairo_sys::cairo_set_source_rgb(
-                    self.raw,
-                    r as c_double,
-                    g as c_double,
-                    b as c_double,
-                );
+                let funcs = draw_funcs::<S>();
+                hb_font_draw_glyph(self.raw, glyph, funcs, draw_data.as_ptr());
+                hb_draw_funcs_destroy(funcs);
             }
+            draw_data.resume_panic();
         }
 
-        pub fn set_source_rgba(&self, r: f32, g: f32, b: f32, a: f32) {
+        /// Paints a color glyph by calling the painter for every node of the
+        /// paint graph, see `hb_font_paint_glyph`. Glyphs without color
+        /// information are painted as a glyph clip filled with `foreground`.
+        pub fn paint_glyph<P: GlyphPainter>(
+            &self,
+            glyph: u32,
+            painter: &mut P,
+            palette: u32,
+            foreground: Color,
+        ) {
+            let mut paint_data = CallbackData::new(painter);
             unsafe {
-                cairo_sys::cairo_set_source_rgba(
+                let funcs = paint_funcs::<P>();
+                hb_font_paint_glyph(
                     self.raw,
-                    r as c_double,
-                    g as c_double,
-                    b as c_double,
-                    a as c_double,
+                    glyph,
+                    funcs,
+                    paint_data.as_ptr(),
+                    palette,
+                    foreground.into(),
                 );
+                hb_paint_funcs_destroy(funcs);
             }
+            paint_data.resume_panic();
         }
+    }
 
-        pub fn paint(&self) {
+    impl Drop for Font {
+        fn drop(&mut self) {
             unsafe {
-                cairo_sys::cairo_paint(self.raw);
+                hb_font_destroy(self.raw);
             }
         }
+    }
 
-        pub fn paint_with_alpha(&self, alpha: f32) {
-            unsafe {
-                cairo_sys::cairo_paint_with_alpha(self.raw, alpha as c_double);
-            }
-        }
+    /// Font wide metrics of the OS/2, hhea, vhea and post tables, see
+    /// [`Font::metrics_position`].
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum MetricsTag {
+        HorizontalAscender,
+        HorizontalDescender,
+        HorizontalLineGap,
+        HorizontalClippingAscent,
+        HorizontalClippingDescent,
+        VerticalAscender,
+        VerticalDescender,
+        VerticalLineGap,
+        HorizontalCaretRise,
+        HorizontalCaretRun,
+        HorizontalCaretOffset,
+        VerticalCaretRise,
+        VerticalCaretRun,
+        VerticalCaretOffset,
+        XHeight,
+        CapHeight,
+        SubscriptEmXSize,
+        SubscriptEmYSize,
+        SubscriptEmXOffset,
+        SubscriptEmYOffset,
+        SuperscriptEmXSize,
+        SuperscriptEmYSize,
+        SuperscriptEmXOffset,
+        SuperscriptEmYOffset,
+        StrikeoutSize,
+        StrikeoutOffset,
+        UnderlineSize,
+        UnderlineOffset,
     }
 
-    impl Drop for CairoContext {
-        fn drop(&mut self) {
-            unsafe {
-                cairo_sys::cairo_destroy(self.raw);
-            }
-        }
+    impl From<MetricsTag> for hb_ot_metrics_tag_t {
+        fn from(tag: MetricsTag) -> Self {
+            match tag {
+                MetricsTag::HorizontalAscender => {
+                    harfbuzz_sys::hb_ot_metrics_tag_t_HB_OT_METRICS_TAG_HORIZONTAL_ASCENDER
+                }
+                MetricsTag::HorizontalDescender => {
+                    harfbuzz_sys::hb_ot_metrics_tag_t_HB_OT_METRICS_TAG_HORIZONTAL_DESCENDER
+                }
+                MetricsTag::HorizontalLineGap => {
+                    harfbuzz_sys::hb_ot_metrics_tag_t_HB_OT_METRICS_TAG_HORIZONTAL_LINE_GAP
+                }
+                MetricsTag::HorizontalClippingAscent => {
+                    harfbuzz_sys::hb_ot_metrics_tag_t_HB_OT_METRICS_TAG_HORIZONTAL_CLIPPING_ASCENT
+                }
+                MetricsTag::HorizontalClippingDescent => {
+                    harfbuzz_sys::hb_ot_metrics_tag_t_HB_OT_METRICS_TAG_HORIZONTAL_CLIPPING_DESCENT
+                }
+                MetricsTag::VerticalAscender => {
+                    harfbuzz_sys::hb_ot_metrics_tag_t_HB_OT_METRICS_TAG_VERTICAL_ASCENDER
+                }
+                MetricsTag::VerticalDescender => {
+                    harfbuzz_sys::hb_ot_metrics_tag_t_HB_OT_METRICS_TAG_VERTICAL_DESCENDER
+                }
+                MetricsTag::VerticalLineGap => {
+                    harfbuzz_sys::hb_ot_metrics_tag_t_HB_OT_METRICS_TAG_VERTICAL_LINE_GAP
+                }
+                MetricsTag::HorizontalCaretRise => {
+                    harfbuzz_sys::hb_ot_metrics_tag_t_HB_OT_METRICS_TAG_HORIZONTAL_CARET_RISE
+                }
+                MetricsTag::HorizontalCaretRun => {
+                    harfbuzz_sys::hb_ot_metrics_tag_t_HB_OT_METRICS_TAG_HORIZONTAL_CARET_RUN
+                }
+                MetricsTag::HorizontalCaretOffset => {
+                    harfbuzz_sys::hb_ot_metrics_tag_t_HB_OT_METRICS_TAG_HORIZONTAL_CARET_OFFSET
+                }
+                MetricsTag::VerticalCaretRise => {
+                    harfbuzz_sys::hb_ot_metrics_tag_t_HB_OT_METRICS_TAG_VERTICAL_CARET_RISE
+                }
+                MetricsTag::VerticalCaretRun => {
+                    harfbuzz_sys::hb_ot_metrics_tag_t_HB_OT_METRICS_TAG_VERTICAL_CARET_RUN
+                }
+                MetricsTag::VerticalCaretOffset => {
+                    harfbuzz_sys::hb_ot_metrics_tag_t_HB_OT_METRICS_TAG_VERTICAL_CARET_OFFSET
+                }
+                MetricsTag::XHeight => harfbuzz_sys::hb_ot_metrics_tag_t_HB_OT_METRICS_TAG_X_HEIGHT,
+                MetricsTag::CapHeight => {
+                    harfbuzz_sys::hb_ot_metrics_tag_t_HB_OT_METRICS_TAG_CAP_HEIGHT
+                }
+                MetricsTag::SubscriptEmXSize => {
+                    harfbuzz_sys::hb_ot_metrics_tag_t_HB_OT_METRICS_TAG_SUBSCRIPT_EM_X_SIZE
+                }
+                MetricsTag::SubscriptEmYSize => {
+                    harfbuzz_sys::hb_ot_metrics_tag_t_HB_OT_METRICS_TAG_SUBSCRIPT_EM_Y_SIZE
+                }
+                MetricsTag::SubscriptEmXOffset => {
+                    harfbuzz_sys::hb_ot_metrics_tag_t_HB_OT_METRICS_TAG_SUBSCRIPT_EM_X_OFFSET
+                }
+                MetricsTag::SubscriptEmYOffset => {
+                    harfbuzz_sys::hb_ot_metrics_tag_t_HB_OT_METRICS_TAG_SUBSCRIPT_EM_Y_OFFSET
+                }
+                MetricsTag::SuperscriptEmXSize => {
+                    harfbuzz_sys::hb_ot_metrics_tag_t_HB_OT_METRICS_TAG_SUPERSCRIPT_EM_X_SIZE
+                }
+                MetricsTag::SuperscriptEmYSize => {
+                    harfbuzz_sys::hb_ot_metrics_tag_t_HB_OT_METRICS_TAG_SUPERSCRIPT_EM_Y_SIZE
+                }
+                MetricsTag::SuperscriptEmXOffset => {
+                    harfbuzz_sys::hb_ot_metrics_tag_t_HB_OT_METRICS_TAG_SUPERSCRIPT_EM_X_OFFSET
+                }
+                MetricsTag::SuperscriptEmYOffset => {
+                    harfbuzz_sys::hb_ot_metrics_tag_t_HB_OT_METRICS_TAG_SUPERSCRIPT_EM_Y_OFFSET
+                }
+                MetricsTag::StrikeoutSize => {
+                    harfbuzz_sys::hb_ot_metrics_tag_t_HB_OT_METRICS_TAG_STRIKEOUT_SIZE
+                }
+                MetricsTag::StrikeoutOffset => {
+                    harfbuzz_sys::hb_ot_metrics_tag_t_HB_OT_METRICS_TAG_STRIKEOUT_OFFSET
+                }
+                MetricsTag::UnderlineSize => {
+                    harfbuzz_sys::hb_ot_metrics_tag_t_HB_OT_METRICS_TAG_UNDERLINE_SIZE
+                }
+                MetricsTag::UnderlineOffset => {
+                    harfbuzz_sys::hb_ot_metrics_tag_t_HB_OT_METRICS_TAG_UNDERLINE_OFFSET
+                }
+            }
+        }
+    }
+
+    /// Style of a font as returned by [`Font::style`].
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub struct Style {
+        /// Weight on the CSS scale, 400 is regular and 700 bold.
+        pub weight: f32,
+        /// Width in percent of the normal width.
+        pub width: f32,
+        /// 0 for upright and 1 for italic designs.
+        pub italic: f32,
+        /// Oblique slant in degrees, counter-clockwise from upright.
+        pub slant: f32,
+    }
+
+    /// Receives the contours of a glyph outline, y grows upwards.
+    pub trait OutlineSink {
+        fn move_to(&mut self, x: f32, y: f32);
+
+        fn line_to(&mut self, x: f32, y: f32);
+
+        fn quadratic_to(&mut self, control: (f32, f32), to: (f32, f32));
+
+        fn cubic_to(&mut self, control1: (f32, f32), control2: (f32, f32), to: (f32, f32));
+
+        fn close_path(&mut self);
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum PathCommand {
+        MoveTo(f32, f32),
+        LineTo(f32, f32),
+        QuadraticTo(f32, f32, f32, f32),
+        CubicTo(f32, f32, f32, f32, f32, f32),
+        ClosePath,
+    }
+
+    /// An [`OutlineSink`] collecting the outline as a list of commands.
+    #[derive(Debug, Clone, Default, PartialEq)]
+    pub struct PathBuilder {
+        commands: Vec<PathCommand>,
+    }
+
+    impl PathBuilder {
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        pub fn commands(&self) -> &[PathCommand] {
+            &self.commands
+        }
+
+        pub fn into_commands(self) -> Vec<PathCommand> {
+            self.commands
+        }
+    }
+
+    impl OutlineSink for PathBuilder {
+        fn move_to(&mut self, x: f32, y: f32) {
+            self.commands.push(PathCommand::MoveTo(x, y));
+        }
+
+        fn line_to(&mut self, x: f32, y: f32) {
+            self.commands.push(PathCommand::LineTo(x, y));
+        }
+
+        fn quadratic_to(&mut self, control: (f32, f32), to: (f32, f32)) {
+            self.commands
+                .push(PathCommand::QuadraticTo(control.0, control.1, to.0, to.1));
+        }
+
+        fn cubic_to(&mut self, control1: (f32, f32), control2: (f32, f32), to: (f32, f32)) {
+            self.commands.push(PathCommand::CubicTo(
+                control1.0, control1.1, control2.0, control2.1, to.0, to.1,
+            ));
+        }
+
+        fn close_path(&mut self) {
+            self.commands.push(PathCommand::ClosePath);
+        }
+    }
+
+    /// The draw or paint data passed to HarfBuzz. A panic of the target is
+    /// kept and the remaining callbacks are skipped, the caller resumes it
+    /// once HarfBuzz returns, unwinding through HarfBuzz would abort the
+    /// process.
+    struct CallbackData<'a, T> {
+        target: &'a mut T,
+        panic: Option<Box<dyn Any + Send>>,
+    }
+
+    impl<'a, T> CallbackData<'a, T> {
+        fn new(target: &'a mut T) -> Self {
+            CallbackData {
+                target,
+                panic: None,
+            }
+        }
+
+        fn as_ptr(&mut self) -> *mut c_void {
+            self as *mut Self as *mut c_void
+        }
+
+        /// Calls `call` with the target unless an earlier callback panicked,
+        /// returns `None` if it is skipped or panics.
+        unsafe fn call<R>(data: *mut c_void, call: impl FnOnce(&mut T) -> R) -> Option<R> {
+            let data = &mut *(data as *mut Self);
+            if data.panic.is_some() {
+                return None;
+            }
+            match panic::catch_unwind(AssertUnwindSafe(|| call(data.target))) {
+                Ok(result) => Some(result),
+                Err(payload) => {
+                    data.panic = Some(payload);
+                    None
+                }
+            }
+        }
+
+        fn resume_panic(self) {
+            if let Some(payload) = self.panic {
+                panic::resume_unwind(payload);
+            }
+        }
+    }
+
+    /// Creates draw funcs that forward every callback to the `S` of the
+    /// [`CallbackData`] passed as draw data.
+    unsafe fn draw_funcs<S: OutlineSink>() -> *mut hb_draw_funcs_t {
+        unsafe extern "C" fn move_to<S: OutlineSink>(
+            _funcs: *mut hb_draw_funcs_t,
+            draw_data: *mut c_void,
+            _state: *mut hb_draw_state_t,
+            to_x: f32,
+            to_y: f32,
+            _user_data: *mut c_void,
+        ) {
+            CallbackData::<S>::call(draw_data, |sink| sink.move_to(to_x, to_y));
+        }
+
+        unsafe extern "C" fn line_to<S: OutlineSink>(
+            _funcs: *mut hb_draw_funcs_t,
+            draw_data: *mut c_void,
+            _state: *mut hb_draw_state_t,
+            to_x: f32,
+            to_y: f32,
+            _user_data: *mut c_void,
+        ) {
+            CallbackData::<S>::call(draw_data, |sink| sink.line_to(to_x, to_y));
+        }
+
+        #[allow(clippy::too_many_arguments)]
+        unsafe extern "C" fn quadratic_to<S: OutlineSink>(
+            _funcs: *mut hb_draw_funcs_t,
+            draw_data: *mut c_void,
+            _state: *mut hb_draw_state_t,
+            control_x: f32,
+            control_y: f32,
+            to_x: f32,
+            to_y: f32,
+            _user_data: *mut c_void,
+        ) {
+            CallbackData::<S>::call(draw_data, |sink| {
+                sink.quadratic_to((control_x, control_y), (to_x, to_y))
+            });
+        }
+
+        #[allow(clippy::too_many_arguments)]
+        unsafe extern "C" fn cubic_to<S: OutlineSink>(
+            _funcs: *mut hb_draw_funcs_t,
+            draw_data: *mut c_void,
+            _state: *mut hb_draw_state_t,
+            control1_x: f32,
+            control1_y: f32,
+            control2_x: f32,
+            control2_y: f32,
+            to_x: f32,
+            to_y: f32,
+            _user_data: *mut c_void,
+        ) {
+            CallbackData::<S>::call(draw_data, |sink| {
+                sink.cubic_to(
+                    (control1_x, control1_y),
+                    (control2_x, control2_y),
+                    (to_x, to_y),
+                )
+            });
+        }
+
+        unsafe extern "C" fn close_path<S: OutlineSink>(
+            _funcs: *mut hb_draw_funcs_t,
+            draw_data: *mut c_void,
+            _state: *mut hb_draw_state_t,
+            _user_data: *mut c_void,
+        ) {
+            CallbackData::<S>::call(draw_data, |sink| sink.close_path());
+        }
+
+        let funcs = hb_draw_funcs_create();
+        let user_data = ptr::null_mut();
+        hb_draw_funcs_set_move_to_func(funcs, Some(move_to::<S>), user_data, None);
+        hb_draw_funcs_set_line_to_func(funcs, Some(line_to::<S>), user_data, None);
+        hb_draw_funcs_set_quadratic_to_func(funcs, Some(quadratic_to::<S>), user_data, None);
+        hb_draw_funcs_set_cubic_to_func(funcs, Some(cubic_to::<S>), user_data, None);
+        hb_draw_funcs_set_close_path_func(funcs, Some(close_path::<S>), user_data, None);
+        hb_draw_funcs_make_immutable(funcs);
+        funcs
+    }
+
+    /// An affine transformation as passed to [`GlyphPainter::push_transform`],
+    /// `x' = xx * x + xy * y + dx` and `y' = yx * x + yy * y + dy`.
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub struct Transform {
+        pub xx: f32,
+        pub yx: f32,
+        pub xy: f32,
+        pub yy: f32,
+        pub dx: f32,
+        pub dy: f32,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct GlyphExtents {
+        pub x_bearing: i32,
+        pub y_bearing: i32,
+        pub width: i32,
+        pub height: i32,
+    }
+
+    impl From<hb_glyph_extents_t> for GlyphExtents {
+        fn from(extents: hb_glyph_extents_t) -> Self {
+            GlyphExtents {
+                x_bearing: extents.x_bearing,
+                y_bearing: extents.y_bearing,
+                width: extents.width,
+                height: extents.height,
+            }
+        }
+    }
+
+    /// How a gradient is continued outside of its color stops.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum PaintExtend {
+        Pad,
+        Repeat,
+        Reflect,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub struct ColorStop {
+        pub offset: f32,
+        /// The stop uses the foreground color, `color` then only carries its alpha.
+        pub is_foreground: bool,
+        pub color: Color,
+    }
+
+    /// The color stops of a gradient.
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct ColorLine {
+        pub extend: PaintExtend,
+        pub stops: Vec<ColorStop>,
+    }
+
+    impl ColorLine {
+        unsafe fn from_raw(color_line: *mut hb_color_line_t) -> Self {
+            let extend = match hb_color_line_get_extend(color_line) {
+                harfbuzz_sys::hb_paint_extend_t_HB_PAINT_EXTEND_REPEAT => PaintExtend::Repeat,
+                harfbuzz_sys::hb_paint_extend_t_HB_PAINT_EXTEND_REFLECT => PaintExtend::Reflect,
+                _ => PaintExtend::Pad,
+            };
+            let count =
+                hb_color_line_get_color_stops(color_line, 0, ptr::null_mut(), ptr::null_mut());
+            let mut stops = vec![
+                hb_color_stop_t {
+                    offset: 0.0,
+                    is_foreground: 0,
+                    color: 0,
+                };
+                count as usize
+            ];
+            let mut stop_count = count;
+            hb_color_line_get_color_stops(color_line, 0, &mut stop_count, stops.as_mut_ptr());
+            stops.truncate(stop_count as usize);
+            ColorLine {
+                extend,
+                stops: stops
+                    .into_iter()
+                    .map(|stop| ColorStop {
+                        offset: stop.offset,
+                        is_foreground: stop.is_foreground != 0,
+                        color: stop.color.into(),
+                    })
+                    .collect(),
+            }
+        }
+    }
+
+    /// Blend modes of [`GlyphPainter::pop_group`], see the COLRv1 specification.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum CompositeMode {
+        Clear,
+        Src,
+        Dest,
+        SrcOver,
+        DestOver,
+        SrcIn,
+        DestIn,
+        SrcOut,
+        DestOut,
+        SrcAtop,
+        DestAtop,
+        Xor,
+        Plus,
+        Screen,
+        Overlay,
+        Darken,
+        Lighten,
+        ColorDodge,
+        ColorBurn,
+        HardLight,
+        SoftLight,
+        Difference,
+        Exclusion,
+        Multiply,
+        HslHue,
+        HslSaturation,
+        HslColor,
+        HslLuminosity,
+    }
+
+    impl From<hb_paint_composite_mode_t> for CompositeMode {
+        fn from(mode: hb_paint_composite_mode_t) -> Self {
+            // the values of hb_paint_composite_mode_t are consecutive
+            const MODES: [CompositeMode; 28] = [
+                CompositeMode::Clear,
+                CompositeMode::Src,
+                CompositeMode::Dest,
+                CompositeMode::SrcOver,
+                CompositeMode::DestOver,
+                CompositeMode::SrcIn,
+                CompositeMode::DestIn,
+                CompositeMode::SrcOut,
+                CompositeMode::DestOut,
+                CompositeMode::SrcAtop,
+                CompositeMode::DestAtop,
+                CompositeMode::Xor,
+                CompositeMode::Plus,
+                CompositeMode::Screen,
+                CompositeMode::Overlay,
+                CompositeMode::Darken,
+                CompositeMode::Lighten,
+                CompositeMode::ColorDodge,
+                CompositeMode::ColorBurn,
+                CompositeMode::HardLight,
+                CompositeMode::SoftLight,
+                CompositeMode::Difference,
+                CompositeMode::Exclusion,
+                CompositeMode::Multiply,
+                CompositeMode::HslHue,
+                CompositeMode::HslSaturation,
+                CompositeMode::HslColor,
+                CompositeMode::HslLuminosity,
+            ];
+            MODES
+                .get(mode as usize)
+                .copied()
+                .unwrap_or(CompositeMode::SrcOver)
+        }
+    }
+
+    /// Encoding of the data passed to [`GlyphPainter::image`].
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum ImageFormat {
+        Png,
+        Svg,
+        /// Premultiplied BGRA pixels, `width * 4` bytes per row.
+        Bgra,
+        Other(u32),
+    }
+
+    impl From<hb_tag_t> for ImageFormat {
+        fn from(tag: hb_tag_t) -> Self {
+            match &tag.to_be_bytes() {
+                b"png " => ImageFormat::Png,
+                b"svg " => ImageFormat::Svg,
+                b"BGRA" => ImageFormat::Bgra,
+                _ => ImageFormat::Other(tag),
+            }
+        }
+    }
+
+    /// Receives the paint operations of a color glyph, one method per
+    /// callback of `hb_paint_funcs_t`. Push and pop calls are balanced, all
+    /// methods default to doing nothing.
+    pub trait GlyphPainter {
+        fn push_transform(&mut self, _transform: Transform) {}
+
+        fn pop_transform(&mut self) {}
+
+        /// Clips to the outline of the glyph in the font that is painted.
+        fn push_clip_glyph(&mut self, _glyph: u32) {}
+
+        fn push_clip_rectangle(&mut self, _xmin: f32, _ymin: f32, _xmax: f32, _ymax: f32) {}
+
+        fn pop_clip(&mut self) {}
+
+        /// Fills the current clip with a solid color, if `is_foreground` is
+        /// set the color is the foreground color passed to `paint_glyph`.
+        fn color(&mut self, _is_foreground: bool, _color: Color) {}
+
+        /// Paints a bitmap or SVG glyph image, returns whether the image was
+        /// painted.
+        fn image(
+            &mut self,
+            _image: &[u8],
+            _width: u32,
+            _height: u32,
+            _format: ImageFormat,
+            _slant: f32,
+            _extents: Option<GlyphExtents>,
+        ) -> bool {
+            false
+        }
+
+        /// Fills the current clip with a linear gradient from `p0` to `p1`,
+        /// `p2` defines the rotation of the gradient.
+        fn linear_gradient(
+            &mut self,
+            _color_line: &ColorLine,
+            _p0: (f32, f32),
+            _p1: (f32, f32),
+            _p2: (f32, f32),
+        ) {
+        }
+
+        fn radial_gradient(
+            &mut self,
+            _color_line: &ColorLine,
+            _c0: (f32, f32),
+            _r0: f32,
+            _c1: (f32, f32),
+            _r1: f32,
+        ) {
+        }
+
+        /// Angles are in radians.
+        fn sweep_gradient(
+            &mut self,
+            _color_line: &ColorLine,
+            _center: (f32, f32),
+            _start_angle: f32,
+            _end_angle: f32,
+        ) {
+        }
+
+        fn push_group(&mut self) {}
+
+        /// Composites the group painted since the matching `push_group`
+        /// onto the content below it.
+        fn pop_group(&mut self, _mode: CompositeMode) {}
+
+        /// Overrides a color of the palette, `None` uses the palette color.
+        fn custom_palette_color(&mut self, _color_index: u32) -> Option<Color> {
+            None
+        }
+    }
+
+    /// Creates paint funcs that forward every callback to the `P` of the
+    /// [`CallbackData`] passed as paint data.
+    unsafe fn paint_funcs<P: GlyphPainter>() -> *mut hb_paint_funcs_t {
+        #[allow(clippy::too_many_arguments)]
+        unsafe extern "C" fn push_transform<P: GlyphPainter>(
+            _funcs: *mut hb_paint_funcs_t,
+            paint_data: *mut c_void,
+            xx: f32,
+            yx: f32,
+            xy: f32,
+            yy: f32,
+            dx: f32,
+            dy: f32,
+            _user_data: *mut c_void,
+        ) {
+            let transform = Transform {
+                xx,
+                yx,
+                xy,
+                yy,
+                dx,
+                dy,
+            };
+            CallbackData::<P>::call(paint_data, |painter| painter.push_transform(transform));
+        }
+
+        unsafe extern "C" fn pop_transform<P: GlyphPainter>(
+            _funcs: *mut hb_paint_funcs_t,
+            paint_data: *mut c_void,
+            _user_data: *mut c_void,
+        ) {
+            CallbackData::<P>::call(paint_data, |painter| painter.pop_transform());
+        }
+
+        unsafe extern "C" fn push_clip_glyph<P: GlyphPainter>(
+            _funcs: *mut hb_paint_funcs_t,
+            paint_data: *mut c_void,
+            glyph: hb_codepoint_t,
+            _font: *mut hb_font_t,
+            _user_data: *mut c_void,
+        ) {
+            CallbackData::<P>::call(paint_data, |painter| painter.push_clip_glyph(glyph));
+        }
+
+        unsafe extern "C" fn push_clip_rectangle<P: GlyphPainter>(
+            _funcs: *mut hb_paint_funcs_t,
+            paint_data: *mut c_void,
+            xmin: f32,
+            ymin: f32,
+            xmax: f32,
+            ymax: f32,
+            _user_data: *mut c_void,
+        ) {
+            CallbackData::<P>::call(paint_data, |painter| {
+                painter.push_clip_rectangle(xmin, ymin, xmax, ymax)
+            });
+        }
+
+        unsafe extern "C" fn pop_clip<P: GlyphPainter>(
+            _funcs: *mut hb_paint_funcs_t,
+            paint_data: *mut c_void,
+            _user_data: *mut c_void,
+        ) {
+            CallbackData::<P>::call(paint_data, |painter| painter.pop_clip());
+        }
+
+        unsafe extern "C" fn color<P: GlyphPainter>(
+            _funcs: *mut hb_paint_funcs_t,
+            paint_data: *mut c_void,
+            is_foreground: hb_bool_t,
+            color: hb_color_t,
+            _user_data: *mut c_void,
+        ) {
+            CallbackData::<P>::call(paint_data, |painter| {
+                painter.color(is_foreground != 0, color.into())
+            });
+        }
+
+        #[allow(clippy::too_many_arguments)]
+        unsafe extern "C" fn image<P: GlyphPainter>(
+            _funcs: *mut hb_paint_funcs_t,
+            paint_data: *mut c_void,
+            image: *mut hb_blob_t,
+            width: c_uint,
+            height: c_uint,
+            format: hb_tag_t,
+            slant: f32,
+            extents: *mut hb_glyph_extents_t,
+            _user_data: *mut c_void,
+        ) -> hb_bool_t {
+            let mut length: c_uint = 0;
+            let data = hb_blob_get_data(image, &mut length);
+            let data = if data.is_null() {
+                &[]
+            } else {
+                std::slice::from_raw_parts(data as *const u8, length as usize)
+            };
+            let extents = if extents.is_null() {
+                None
+            } else {
+                Some((*extents).into())
+            };
+            CallbackData::<P>::call(paint_data, |painter| {
+                painter.image(data, width, height, format.into(), slant, extents)
+            })
+            .unwrap_or(false) as hb_bool_t
+        }
+
+        #[allow(clippy::too_many_arguments)]
+        unsafe extern "C" fn linear_gradient<P: GlyphPainter>(
+            _funcs: *mut hb_paint_funcs_t,
+            paint_data: *mut c_void,
+            color_line: *mut hb_color_line_t,
+            x0: f32,
+            y0: f32,
+            x1: f32,
+            y1: f32,
+            x2: f32,
+            y2: f32,
+            _user_data: *mut c_void,
+        ) {
+            let color_line = ColorLine::from_raw(color_line);
+            CallbackData::<P>::call(paint_data, |painter| {
+                painter.linear_gradient(&color_line, (x0, y0), (x1, y1), (x2, y2))
+            });
+        }
+
+        #[allow(clippy::too_many_arguments)]
+        unsafe extern "C" fn radial_gradient<P: GlyphPainter>(
+            _funcs: *mut hb_paint_funcs_t,
+            paint_data: *mut c_void,
+            color_line: *mut hb_color_line_t,
+            x0: f32,
+            y0: f32,
+            r0: f32,
+            x1: f32,
+            y1: f32,
+            r1: f32,
+            _user_data: *mut c_void,
+        ) {
+            let color_line = ColorLine::from_raw(color_line);
+            CallbackData::<P>::call(paint_data, |painter| {
+                painter.radial_gradient(&color_line, (x0, y0), r0, (x1, y1), r1)
+            });
+        }
+
+        #[allow(clippy::too_many_arguments)]
+        unsafe extern "C" fn sweep_gradient<P: GlyphPainter>(
+            _funcs: *mut hb_paint_funcs_t,
+            paint_data: *mut c_void,
+            color_line: *mut hb_color_line_t,
+            x0: f32,
+            y0: f32,
+            start_angle: f32,
+            end_angle: f32,
+            _user_data: *mut c_void,
+        ) {
+            let color_line = ColorLine::from_raw(color_line);
+            CallbackData::<P>::call(paint_data, |painter| {
+                painter.sweep_gradient(&color_line, (x0, y0), start_angle, end_angle)
+            });
+        }
+
+        unsafe extern "C" fn push_group<P: GlyphPainter>(
+            _funcs: *mut hb_paint_funcs_t,
+            paint_data: *mut c_void,
+            _user_data: *mut c_void,
+        ) {
+            CallbackData::<P>::call(paint_data, |painter| painter.push_group());
+        }
+
+        unsafe extern "C" fn pop_group<P: GlyphPainter>(
+            _funcs: *mut hb_paint_funcs_t,
+            paint_data: *mut c_void,
+            mode: hb_paint_composite_mode_t,
+            _user_data: *mut c_void,
+        ) {
+            CallbackData::<P>::call(paint_data, |painter| painter.pop_group(mode.into()));
+        }
+
+        unsafe extern "C" fn custom_palette_color<P: GlyphPainter>(
+            _funcs: *mut hb_paint_funcs_t,
+            paint_data: *mut c_void,
+            color_index: c_uint,
+            color: *mut hb_color_t,
+            _user_data: *mut c_void,
+        ) -> hb_bool_t {
+            let custom = CallbackData::<P>::call(paint_data, |painter| {
+                painter.custom_palette_color(color_index)
+            });
+            match custom.flatten() {
+                Some(custom) => {
+                    *color = custom.into();
+                    1
+                }
+                None => 0,
+            }
+        }
+
+        let funcs = hb_paint_funcs_create();
+        let user_data = ptr::null_mut();
+        hb_paint_funcs_set_push_transform_func(funcs, Some(push_transform::<P>), user_data, None);
+        hb_paint_funcs_set_pop_transform_func(funcs, Some(pop_transform::<P>), user_data, None);
+        hb_paint_funcs_set_push_clip_glyph_func(funcs, Some(push_clip_glyph::<P>), user_data, None);
+        hb_paint_funcs_set_push_clip_rectangle_func(
+            funcs,
+            Some(push_clip_rectangle::<P>),
+            user_data,
+            None,
+        );
+        hb_paint_funcs_set_pop_clip_func(funcs, Some(pop_clip::<P>), user_data, None);
+        hb_paint_funcs_set_color_func(funcs, Some(color::<P>), user_data, None);
+        hb_paint_funcs_set_image_func(funcs, Some(image::<P>), user_data, None);
+        hb_paint_funcs_set_linear_gradient_func(funcs, Some(linear_gradient::<P>), user_data, None);
+        hb_paint_funcs_set_radial_gradient_func(funcs, Some(radial_gradient::<P>), user_data, None);
+        hb_paint_funcs_set_sweep_gradient_func(funcs, Some(sweep_gradient::<P>), user_data, None);
+        hb_paint_funcs_set_push_group_func(funcs, Some(push_group::<P>), user_data, None);
+        hb_paint_funcs_set_pop_group_func(funcs, Some(pop_group::<P>), user_data, None);
+        hb_paint_funcs_set_custom_palette_color_func(
+            funcs,
+            Some(custom_palette_color::<P>),
+            user_data,
+            None,
+        );
+        hb_paint_funcs_make_immutable(funcs);
+        funcs
+    }
+}
+
+pub mod pango2 {
+    use super::cairo;
+    use super::harfbuzz;
+    use glib_sys::{g_bytes_get_data, g_bytes_new, g_bytes_unref, g_error_free, g_free};
+    use glib_sys::{gpointer, GType};
+    use gobject_sys::{
+        g_object_new_with_properties, g_object_ref, g_object_unref, g_type_class_peek_parent,
+        g_type_query, g_type_register_static_simple, GObject, GTypeQuery,
+    };
+    use pango2_sys::{pango2_font_description_free, pango2_hb_face_new_from_hb_face};
+    use std::{
+        any::Any,
+        ffi::{c_char, c_int, c_uint, CStr, CString},
+        fmt,
+        marker::PhantomData,
+        mem,
+        ops::BitOr,
+        panic::{self, AssertUnwindSafe},
+        ptr::{self, NonNull},
+        slice,
+        sync::{
+            atomic::{AtomicPtr, Ordering},
+            OnceLock,
+        },
+    };
+
+    /// Copies a newly allocated C string into a `String` and frees it.
+    unsafe fn take_string(raw: *mut c_char) -> String {
+        if raw.is_null() {
+            return String::new();
+        }
+        let string = CStr::from_ptr(raw).to_string_lossy().to_string();
+        g_free(raw as *mut _);
+        string
+    }
+
+    /// Number of Pango units in one device unit.
+    pub const SCALE: i32 = 1024;
+
+    pub fn units_to_double(units: i32) -> f64 {
+        unsafe { pango2_sys::pango2_units_to_double(units as c_int) }
+    }
+
+    pub fn units_from_double(value: f64) -> i32 {
+        unsafe { pango2_sys::pango2_units_from_double(value) as i32 }
+    }
+
+    /// Copies the contents of a `GBytes` into a vector and releases the `GBytes`.
+    unsafe fn bytes_into_vec(bytes: *mut pango2_sys::GBytes) -> Vec<u8> {
+        let bytes = bytes as *mut glib_sys::GBytes;
+        let mut size = 0;
+        let data = g_bytes_get_data(bytes, &mut size) as *const u8;
+        let vec = if data.is_null() {
+            Vec::new()
+        } else {
+            slice::from_raw_parts(data, size).to_vec()
+        };
+        g_bytes_unref(bytes);
+        vec
+    }
+
+    pub struct Pango2HbFace {
+        raw: *mut pango2_sys::Pango2HbFace,
+    }
+
+    impl Pango2HbFace {
+        pub fn from_hb_face(face: &harfbuzz::Face, instance_id: i32) -> Self {
+            unsafe {
+                let raw = pango2_hb_face_new_from_hb_face(
+                    *face.raw(),
+                    instance_id as c_int,
+                    ptr::null(),
+                    ptr::null(),
+                );
+                Self { raw }
+            }
+        }
+
+        /// Names the palette at `index` of the face, so it can be selected
+        /// with [`Pango2Context::set_palette`] or a palette attribute.
+        pub fn set_palette_name(&self, name: &str, index: u32) {
+            unsafe {
+                let cname = CString::new(name).unwrap();
+                pango2_sys::pango2_hb_face_set_palette_name(self.raw, cname.as_ptr(), index);
+            }
+        }
+
+        pub fn palette_name(&self, index: u32) -> Option<String> {
+            unsafe {
+                let name = pango2_sys::pango2_hb_face_get_palette_name(self.raw, index);
+                if name.is_null() {
+                    None
+                } else {
+                    Some(CStr::from_ptr(name).to_string_lossy().to_string())
+                }
+            }
+        }
+    }
+
+    /* 
+    TODO This crashes / segfaults
+
+        impl Drop for Pango2HbFace {
+            fn drop(&mut self) {
+                unsafe {
+                    g_object_unref(self.raw as *mut GObject);
+                }
+            }
+        }
+    */
+
+    pub struct Pango2FontMap {
+        raw: *mut pango2_sys::Pango2FontMap,
+    }
+
+    impl Pango2FontMap {
+        pub fn new() -> Self {
+            unsafe {
+                Pango2FontMap {
+                    raw: pango2_sys::pango2_font_map_new(),
+                }
+            }
+        }
+
+        pub fn add_face(&self, face: &Pango2HbFace) {
+            unsafe {
+                pango2_sys::pango2_font_map_add_face(
+                    self.raw,
+                    face.raw as *mut pango2_sys::Pango2FontFace,
+                );
+            }
+        }
+    }
+
+    impl Default for Pango2FontMap {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    impl Drop for Pango2FontMap {
+        fn drop(&mut self) {
+            unsafe {
+                g_object_unref(self.raw as *mut GObject);
+            }
+        }
+    }
+
+    pub struct Pango2Context {
+        raw: *mut pango2_sys::Pango2Context,
+    }
+
+    impl Pango2Context {
+        pub fn from_font_map(font_map: &Pango2FontMap) -> Self {
+            unsafe {
+                Pango2Context {
+                    raw: pango2_sys::pango2_context_new_with_font_map(font_map.raw),
+                }
+            }
+        }
+
+        pub fn update_cairo_context(&self, cairo_context: &cairo::CairoContext) {
+            unsafe {
+                pango2_sys::pango2_cairo_update_context(*cairo_context.raw(), self.raw);
+            }
+        }
+
+        pub fn set_language(&self, language: Language) {
+            unsafe {
+                pango2_sys::pango2_context_set_language(self.raw, language.raw);
+            }
+        }
+
+        pub fn language(&self) -> Option<Language> {
+            unsafe { Language::from_raw(pango2_sys::pango2_context_get_language(self.raw)) }
+        }
+
+        /// Sets the direction used for paragraphs without strong directional characters.
+        pub fn set_base_dir(&self, direction: Direction) {
+            unsafe {
+                pango2_sys::pango2_context_set_base_dir(self.raw, direction.into());
+            }
+        }
+
+        pub fn base_dir(&self) -> Direction {
+            unsafe { pango2_sys::pango2_context_get_base_dir(self.raw).into() }
+        }
+
+        /// Sets the gravity of the text, [`Gravity::East`] lays out text vertically.
+        pub fn set_base_gravity(&self, gravity: Gravity) {
+            unsafe {
+                pango2_sys::pango2_context_set_base_gravity(self.raw, gravity.into());
+            }
+        }
+
+        pub fn base_gravity(&self) -> Gravity {
+            unsafe { pango2_sys::pango2_context_get_base_gravity(self.raw).into() }
+        }
+
+        /// Returns the resolved gravity, which is the base gravity unless
+        /// that is [`Gravity::Auto`], in which case it's derived from the matrix.
+        pub fn gravity(&self) -> Gravity {
+            unsafe { pango2_sys::pango2_context_get_gravity(self.raw).into() }
+        }
+
+        pub fn set_gravity_hint(&self, hint: GravityHint) {
+            unsafe {
+                pango2_sys::pango2_context_set_gravity_hint(self.raw, hint.into());
+            }
+        }
+
+        pub fn gravity_hint(&self) -> GravityHint {
+            unsafe { pango2_sys::pango2_context_get_gravity_hint(self.raw).into() }
+        }
+
+        /// Sets the transformation from user space to device space, `None`
+        /// resets it to the identity.
+        pub fn set_matrix(&self, matrix: Option<&Matrix>) {
+            unsafe {
+                let matrix = matrix.map(|matrix| pango2_sys::Pango2Matrix::from(*matrix));
+                pango2_sys::pango2_context_set_matrix(
+                    self.raw,
+                    matrix.as_ref().map_or(ptr::null(), |matrix| matrix),
+                );
+            }
+        }
+
+        pub fn matrix(&self) -> Option<Matrix> {
+            unsafe {
+                let matrix = pango2_sys::pango2_context_get_matrix(self.raw);
+                if matrix.is_null() {
+                    None
+                } else {
+                    Some((*matrix).into())
+                }
+            }
+        }
+
+        /// Disable rounding of glyph positions to whole pixels, e.g. for
+        /// smooth subpixel animation.
+        pub fn set_round_glyph_positions(&self, round_positions: bool) {
+            unsafe {
+                pango2_sys::pango2_context_set_round_glyph_positions(
+                    self.raw,
+                    round_positions as pango2_sys::gboolean,
+                );
+            }
+        }
+
+        pub fn round_glyph_positions(&self) -> bool {
+            unsafe { pango2_sys::pango2_context_get_round_glyph_positions(self.raw) != 0 }
+        }
+
+        /// Sets the color palette used for color fonts, either `"default"`,
+        /// `"light"`, `"dark"` or a palette name like `"palette3"`.
+        pub fn set_palette(&self, palette: &str) {
+            unsafe {
+                let cpalette = CString::new(palette).unwrap();
+                pango2_sys::pango2_context_set_palette(self.raw, cpalette.as_ptr());
+            }
+        }
+
+        pub fn palette(&self) -> Option<String> {
+            unsafe {
+                let palette = pango2_sys::pango2_context_get_palette(self.raw);
+                if palette.is_null() {
+                    None
+                } else {
+                    Some(CStr::from_ptr(palette).to_string_lossy().to_string())
+                }
+            }
+        }
+
+        /// Sets the Cairo font options used for rendering: antialiasing,
+        /// hinting, variations and the palette colors of color fonts. Layouts
+        /// created from this context pick the options up on their next
+        /// relayout.
+        pub fn set_font_options(&self, options: &cairo::FontOptions) {
+            unsafe {
+                pango2_sys::pango2_cairo_context_set_font_options(
+                    self.raw,
+                    *options.raw() as *const pango2_sys::cairo_font_options_t,
+                );
+            }
+        }
+
+        pub fn font_options(&self) -> Option<cairo::FontOptions> {
+            unsafe {
+                let options = pango2_sys::pango2_cairo_context_get_font_options(self.raw);
+                cairo::FontOptions::copy_from(options as *const cairo_sys::cairo_font_options_t)
+            }
+        }
+
+        pub fn set_emoji_presentation(&self, presentation: EmojiPresentation) {
+            unsafe {
+                pango2_sys::pango2_context_set_emoji_presentation(self.raw, presentation.into());
+            }
+        }
+
+        pub fn emoji_presentation(&self) -> EmojiPresentation {
+            unsafe { pango2_sys::pango2_context_get_emoji_presentation(self.raw).into() }
+        }
+
+        /// Returns the metrics of the fonts selected by `description`,
+        /// averaged over the fonts used for `language`. `None` uses the
+        /// language of the context.
+        pub fn metrics(
+            &self,
+            description: &FontDescription,
+            language: Option<Language>,
+        ) -> FontMetrics {
+            unsafe {
+                FontMetrics::take(pango2_sys::pango2_context_get_metrics(
+                    self.raw,
+                    description.raw,
+                    language.map_or(ptr::null_mut(), |language| language.raw),
+                ))
+            }
+        }
+
+        /// Loads the font of the context's font map that matches
+        /// `description` best, `None` if the font map has no fonts.
+        pub fn load_font(&self, description: &FontDescription) -> Option<Pango2Font> {
+            unsafe {
+                let raw = pango2_sys::pango2_context_load_font(self.raw, description.raw);
+                if raw.is_null() {
+                    None
+                } else {
+                    Some(Pango2Font { raw })
+                }
+            }
+        }
+    }
+
+    impl Drop for Pango2Context {
+        fn drop(&mut self) {
+            unsafe {
+                g_object_unref(self.raw as *mut GObject);
+            }
+        }
+    }
+
+    /// A language tag like `he` or `ja-JP`. Languages are interned by Pango
+    /// and live for the lifetime of the process.
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    pub struct Language {
+        raw: *mut pango2_sys::Pango2Language,
+    }
+
+    impl Language {
+        pub fn from_string(language: &str) -> Option<Self> {
+            unsafe {
+                let clanguage = CString::new(language).ok()?;
+                Self::from_raw(pango2_sys::pango2_language_from_string(clanguage.as_ptr()))
+            }
+        }
+
+        /// Returns the language of the current locale.
+        pub fn system_default() -> Self {
+            unsafe {
+                Language {
+                    raw: pango2_sys::pango2_language_get_default(),
+                }
+            }
+        }
+
+        unsafe fn from_raw(raw: *mut pango2_sys::Pango2Language) -> Option<Self> {
+            if raw.is_null() {
+                None
+            } else {
+                Some(Language { raw })
+            }
+        }
+    }
+
+    impl fmt::Display for Language {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            let string = unsafe { CStr::from_ptr(pango2_sys::pango2_language_to_string(self.raw)) };
+            f.write_str(&string.to_string_lossy())
+        }
+    }
+
+    impl fmt::Debug for Language {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_tuple("Language").field(&self.to_string()).finish()
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum Direction {
+        Ltr,
+        Rtl,
+        WeakLtr,
+        WeakRtl,
+        Neutral,
+    }
+
+    impl From<Direction> for pango2_sys::Pango2Direction {
+        fn from(direction: Direction) -> Self {
+            match direction {
+                Direction::Ltr => pango2_sys::Pango2Direction_PANGO2_DIRECTION_LTR,
+                Direction::Rtl => pango2_sys::Pango2Direction_PANGO2_DIRECTION_RTL,
+                Direction::WeakLtr => pango2_sys::Pango2Direction_PANGO2_DIRECTION_WEAK_LTR,
+                Direction::WeakRtl => pango2_sys::Pango2Direction_PANGO2_DIRECTION_WEAK_RTL,
+                Direction::Neutral => pango2_sys::Pango2Direction_PANGO2_DIRECTION_NEUTRAL,
+            }
+        }
+    }
+
+    impl From<pango2_sys::Pango2Direction> for Direction {
+        fn from(direction: pango2_sys::Pango2Direction) -> Self {
+            match direction {
+                pango2_sys::Pango2Direction_PANGO2_DIRECTION_LTR => Direction::Ltr,
+                pango2_sys::Pango2Direction_PANGO2_DIRECTION_RTL => Direction::Rtl,
+                pango2_sys::Pango2Direction_PANGO2_DIRECTION_WEAK_LTR => Direction::WeakLtr,
+                pango2_sys::Pango2Direction_PANGO2_DIRECTION_WEAK_RTL => Direction::WeakRtl,
+                _ => Direction::Neutral,
+            }
+        }
+    }
+
+    /// The direction the bottom of the glyphs point to, [`Gravity::South`]
+    /// is regular horizontal text.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    pub enum Gravity {
+        #[default]
+        South,
+        East,
+        North,
+        West,
+        Auto,
+    }
+
+    impl Gravity {
+        /// Returns the rotation in radians the glyphs are drawn with.
+        pub fn to_rotation(self) -> f64 {
+            unsafe { pango2_sys::pango2_gravity_to_rotation(self.into()) }
+        }
+
+        /// Returns the gravity matching the rotation of the matrix.
+        pub fn for_matrix(matrix: &Matrix) -> Self {
+            unsafe {
+                let matrix: pango2_sys::Pango2Matrix = (*matrix).into();
+                pango2_sys::pango2_gravity_get_for_matrix(&matrix).into()
+            }
+        }
+
+        pub fn is_vertical(self) -> bool {
+            matches!(self, Gravity::East | Gravity::West)
+        }
+    }
+
+    impl From<Gravity> for pango2_sys::Pango2Gravity {
+        fn from(gravity: Gravity) -> Self {
+            match gravity {
+                Gravity::South => pango2_sys::Pango2Gravity_PANGO2_GRAVITY_SOUTH,
+                Gravity::East => pango2_sys::Pango2Gravity_PANGO2_GRAVITY_EAST,
+                Gravity::North => pango2_sys::Pango2Gravity_PANGO2_GRAVITY_NORTH,
+                Gravity::West => pango2_sys::Pango2Gravity_PANGO2_GRAVITY_WEST,
+                Gravity::Auto => pango2_sys::Pango2Gravity_PANGO2_GRAVITY_AUTO,
+            }
+        }
+    }
+
+    impl From<pango2_sys::Pango2Gravity> for Gravity {
+        fn from(gravity: pango2_sys::Pango2Gravity) -> Self {
+            match gravity {
+                pango2_sys::Pango2Gravity_PANGO2_GRAVITY_SOUTH => Gravity::South,
+                pango2_sys::Pango2Gravity_PANGO2_GRAVITY_EAST => Gravity::East,
+                pango2_sys::Pango2Gravity_PANGO2_GRAVITY_NORTH => Gravity::North,
+                pango2_sys::Pango2Gravity_PANGO2_GRAVITY_WEST => Gravity::West,
+                _ => Gravity::Auto,
+            }
+        }
+    }
+
+    /// How the gravity of individual scripts relates to the base gravity
+    /// in vertical text.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    pub enum GravityHint {
+        /// Scripts take their natural gravity, e.g. Latin is rotated.
+        #[default]
+        Natural,
+        /// All scripts use the base gravity.
+        Strong,
+        /// Like natural, but rotated scripts face the same direction as the line.
+        Line,
+    }
+
+    impl From<GravityHint> for pango2_sys::Pango2GravityHint {
+        fn from(hint: GravityHint) -> Self {
+            match hint {
+                GravityHint::Natural => pango2_sys::Pango2GravityHint_PANGO2_GRAVITY_HINT_NATURAL,
+                GravityHint::Strong => pango2_sys::Pango2GravityHint_PANGO2_GRAVITY_HINT_STRONG,
+                GravityHint::Line => pango2_sys::Pango2GravityHint_PANGO2_GRAVITY_HINT_LINE,
+            }
+        }
+    }
+
+    impl From<pango2_sys::Pango2GravityHint> for GravityHint {
+        fn from(hint: pango2_sys::Pango2GravityHint) -> Self {
+            match hint {
+                pango2_sys::Pango2GravityHint_PANGO2_GRAVITY_HINT_STRONG => GravityHint::Strong,
+                pango2_sys::Pango2GravityHint_PANGO2_GRAVITY_HINT_LINE => GravityHint::Line,
+                _ => GravityHint::Natural,
+            }
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    pub enum EmojiPresentation {
+        /// Use the default presentation of each character.
+        #[default]
+        Auto,
+        Text,
+        Emoji,
+    }
+
+    impl From<EmojiPresentation> for pango2_sys::Pango2EmojiPresentation {
+        fn from(presentation: EmojiPresentation) -> Self {
+            match presentation {
+                EmojiPresentation::Auto => {
+                    pango2_sys::Pango2EmojiPresentation_PANGO2_EMOJI_PRESENTATION_AUTO
+                }
+                EmojiPresentation::Text => {
+                    pango2_sys::Pango2EmojiPresentation_PANGO2_EMOJI_PRESENTATION_TEXT
+                }
+                EmojiPresentation::Emoji => {
+                    pango2_sys::Pango2EmojiPresentation_PANGO2_EMOJI_PRESENTATION_EMOJI
+                }
+            }
+        }
+    }
+
+    impl From<pango2_sys::Pango2EmojiPresentation> for EmojiPresentation {
+        fn from(presentation: pango2_sys::Pango2EmojiPresentation) -> Self {
+            match presentation {
+                pango2_sys::Pango2EmojiPresentation_PANGO2_EMOJI_PRESENTATION_TEXT => {
+                    EmojiPresentation::Text
+                }
+                pango2_sys::Pango2EmojiPresentation_PANGO2_EMOJI_PRESENTATION_EMOJI => {
+                    EmojiPresentation::Emoji
+                }
+                _ => EmojiPresentation::Auto,
+            }
+        }
+    }
+
+    /// An affine transformation, `x' = xx * x + xy * y + x0` and
+    /// `y' = yx * x + yy * y + y0`.
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub struct Matrix {
+        pub xx: f64,
+        pub xy: f64,
+        pub yx: f64,
+        pub yy: f64,
+        pub x0: f64,
+        pub y0: f64,
+    }
+
+    impl Matrix {
+        pub const IDENTITY: Matrix = Matrix {
+            xx: 1.0,
+            xy: 0.0,
+            yx: 0.0,
+            yy: 1.0,
+            x0: 0.0,
+            y0: 0.0,
+        };
+
+        pub fn translate(&mut self, tx: f64, ty: f64) {
+            self.apply(|matrix| unsafe { pango2_sys::pango2_matrix_translate(matrix, tx, ty) });
+        }
+
+        pub fn scale(&mut self, scale_x: f64, scale_y: f64) {
+            self.apply(|matrix| unsafe {
+                pango2_sys::pango2_matrix_scale(matrix, scale_x, scale_y)
+            });
+        }
+
+        /// Rotates counter-clockwise by the given angle in degrees.
+        pub fn rotate(&mut self, degrees: f64) {
+            self.apply(|matrix| unsafe { pango2_sys::pango2_matrix_rotate(matrix, degrees) });
+        }
+
+        fn apply(&mut self, f: impl FnOnce(&mut pango2_sys::Pango2Matrix)) {
+            let mut matrix = (*self).into();
+            f(&mut matrix);
+            *self = matrix.into();
+        }
+    }
+
+    impl Default for Matrix {
+        fn default() -> Self {
+            Self::IDENTITY
+        }
+    }
+
+    impl From<pango2_sys::Pango2Matrix> for Matrix {
+        fn from(matrix: pango2_sys::Pango2Matrix) -> Self {
+            Matrix {
+                xx: matrix.xx,
+                xy: matrix.xy,
+                yx: matrix.yx,
+                yy: matrix.yy,
+                x0: matrix.x0,
+                y0: matrix.y0,
+            }
+        }
+    }
+
+    impl From<Matrix> for pango2_sys::Pango2Matrix {
+        fn from(matrix: Matrix) -> Self {
+            pango2_sys::Pango2Matrix {
+                xx: matrix.xx,
+                xy: matrix.xy,
+                yx: matrix.yx,
+                yy: matrix.yy,
+                x0: matrix.x0,
+                y0: matrix.y0,
+            }
+        }
+    }
+
+    pub struct Pango2Layout {
+        raw: *mut pango2_sys::Pango2Layout,
+    }
+
+    impl Pango2Layout {
+        pub fn new(context: &Pango2Context) -> Self {
+            unsafe {
+                Pango2Layout {
+                    raw: pango2_sys::pango2_layout_new(context.raw),
+                }
+            }
+        }
+
+        pub fn set_font_description_string(&self, font_description: &str) {
+            let description = FontDescription::from_string(font_description);
+            self.set_font_description(&description);
+        }
+
+        pub fn set_font_description(&self, font_description: &FontDescription) {
+            unsafe {
+                pango2_sys::pango2_layout_set_font_description(self.raw, font_description.raw);
+            }
+        }
+
+        pub fn set_text(&self, text: &str) {
+            unsafe {
+                let ctext = CString::new(text).unwrap();
+                pango2_sys::pango2_layout_set_text(self.raw, ctext.as_ptr(), -1);
+            }
+        }
+
+        /// Draws the layout with its top left corner at the current point.
+        pub fn paint(&self, cairo_context: &cairo::CairoContext) {
+            unsafe {
+                pango2_sys::pango2_cairo_show_layout(*cairo_context.raw(), self.raw);
+            }
+        }
+
+        /// A copy of the lines of the layout at their positions. Changing
+        /// the layout lays it out into new lines, the copy keeps the lines
+        /// of the time it was made.
+        pub fn lines(&self) -> Pango2Lines {
+            // the lines stay owned by the layout
+            let layout_lines = mem::ManuallyDrop::new(Pango2Lines {
+                raw: unsafe { pango2_sys::pango2_layout_get_lines(self.raw) },
+            });
+            let lines = Pango2Lines::new();
+            for (line, x, y) in layout_lines.lines() {
+                lines.add_line(line, x, y);
+            }
+            lines
+        }
+
+        /// Records painting the layout with its top left corner at the
+        /// origin, the text without a foreground attribute is black.
+        pub fn record(&self) -> Result<cairo::RecordingSurface, cairo::CairoError> {
+            let surface = cairo::RecordingSurface::new(cairo::Content::ColorAlpha, None)?;
+            let cairo_context = cairo::CairoContext::create(&surface);
+            self.paint(&cairo_context);
+            Ok(surface)
+        }
+
+        /// Adds the outlines of the text to the current path of the context,
+        /// with the top left corner of the layout at the current point.
+        pub fn append_path(&self, cairo_context: &cairo::CairoContext) {
+            unsafe {
+                pango2_sys::pango2_cairo_layout_path(*cairo_context.raw(), self.raw);
+            }
+        }
+
+        /// Returns the outlines of the text as SVG path data, in pixels with
+        /// the origin at the top left corner of the layout.
+        pub fn to_svg_path_data(&self) -> String {
+            self.run_paths()
+                .into_iter()
+                .map(|(data, _)| data)
+                .filter(|data| !data.is_empty())
+                .collect::<Vec<_>>()
+                .join(" ")
+        }
+
+        /// Returns a standalone SVG document with the text as outlines, one
+        /// `<path>` per run filled with its foreground color or black.
+        pub fn to_svg_document(&self) -> String {
+            let (_, logical) = self.extents();
+            let width = (logical.width + SCALE - 1) / SCALE;
+            let height = (logical.height + SCALE - 1) / SCALE;
+            let mut svg = format!(
+                "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\">\n",
+                width, height, width, height
+            );
+            for (data, color) in self.run_paths() {
+                if data.is_empty() {
+                    continue;
+                }
+                let color = color.unwrap_or(Color::new(0, 0, 0, 0xffff));
+                svg.push_str(&format!(
+                    "  <path d=\"{}\" fill=\"#{:02x}{:02x}{:02x}\"",
+                    data,
+                    color.red >> 8,
+                    color.green >> 8,
+                    color.blue >> 8
+                ));
+                if color.alpha != 0xffff {
+                    svg.push_str(&format!(
+                        " fill-opacity=\"{:.3}\"",
+                        color.alpha as f32 / 65535.0
+                    ));
+                }
+                svg.push_str("/>\n");
+            }
+            svg.push_str("</svg>\n");
+            svg
+        }
+
+        /// Returns the SVG path data and foreground color of every run.
+        fn run_paths(&self) -> Vec<(String, Option<Color>)> {
+            let surface = match cairo::CairoSurface::new_image_surface(1, 1) {
+                Ok(surface) => surface,
+                Err(_) => return Vec::new(),
+            };
+            let cairo_context = cairo::CairoContext::create(&surface);
+            let mut paths = Vec::new();
+            unsafe {
+                let text = pango2_sys::pango2_layout_get_text(self.raw);
+                self.for_each_run(|run, x, baseline| {
+                    // the path is kept in device space, copy it after
+                    // restoring to get coordinates relative to the layout
+                    cairo_context.new_path();
+                    cairo_context.save();
+                    cairo_context
+                        .translate(units_to_double(x) as f32, units_to_double(baseline) as f32);
+                    pango2_sys::pango2_cairo_run_path(*cairo_context.raw(), text, run);
+                    cairo_context.restore();
+                    let data = cairo_context
+                        .copy_path()
+                        .unwrap_or_default()
+                        .iter()
+                        .map(|segment| segment.to_string())
+                        .collect::<Vec<_>>()
+                        .join(" ");
+                    paths.push((data, run_foreground(run)));
+                });
+            }
+            paths
+        }
+
+        /// Returns the shaped glyphs of every run, in visual order.
+        pub fn glyph_runs(&self) -> Vec<GlyphRun> {
+            let mut runs = Vec::new();
+            unsafe {
+                self.for_each_run(|run, x, baseline| {
+                    let item = pango2_sys::pango2_run_get_item(run);
+                    let analysis = pango2_sys::pango2_item_get_analysis(item);
+                    let font = pango2_sys::pango2_analysis_get_font(analysis);
+                    runs.push(GlyphRun {
+                        x,
+                        baseline,
+                        glyphs: glyph_infos(pango2_sys::pango2_run_get_glyphs(run)),
+                        font: harfbuzz::Font::from_raw(pango2_sys::pango2_font_get_hb_font(font)),
+                    });
+                });
+            }
+            runs
+        }
+
+        /// Calls `f` with every run, the x position of its logical extents
+        /// and its baseline in Pango units.
+        unsafe fn for_each_run(&self, mut f: impl FnMut(*mut pango2_sys::Pango2Run, i32, i32)) {
+            let lines = pango2_sys::pango2_layout_get_lines(self.raw);
+            let iter = pango2_sys::pango2_lines_get_iter(lines);
+            loop {
+                let run = pango2_sys::pango2_line_iter_get_run(iter);
+                if !run.is_null() {
+                    let mut logical = Rectangle::default().into();
+                    pango2_sys::pango2_line_iter_get_run_extents(
+                        iter,
+                        ptr::null_mut(),
+                        &mut logical,
+                    );
+                    let baseline = pango2_sys::pango2_line_iter_get_run_baseline(iter);
+                    f(run, logical.x, baseline);
+                }
+                if pango2_sys::pango2_line_iter_next_run(iter) == 0 {
+                    break;
+                }
+            }
+            pango2_sys::pango2_line_iter_free(iter);
+        }
+
+        pub fn set_tabs(&self, tabs: Option<&TabArray>) {
+            unsafe {
+                pango2_sys::pango2_layout_set_tabs(
+                    self.raw,
+                    tabs.map_or(ptr::null_mut(), |tabs| tabs.raw),
+                );
+            }
+        }
+
+        pub fn tabs(&self) -> Option<TabArray> {
+            unsafe { TabArray::copy_from(pango2_sys::pango2_layout_get_tabs(self.raw)) }
+        }
+
+        pub fn set_attributes(&self, attributes: &AttrList) {
+            unsafe {
+                pango2_sys::pango2_layout_set_attributes(self.raw, attributes.raw);
+            }
+        }
+
+        pub fn attributes(&self) -> Option<AttrList> {
+            unsafe { AttrList::copy_from(pango2_sys::pango2_layout_get_attributes(self.raw)) }
+        }
+
+        /// Sets up the layout for vertical writing (tategaki), lines run from
+        /// top to bottom and are stacked from right to left. Draw it with
+        /// [`Pango2Layout::paint_vertical`].
+        ///
+        /// With [`GravityHint::Natural`] CJK characters stay upright while
+        /// other scripts like Latin are rotated, [`GravityHint::Strong`]
+        /// keeps every glyph upright. The `vert` feature selects vertical
+        /// punctuation forms, `vrt2` is only enabled for strong gravity since
+        /// its rotated glyphs would otherwise be rotated a second time.
+        ///
+        /// The settings are attributes over the whole text, calling this
+        /// again replaces them. To make every layout of a context vertical,
+        /// use [`Pango2Context::set_base_gravity`] and
+        /// [`Pango2Context::set_gravity_hint`] instead.
+        pub fn set_vertical(&self, hint: GravityHint) {
+            let attributes = self.attributes().unwrap_or_default();
+            attributes.remove(&[
+                Attribute::gravity(Gravity::East),
+                Attribute::gravity_hint(GravityHint::Natural),
+                Attribute::gravity_hint(GravityHint::Strong),
+                Attribute::gravity_hint(GravityHint::Line),
+                Attribute::font_features("vert"),
+                Attribute::font_features("vert, vrt2"),
+            ]);
+            attributes.insert(Attribute::gravity(Gravity::East));
+            attributes.insert(Attribute::gravity_hint(hint));
+            attributes.insert(Attribute::font_features(match hint {
+                GravityHint::Strong => "vert, vrt2",
+                _ => "vert",
+            }));
+            self.set_attributes(&attributes);
+        }
+
+        /// Paints a layout set up with [`Pango2Layout::set_vertical`], `x` and
+        /// `y` are the top right corner of the text in user space.
+        pub fn paint_vertical(&self, cairo_context: &cairo::CairoContext, x: f32, y: f32) {
+            cairo_context.save();
+            cairo_context.translate(x, y);
+            // the natural rotation of east gravity is counter-clockwise in
+            // Pango's matrix convention, Cairo's rotation is clockwise
+            cairo_context.rotate(-Gravity::East.to_rotation() as f32);
+            unsafe {
+                pango2_sys::pango2_cairo_update_layout(*cairo_context.raw(), self.raw);
+                pango2_sys::pango2_cairo_show_layout(*cairo_context.raw(), self.raw);
+            }
+            cairo_context.restore();
+            // the context is shared with other layouts, undo the rotation
+            unsafe {
+                pango2_sys::pango2_cairo_update_layout(*cairo_context.raw(), self.raw);
+            }
+        }
+
+        /// Returns the ink and logical extents of the layout in Pango units.
+        pub fn extents(&self) -> (Rectangle, Rectangle) {
+            unsafe {
+                let mut ink = Rectangle::default().into();
+                let mut logical = Rectangle::default().into();
+                let lines = pango2_sys::pango2_layout_get_lines(self.raw);
+                pango2_sys::pango2_lines_get_extents(lines, &mut ink, &mut logical);
+                (ink.into(), logical.into())
+            }
+        }
+
+        /// Serializes the layout to JSON, see `pango2_layout_serialize`.
+        pub fn to_json(&self, flags: LayoutSerializeFlags) -> Vec<u8> {
+            unsafe { bytes_into_vec(pango2_sys::pango2_layout_serialize(self.raw, flags.0)) }
+        }
+
+        /// Serializes only the lines of the layout output to JSON.
+        pub fn lines_to_json(&self) -> Vec<u8> {
+            unsafe {
+                let lines = pango2_sys::pango2_layout_get_lines(self.raw);
+                bytes_into_vec(pango2_sys::pango2_lines_serialize(lines))
+            }
+        }
+
+        /// Creates a layout from JSON previously produced by [`Pango2Layout::to_json`].
+        pub fn from_json(
+            context: &Pango2Context,
+            json: &[u8],
+            flags: LayoutDeserializeFlags,
+        ) -> Result<Self, DeserializeError> {
+            unsafe {
+                let bytes = g_bytes_new(json.as_ptr() as *const _, json.len());
+                let mut error: *mut pango2_sys::GError = ptr::null_mut();
+                let raw = pango2_sys::pango2_layout_deserialize(
+                    context.raw,
+                    bytes as *mut pango2_sys::GBytes,
+                    flags.0,
+                    &mut error,
+                );
+                g_bytes_unref(bytes);
+                if raw.is_null() {
+                    Err(DeserializeError::from_gerror(error))
+                } else {
+                    Ok(Pango2Layout { raw })
+                }
+            }
+        }
+    }
+
+    impl Drop for Pango2Layout {
+        fn drop(&mut self) {
+            unsafe {
+                g_object_unref(self.raw as *mut GObject);
+            }
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    pub enum WrapMode {
+        #[default]
+        Word,
+        Char,
+        WordChar,
+    }
+
+    impl From<WrapMode> for pango2_sys::Pango2WrapMode {
+        fn from(mode: WrapMode) -> Self {
+            match mode {
+                WrapMode::Word => pango2_sys::Pango2WrapMode_PANGO2_WRAP_WORD,
+                WrapMode::Char => pango2_sys::Pango2WrapMode_PANGO2_WRAP_CHAR,
+                WrapMode::WordChar => pango2_sys::Pango2WrapMode_PANGO2_WRAP_WORD_CHAR,
+            }
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    pub enum EllipsizeMode {
+        #[default]
+        None,
+        Start,
+        Middle,
+        End,
+    }
+
+    impl From<EllipsizeMode> for pango2_sys::Pango2EllipsizeMode {
+        fn from(mode: EllipsizeMode) -> Self {
+            match mode {
+                EllipsizeMode::None => pango2_sys::Pango2EllipsizeMode_PANGO2_ELLIPSIZE_NONE,
+                EllipsizeMode::Start => pango2_sys::Pango2EllipsizeMode_PANGO2_ELLIPSIZE_START,
+                EllipsizeMode::Middle => pango2_sys::Pango2EllipsizeMode_PANGO2_ELLIPSIZE_MIDDLE,
+                EllipsizeMode::End => pango2_sys::Pango2EllipsizeMode_PANGO2_ELLIPSIZE_END,
+            }
+        }
+    }
+
+    /// Breaks paragraphs into lines one at a time, allowing every line to be
+    /// placed at a different position and width.
+    pub struct Pango2LineBreaker {
+        raw: *mut pango2_sys::Pango2LineBreaker,
+    }
+
+    impl Pango2LineBreaker {
+        pub fn new(context: &Pango2Context) -> Self {
+            unsafe {
+                Pango2LineBreaker {
+                    raw: pango2_sys::pango2_line_breaker_new(context.raw),
+                }
+            }
+        }
+
+        pub fn set_tabs(&self, tabs: Option<&TabArray>) {
+            unsafe {
+                pango2_sys::pango2_line_breaker_set_tabs(
+                    self.raw,
+                    tabs.map_or(ptr::null_mut(), |tabs| tabs.raw),
+                );
+            }
+        }
+
+        pub fn tabs(&self) -> Option<TabArray> {
+            unsafe { TabArray::copy_from(pango2_sys::pango2_line_breaker_get_tabs(self.raw)) }
+        }
+
+        pub fn add_text(&self, text: &str, attributes: Option<&AttrList>) {
+            unsafe {
+                let ctext = CString::new(text).unwrap();
+                pango2_sys::pango2_line_breaker_add_text(
+                    self.raw,
+                    ctext.as_ptr(),
+                    -1,
+                    attributes.map_or(ptr::null_mut(), |attributes| attributes.raw),
+                );
+            }
+        }
+
+        pub fn has_line(&self) -> bool {
+            unsafe { pango2_sys::pango2_line_breaker_has_line(self.raw) != 0 }
+        }
+
+        /// Breaks off the next line starting at `x` with the given width, both
+        /// in Pango units. Returns `None` once all text has been consumed.
+        pub fn next_line(
+            &self,
+            x: i32,
+            width: i32,
+            wrap: WrapMode,
+            ellipsize: EllipsizeMode,
+        ) -> Option<Pango2Line> {
+            unsafe {
+                let raw = pango2_sys::pango2_line_breaker_next_line(
+                    self.raw,
+                    x as c_int,
+                    width as c_int,
+                    wrap.into(),
+                    ellipsize.into(),
+                );
+                if raw.is_null() {
+                    None
+                } else {
+                    Some(Pango2Line { raw })
+                }
+            }
+        }
+    }
+
+    impl Drop for Pango2LineBreaker {
+        fn drop(&mut self) {
+            unsafe {
+                g_object_unref(self.raw as *mut GObject);
+            }
+        }
+    }
+
+    pub struct Pango2Line {
+        raw: *mut pango2_sys::Pango2Line,
+    }
+
+    impl Pango2Line {
+        /// Byte index of the start of the line in the text.
+        pub fn start_index(&self) -> usize {
+            unsafe { pango2_sys::pango2_line_get_start_index(self.raw) as usize }
+        }
+
+        /// Length of the line in bytes.
+        pub fn len(&self) -> usize {
+            unsafe { pango2_sys::pango2_line_get_length(self.raw) as usize }
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.len() == 0
+        }
+
+        /// Returns the ink and logical extents of the line in Pango units.
+        pub fn extents(&self) -> (Rectangle, Rectangle) {
+            unsafe {
+                let mut ink = Rectangle::default().into();
+                let mut logical = Rectangle::default().into();
+                pango2_sys::pango2_line_get_extents(self.raw, &mut ink, &mut logical);
+                (ink.into(), logical.into())
+            }
+        }
+
+        /// Adds the outlines of the line to the current path of the context,
+        /// with the start of the baseline at the current point.
+        pub fn append_path(&self, cairo_context: &cairo::CairoContext) {
+            unsafe {
+                pango2_sys::pango2_cairo_line_path(*cairo_context.raw(), self.raw);
+            }
+        }
+
+        /// Draws the line with the start of its baseline at the current
+        /// point, use [`CairoContext::move_to`](cairo::CairoContext::move_to)
+        /// to place it.
+        pub fn show(&self, cairo_context: &cairo::CairoContext) {
+            unsafe {
+                pango2_sys::pango2_cairo_show_line(*cairo_context.raw(), self.raw);
+            }
+        }
+
+        /// The runs of the line in visual order.
+        pub fn runs(&self) -> Vec<Pango2Run<'_>> {
+            unsafe {
+                let count = pango2_sys::pango2_line_get_run_count(self.raw) as usize;
+                let runs = pango2_sys::pango2_line_get_runs(self.raw);
+                if runs.is_null() {
+                    return Vec::new();
+                }
+                slice::from_raw_parts(runs, count)
+                    .iter()
+                    .map(|&raw| Pango2Run { raw, line: self })
+                    .collect()
+            }
+        }
+    }
+
+    impl Drop for Pango2Line {
+        fn drop(&mut self) {
+            unsafe {
+                pango2_sys::pango2_line_free(self.raw);
+            }
+        }
+    }
+
+    /// A run of a [`Pango2Line`], glyphs shaped with the same font and
+    /// attributes.
+    pub struct Pango2Run<'a> {
+        raw: *mut pango2_sys::Pango2Run,
+        line: &'a Pango2Line,
+    }
+
+    impl Pango2Run<'_> {
+        /// Returns the ink and logical extents of the run in Pango units,
+        /// relative to the start of its baseline.
+        pub fn extents(&self) -> (Rectangle, Rectangle) {
+            unsafe {
+                let mut ink = Rectangle::default().into();
+                let mut logical = Rectangle::default().into();
+                pango2_sys::pango2_run_get_extents(
+                    self.raw,
+                    pango2_sys::Pango2LeadingTrim_PANGO2_LEADING_TRIM_NONE,
+                    &mut ink,
+                    &mut logical,
+                );
+                (ink.into(), logical.into())
+            }
+        }
+
+        pub fn glyphs(&self) -> Vec<GlyphInfo> {
+            unsafe { glyph_infos(pango2_sys::pango2_run_get_glyphs(self.raw)) }
+        }
+
+        /// Draws the run with the start of its baseline at the current
+        /// point, including its background and decoration lines.
+        pub fn show(&self, cairo_context: &cairo::CairoContext) {
+            // pango2_line_get_text requires the out parameters, the run
+            // only needs the text its item offsets index into
+            let (mut start, mut length) = (0, 0);
+            unsafe {
+                let text = pango2_sys::pango2_line_get_text(self.line.raw, &mut start, &mut length);
+                pango2_sys::pango2_cairo_show_run(*cairo_context.raw(), text, self.raw);
+            }
+        }
+
+        /// Draws only the glyphs of the run with the start of their baseline
+        /// at the current point, in the source of the context.
+        pub fn show_glyphs(&self, cairo_context: &cairo::CairoContext) {
+            unsafe {
+                let item = pango2_sys::pango2_run_get_item(self.raw);
+                let analysis = pango2_sys::pango2_item_get_analysis(item);
+                pango2_sys::pango2_cairo_show_glyph_string(
+                    *cairo_context.raw(),
+                    pango2_sys::pango2_analysis_get_font(analysis),
+                    pango2_sys::pango2_run_get_glyphs(self.raw),
+                );
+            }
+        }
+    }
+
+    /// Lines placed in a paragraph, e.g. the lines of a layout or lines
+    /// broken with a [`Pango2LineBreaker`].
+    pub struct Pango2Lines {
+        raw: *mut pango2_sys::Pango2Lines,
+    }
+
+    impl Pango2Lines {
+        pub fn new() -> Self {
+            unsafe {
+                Pango2Lines {
+                    raw: pango2_sys::pango2_lines_new(),
+                }
+            }
+        }
+
+        /// Adds a line with the start of its baseline at `x`, `y` in Pango
+        /// units.
+        pub fn add_line(&self, line: Pango2Line, x: i32, y: i32) {
+            // the lines take over the line
+            let line = mem::ManuallyDrop::new(line);
+            unsafe {
+                pango2_sys::pango2_lines_add_line(self.raw, line.raw, x as c_int, y as c_int);
+            }
+        }
+
+        pub fn line_count(&self) -> usize {
+            unsafe { pango2_sys::pango2_lines_get_line_count(self.raw) as usize }
+        }
+
+        /// Returns copies of the lines with the start of their baseline in
+        /// Pango units.
+        pub fn lines(&self) -> Vec<(Pango2Line, i32, i32)> {
+            unsafe {
+                let lines = pango2_sys::pango2_lines_get_lines(self.raw);
+                if lines.is_null() {
+                    return Vec::new();
+                }
+                slice::from_raw_parts(lines, self.line_count())
+                    .iter()
+                    .enumerate()
+                    .map(|(i, &line)| {
+                        let (mut x, mut y) = (0, 0);
+                        pango2_sys::pango2_lines_get_line_position(
+                            self.raw, i as c_int, &mut x, &mut y,
+                        );
+                        let line = Pango2Line {
+                            raw: pango2_sys::pango2_line_copy(line),
+                        };
+                        (line, x, y)
+                    })
+                    .collect()
+            }
+        }
+
+        /// Returns the ink and logical extents of all lines in Pango units.
+        pub fn extents(&self) -> (Rectangle, Rectangle) {
+            unsafe {
+                let mut ink = Rectangle::default().into();
+                let mut logical = Rectangle::default().into();
+                pango2_sys::pango2_lines_get_extents(self.raw, &mut ink, &mut logical);
+                (ink.into(), logical.into())
+            }
+        }
+
+        /// Draws the lines with their top left corner at the current point.
+        pub fn show(&self, cairo_context: &cairo::CairoContext) {
+            unsafe {
+                pango2_sys::pango2_cairo_show_lines(*cairo_context.raw(), self.raw);
+            }
+        }
+    }
+
+    impl Default for Pango2Lines {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    impl Clone for Pango2Lines {
+        fn clone(&self) -> Self {
+            unsafe {
+                Pango2Lines {
+                    raw: g_object_ref(self.raw as *mut GObject) as *mut pango2_sys::Pango2Lines,
+                }
+            }
+        }
+    }
+
+    impl Drop for Pango2Lines {
+        fn drop(&mut self) {
+            unsafe {
+                g_object_unref(self.raw as *mut GObject);
+            }
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum TabAlign {
+        Left,
+        Right,
+        Center,
+        /// Aligns the decimal point of the text at the tab position,
+        /// see [`TabArray::set_decimal_point`].
+        Decimal,
+    }
+
+    impl From<TabAlign> for pango2_sys::Pango2TabAlign {
+        fn from(align: TabAlign) -> Self {
+            match align {
+                TabAlign::Left => pango2_sys::Pango2TabAlign_PANGO2_TAB_LEFT,
+                TabAlign::Right => pango2_sys::Pango2TabAlign_PANGO2_TAB_RIGHT,
+                TabAlign::Center => pango2_sys::Pango2TabAlign_PANGO2_TAB_CENTER,
+                TabAlign::Decimal => pango2_sys::Pango2TabAlign_PANGO2_TAB_DECIMAL,
+            }
+        }
+    }
+
+    impl From<pango2_sys::Pango2TabAlign> for TabAlign {
+        fn from(align: pango2_sys::Pango2TabAlign) -> Self {
+            match align {
+                pango2_sys::Pango2TabAlign_PANGO2_TAB_RIGHT => TabAlign::Right,
+                pango2_sys::Pango2TabAlign_PANGO2_TAB_CENTER => TabAlign::Center,
+                pango2_sys::Pango2TabAlign_PANGO2_TAB_DECIMAL => TabAlign::Decimal,
+                _ => TabAlign::Left,
+            }
+        }
+    }
+
+    /// The unit tab positions are given in.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    pub enum TabPositions {
+        #[default]
+        PangoUnits,
+        Pixels,
+        Spaces,
+    }
+
+    impl From<TabPositions> for pango2_sys::Pango2TabPositions {
+        fn from(positions: TabPositions) -> Self {
+            match positions {
+                TabPositions::PangoUnits => {
+                    pango2_sys::Pango2TabPositions_PANGO2_TAB_POSITIONS_DEFAULT
+                }
+                TabPositions::Pixels => pango2_sys::Pango2TabPositions_PANGO2_TAB_POSITIONS_PIXELS,
+                TabPositions::Spaces => pango2_sys::Pango2TabPositions_PANGO2_TAB_POSITIONS_SPACES,
+            }
+        }
+    }
+
+    impl From<pango2_sys::Pango2TabPositions> for TabPositions {
+        fn from(positions: pango2_sys::Pango2TabPositions) -> Self {
+            match positions {
+                pango2_sys::Pango2TabPositions_PANGO2_TAB_POSITIONS_PIXELS => TabPositions::Pixels,
+                pango2_sys::Pango2TabPositions_PANGO2_TAB_POSITIONS_SPACES => TabPositions::Spaces,
+                _ => TabPositions::PangoUnits,
+            }
+        }
+    }
+
+    pub struct TabArray {
+        raw: *mut pango2_sys::Pango2TabArray,
+    }
+
+    impl TabArray {
+        pub fn new(size: usize, positions: TabPositions) -> Self {
+            unsafe {
+                TabArray {
+                    raw: pango2_sys::pango2_tab_array_new(size as c_int, positions.into()),
+                }
+            }
+        }
+
+        /// Creates a tab array from a list of alignment and position pairs.
+        pub fn with_positions(positions: TabPositions, tabs: &[(TabAlign, i32)]) -> Self {
+            let array = Self::new(tabs.len(), positions);
+            for (index, (align, location)) in tabs.iter().enumerate() {
+                array.set_tab(index, *align, *location);
+            }
+            array
+        }
+
+        /// Parses the format produced by `pango2_tab_array_to_string`, e.g.
+        /// `"100 right:200 decimal:300"` or `"10px 20px"`.
+        pub fn from_string(string: &str) -> Option<Self> {
+            unsafe {
+                let cstring = CString::new(string).ok()?;
+                let raw = pango2_sys::pango2_tab_array_from_string(cstring.as_ptr());
+                if raw.is_null() {
+                    None
+                } else {
+                    Some(TabArray { raw })
+                }
+            }
+        }
+
+        /// Copies a tab array not owned by the caller.
+        unsafe fn copy_from(raw: *mut pango2_sys::Pango2TabArray) -> Option<Self> {
+            if raw.is_null() {
+                None
+            } else {
+                Some(TabArray {
+                    raw: pango2_sys::pango2_tab_array_copy(raw),
+                })
+            }
+        }
+
+        pub fn len(&self) -> usize {
+            unsafe { pango2_sys::pango2_tab_array_get_size(self.raw) as usize }
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.len() == 0
+        }
+
+        pub fn resize(&self, size: usize) {
+            unsafe {
+                pango2_sys::pango2_tab_array_resize(self.raw, size as c_int);
+            }
+        }
+
+        pub fn set_tab(&self, index: usize, align: TabAlign, location: i32) {
+            unsafe {
+                pango2_sys::pango2_tab_array_set_tab(
+                    self.raw,
+                    index as c_int,
+                    align.into(),
+                    location as c_int,
+                );
+            }
+        }
+
+        pub fn tab(&self, index: usize) -> (TabAlign, i32) {
+            unsafe {
+                let mut align = pango2_sys::Pango2TabAlign_PANGO2_TAB_LEFT;
+                let mut location: c_int = 0;
+                pango2_sys::pango2_tab_array_get_tab(
+                    self.raw,
+                    index as c_int,
+                    &mut align,
+                    &mut location,
+                );
+                (align.into(), location as i32)
+            }
+        }
+
+        pub fn tabs(&self) -> Vec<(TabAlign, i32)> {
+            (0..self.len()).map(|index| self.tab(index)).collect()
+        }
+
+        pub fn positions(&self) -> TabPositions {
+            unsafe { pango2_sys::pango2_tab_array_get_positions(self.raw).into() }
+        }
+
+        pub fn set_positions(&self, positions: TabPositions) {
+            unsafe {
+                pango2_sys::pango2_tab_array_set_positions(self.raw, positions.into());
+            }
+        }
+
+        /// Sets the character a [`TabAlign::Decimal`] tab aligns to, `None`
+        /// uses the decimal point of the current locale.
+        pub fn set_decimal_point(&self, index: usize, decimal_point: Option<char>) {
+            unsafe {
+                pango2_sys::pango2_tab_array_set_decimal_point(
+                    self.raw,
+                    index as c_int,
+                    decimal_point.map_or(0, |c| c as pango2_sys::gunichar),
+                );
+            }
+        }
+
+        pub fn decimal_point(&self, index: usize) -> Option<char> {
+            unsafe {
+                let c = pango2_sys::pango2_tab_array_get_decimal_point(self.raw, index as c_int);
+                if c == 0 {
+                    None
+                } else {
+                    char::from_u32(c)
+                }
+            }
+        }
+
+        /// Sorts the tabs by position.
+        pub fn sort(&self) {
+            unsafe {
+                pango2_sys::pango2_tab_array_sort(self.raw);
+            }
+        }
+    }
+
+    impl fmt::Display for TabArray {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            let string = unsafe { take_string(pango2_sys::pango2_tab_array_to_string(self.raw)) };
+            f.write_str(&string)
+        }
+    }
+
+    impl fmt::Debug for TabArray {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_tuple("TabArray").field(&self.to_string()).finish()
+        }
+    }
+
+    impl Clone for TabArray {
+        fn clone(&self) -> Self {
+            unsafe {
+                TabArray {
+                    raw: pango2_sys::pango2_tab_array_copy(self.raw),
+                }
+            }
+        }
+    }
+
+    impl Drop for TabArray {
+        fn drop(&mut self) {
+            unsafe {
+                pango2_sys::pango2_tab_array_free(self.raw);
+            }
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    pub struct LayoutSerializeFlags(pango2_sys::Pango2LayoutSerializeFlags);
+
+    impl LayoutSerializeFlags {
+        pub const DEFAULT: Self =
+            Self(pango2_sys::Pango2LayoutSerializeFlags_PANGO2_LAYOUT_SERIALIZE_DEFAULT);
+        /// Include the context settings (language, base direction, ...).
+        pub const CONTEXT: Self =
+            Self(pango2_sys::Pango2LayoutSerializeFlags_PANGO2_LAYOUT_SERIALIZE_CONTEXT);
+        /// Include the formatted output (lines, runs and glyphs).
+        pub const OUTPUT: Self =
+            Self(pango2_sys::Pango2LayoutSerializeFlags_PANGO2_LAYOUT_SERIALIZE_OUTPUT);
+    }
+
+    impl BitOr for LayoutSerializeFlags {
+        type Output = Self;
+
+        fn bitor(self, rhs: Self) -> Self {
+            Self(self.0 | rhs.0)
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    pub struct LayoutDeserializeFlags(pango2_sys::Pango2LayoutDeserializeFlags);
+
+    impl LayoutDeserializeFlags {
+        pub const DEFAULT: Self =
+            Self(pango2_sys::Pango2LayoutDeserializeFlags_PANGO2_LAYOUT_DESERIALIZE_DEFAULT);
+        /// Apply the serialized context settings to the context.
+        pub const CONTEXT: Self =
+            Self(pango2_sys::Pango2LayoutDeserializeFlags_PANGO2_LAYOUT_DESERIALIZE_CONTEXT);
+    }
+
+    impl BitOr for LayoutDeserializeFlags {
+        type Output = Self;
+
+        fn bitor(self, rhs: Self) -> Self {
+            Self(self.0 | rhs.0)
+        }
+    }
+
+    /// Error codes of the `PANGO2_LAYOUT_DESERIALIZE_ERROR` domain, these are
+    /// not part of the generated bindings.
+    const LAYOUT_DESERIALIZE_INVALID: c_int = 0;
+    const LAYOUT_DESERIALIZE_INVALID_VALUE: c_int = 1;
+    const LAYOUT_DESERIALIZE_MISSING_VALUE: c_int = 2;
+
+    #[derive(Debug)]
+    pub enum DeserializeError {
+        /// The serialized data had the wrong structure.
+        Invalid(String),
+        /// A serialized value was invalid.
+        InvalidValue(String),
+        /// A required value was missing.
+        MissingValue(String),
+        /// An error outside of the layout deserialization domain.
+        Other { code: i32, message: String },
+    }
+
+    impl DeserializeError {
+        unsafe fn from_gerror(error: *mut pango2_sys::GError) -> Self {
+            if error.is_null() {
+                return DeserializeError::Invalid(String::new());
+            }
+            let message = if (*error).message.is_null() {
+                String::new()
+            } else {
+                CStr::from_ptr((*error).message)
+                    .to_string_lossy()
+                    .to_string()
+            };
+            let code = (*error).code;
+            let result = if (*error).domain == pango2_sys::pango2_layout_deserialize_error_quark() {
+                match code {
+                    LAYOUT_DESERIALIZE_INVALID => DeserializeError::Invalid(message),
+                    LAYOUT_DESERIALIZE_INVALID_VALUE => DeserializeError::InvalidValue(message),
+                    LAYOUT_DESERIALIZE_MISSING_VALUE => DeserializeError::MissingValue(message),
+                    _ => DeserializeError::Other { code, message },
+                }
+            } else {
+                DeserializeError::Other { code, message }
+            };
+            g_error_free(error as *mut glib_sys::GError);
+            result
+        }
+    }
+
+    pub struct FontDescription {
+        raw: *mut pango2_sys::Pango2FontDescription,
+    }
+
+    impl FontDescription {
+        pub fn from_string(string: &str) -> Self {
+            unsafe {
+                let cstring = CString::new(string).unwrap();
+                FontDescription {
+                    raw: pango2_sys::pango2_font_description_from_string(cstring.as_ptr()),
+                }
+            }
+        }
+
+        pub fn set_gravity(&self, gravity: Gravity) {
+            unsafe {
+                pango2_sys::pango2_font_description_set_gravity(self.raw, gravity.into());
+            }
+        }
+
+        pub fn gravity(&self) -> Gravity {
+            unsafe { pango2_sys::pango2_font_description_get_gravity(self.raw).into() }
+        }
+    }
+
+    impl fmt::Display for FontDescription {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            let string =
+                unsafe { take_string(pango2_sys::pango2_font_description_to_string(self.raw)) };
+            f.write_str(&string)
+        }
+    }
+
+    impl fmt::Debug for FontDescription {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_tuple("FontDescription")
+                .field(&self.to_string())
+                .finish()
+        }
+    }
+
+    impl Clone for FontDescription {
+        fn clone(&self) -> Self {
+            unsafe {
+                FontDescription {
+                    raw: pango2_sys::pango2_font_description_copy(self.raw),
+                }
+            }
+        }
+    }
+
+    impl PartialEq for FontDescription {
+        fn eq(&self, other: &Self) -> bool {
+            unsafe { pango2_sys::pango2_font_description_equal(self.raw, other.raw) != 0 }
+        }
+    }
+
+    impl Drop for FontDescription {
+        fn drop(&mut self) {
+            unsafe {
+                pango2_font_description_free(self.raw);
+            }
+        }
+    }
+
+    /// Metrics of a font or a set of fonts, in Pango units. Positions are
+    /// distances above the baseline, so the usual underline position is
+    /// negative.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    pub struct FontMetrics {
+        pub ascent: i32,
+        pub descent: i32,
+        /// Distance between the baselines of two lines.
+        pub height: i32,
+        pub approximate_char_width: i32,
+        pub approximate_digit_width: i32,
+        /// Distance from the baseline to the top of the underline.
+        pub underline_position: i32,
+        pub underline_thickness: i32,
+        /// Distance from the baseline to the top of the strikethrough.
+        pub strikethrough_position: i32,
+        pub strikethrough_thickness: i32,
+    }
+
+    impl FontMetrics {
+        /// Reads and frees metrics returned by Pango.
+        unsafe fn take(raw: *mut pango2_sys::Pango2FontMetrics) -> Self {
+            if raw.is_null() {
+                return Self::default();
+            }
+            let metrics = FontMetrics {
+                ascent: pango2_sys::pango2_font_metrics_get_ascent(raw),
+                descent: pango2_sys::pango2_font_metrics_get_descent(raw),
+                height: pango2_sys::pango2_font_metrics_get_height(raw),
+                approximate_char_width: pango2_sys::pango2_font_metrics_get_approximate_char_width(
+                    raw,
+                ),
+                approximate_digit_width:
+                    pango2_sys::pango2_font_metrics_get_approximate_digit_width(raw),
+                underline_position: pango2_sys::pango2_font_metrics_get_underline_position(raw),
+                underline_thickness: pango2_sys::pango2_font_metrics_get_underline_thickness(raw),
+                strikethrough_position: pango2_sys::pango2_font_metrics_get_strikethrough_position(
+                    raw,
+                ),
+                strikethrough_thickness:
+                    pango2_sys::pango2_font_metrics_get_strikethrough_thickness(raw),
+            };
+            pango2_sys::pango2_font_metrics_free(raw);
+            metrics
+        }
+    }
+
+    /// A font loaded at a specific size, see [`Pango2Context::load_font`].
+    pub struct Pango2Font {
+        raw: *mut pango2_sys::Pango2Font,
+    }
+
+    impl Pango2Font {
+        pub fn raw(&self) -> &*mut pango2_sys::Pango2Font {
+            &self.raw
+        }
+
+        pub fn describe(&self) -> FontDescription {
+            unsafe {
+                FontDescription {
+                    raw: pango2_sys::pango2_font_describe(self.raw),
+                }
+            }
+        }
+
+        /// The font size in Pango units.
+        pub fn size(&self) -> i32 {
+            unsafe { pango2_sys::pango2_font_get_size(self.raw) }
+        }
+
+        pub fn gravity(&self) -> Gravity {
+            unsafe { pango2_sys::pango2_font_get_gravity(self.raw).into() }
+        }
+
+        /// Returns the metrics of the font, `None` uses the language of the
+        /// context the font was loaded for.
+        pub fn metrics(&self, language: Option<Language>) -> FontMetrics {
+            unsafe {
+                FontMetrics::take(pango2_sys::pango2_font_get_metrics(
+                    self.raw,
+                    language.map_or(ptr::null_mut(), |language| language.raw),
+                ))
+            }
+        }
+
+        /// Returns the ink and logical rectangles of a glyph in Pango units,
+        /// relative to the glyph origin on the baseline with y pointing down.
+        pub fn glyph_extents(&self, glyph: u32) -> (Rectangle, Rectangle) {
+            unsafe {
+                let mut ink = Rectangle::default().into();
+                let mut logical = Rectangle::default().into();
+                pango2_sys::pango2_font_get_glyph_extents(self.raw, glyph, &mut ink, &mut logical);
+                (ink.into(), logical.into())
+            }
+        }
+
+        /// The HarfBuzz font used for shaping, its scale is in Pango units.
+        pub fn hb_font(&self) -> harfbuzz::Font {
+            unsafe { harfbuzz::Font::from_raw(pango2_sys::pango2_font_get_hb_font(self.raw)) }
+        }
+    }
+
+    impl Clone for Pango2Font {
+        fn clone(&self) -> Self {
+            unsafe {
+                Pango2Font {
+                    raw: g_object_ref(self.raw as *mut GObject) as *mut pango2_sys::Pango2Font,
+                }
+            }
+        }
+    }
+
+    impl Drop for Pango2Font {
+        fn drop(&mut self) {
+            unsafe {
+                g_object_unref(self.raw as *mut GObject);
+            }
+        }
+    }
+
+    pub struct AttrList {
+        raw: *mut pango2_sys::Pango2AttrList,
+    }
+
+    impl AttrList {
+        pub fn new() -> Self {
+            unsafe {
+                AttrList {
+                    raw: pango2_sys::pango2_attr_list_new(),
+                }
+            }
+        }
+
+        /// Parses the format produced by `pango2_attr_list_to_string`,
+        /// returns `None` if the string is malformed.
+        pub fn from_string(string: &str) -> Option<Self> {
+            unsafe {
+                let cstring = CString::new(string).ok()?;
+                let raw = pango2_sys::pango2_attr_list_from_string(cstring.as_ptr());
+                if raw.is_null() {
+                    None
+                } else {
+                    Some(AttrList { raw })
+                }
+            }
+        }
+
+        /// Inserts the attribute after all other attributes with the same start index.
+        pub fn insert(&self, attribute: Attribute) {
+            unsafe {
+                pango2_sys::pango2_attr_list_insert(self.raw, attribute.raw);
+            }
+            std::mem::forget(attribute);
+        }
+
+        /// Removes every attribute that is equal to one of `attributes` in
+        /// both value and range.
+        pub fn remove(&self, attributes: &[Attribute]) {
+            unsafe extern "C" fn matches(
+                attribute: *mut pango2_sys::Pango2Attribute,
+                data: gpointer,
+            ) -> pango2_sys::gboolean {
+                let attributes = &*(data as *const &[Attribute]);
+                let range = attribute_range(attribute);
+                attributes.iter().any(|other| {
+                    pango2_sys::pango2_attribute_equal(attribute, other.raw) != 0
+                        && attribute_range(other.raw) == range
+                }) as pango2_sys::gboolean
+            }
+
+            unsafe {
+                let removed = pango2_sys::pango2_attr_list_filter(
+                    self.raw,
+                    Some(matches),
+                    &attributes as *const &[Attribute] as gpointer,
+                );
+                if !removed.is_null() {
+                    pango2_sys::pango2_attr_list_unref(removed);
+                }
+            }
+        }
+
+        /// Copies an attribute list not owned by the caller.
+        unsafe fn copy_from(raw: *mut pango2_sys::Pango2AttrList) -> Option<Self> {
+            if raw.is_null() {
+                None
+            } else {
+                Some(AttrList {
+                    raw: pango2_sys::pango2_attr_list_copy(raw),
+                })
+            }
+        }
+    }
+
+    impl Default for AttrList {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    impl fmt::Display for AttrList {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            let string = unsafe { take_string(pango2_sys::pango2_attr_list_to_string(self.raw)) };
+            f.write_str(&string)
+        }
+    }
+
+    impl fmt::Debug for AttrList {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_tuple("AttrList").field(&self.to_string()).finish()
+        }
+    }
+
+    impl Clone for AttrList {
+        fn clone(&self) -> Self {
+            unsafe {
+                AttrList {
+                    raw: pango2_sys::pango2_attr_list_copy(self.raw),
+                }
+            }
+        }
+    }
+
+    impl PartialEq for AttrList {
+        fn eq(&self, other: &Self) -> bool {
+            unsafe { pango2_sys::pango2_attr_list_equal(self.raw, other.raw) != 0 }
+        }
+    }
+
+    impl Drop for AttrList {
+        fn drop(&mut self) {
+            unsafe {
+                pango2_sys::pango2_attr_list_unref(self.raw);
+            }
+        }
+    }
+
+    /// A single text attribute, applies to the whole text unless a range is set.
+    pub struct Attribute {
+        raw: *mut pango2_sys::Pango2Attribute,
+    }
+
+    impl Attribute {
+        /// OpenType font features in CSS syntax, e.g. `"smcp, liga 0"`.
+        pub fn font_features(features: &str) -> Self {
+            unsafe {
+                let cfeatures = CString::new(features).unwrap();
+                Attribute {
+                    raw: pango2_sys::pango2_attr_font_features_new(cfeatures.as_ptr()),
+                }
+            }
+        }
+
+        pub fn family(family: &str) -> Self {
+            unsafe {
+                let cfamily = CString::new(family).unwrap();
+                Attribute {
+                    raw: pango2_sys::pango2_attr_family_new(cfamily.as_ptr()),
+                }
+            }
+        }
+
+        pub fn foreground(color: Color) -> Self {
+            unsafe {
+                let mut color = color.into();
+                Attribute {
+                    raw: pango2_sys::pango2_attr_foreground_new(&mut color),
+                }
+            }
+        }
+
+        pub fn gravity(gravity: Gravity) -> Self {
+            unsafe {
+                Attribute {
+                    raw: pango2_sys::pango2_attr_gravity_new(gravity.into()),
+                }
+            }
+        }
+
+        pub fn gravity_hint(hint: GravityHint) -> Self {
+            unsafe {
+                Attribute {
+                    raw: pango2_sys::pango2_attr_gravity_hint_new(hint.into()),
+                }
+            }
+        }
+
+        /// Selects a color palette by name for the range, see
+        /// [`Pango2Context::set_palette`]. A range can switch to another
+        /// palette of the font named with [`Pango2HbFace::set_palette_name`],
+        /// e.g. a dark palette or one with custom colors added by
+        /// [`harfbuzz::Face::with_custom_palettes`].
+        pub fn palette(palette: &str) -> Self {
+            unsafe {
+                let cpalette = CString::new(palette).unwrap();
+                Attribute {
+                    raw: pango2_sys::pango2_attr_palette_new(cpalette.as_ptr()),
+                }
+            }
+        }
+
+        /// Fills the background of the range with the color.
+        pub fn background(color: Color) -> Self {
+            unsafe {
+                let mut color = color.into();
+                Attribute {
+                    raw: pango2_sys::pango2_attr_background_new(&mut color),
+                }
+            }
+        }
+
+        pub fn underline(style: LineStyle) -> Self {
+            unsafe {
+                Attribute {
+                    raw: pango2_sys::pango2_attr_underline_new(style.into()),
+                }
+            }
+        }
+
+        pub fn strikethrough(style: LineStyle) -> Self {
+            unsafe {
+                Attribute {
+                    raw: pango2_sys::pango2_attr_strikethrough_new(style.into()),
+                }
+            }
+        }
+
+        pub fn overline(style: LineStyle) -> Self {
+            unsafe {
+                Attribute {
+                    raw: pango2_sys::pango2_attr_overline_new(style.into()),
+                }
+            }
+        }
+
+        /// Limits the attribute to the byte range `start..end` of the text.
+        pub fn with_range(self, start: usize, end: usize) -> Self {
+            unsafe {
+                pango2_sys::pango2_attribute_set_range(
+                    self.raw,
+                    start as pango2_sys::guint,
+                    end as pango2_sys::guint,
+                );
+            }
+            self
+        }
+    }
+
+    unsafe fn attribute_range(attribute: *mut pango2_sys::Pango2Attribute) -> (u32, u32) {
+        let (mut start, mut end) = (0, 0);
+        pango2_sys::pango2_attribute_get_range(attribute, &mut start, &mut end);
+        (start, end)
+    }
+
+    impl Clone for Attribute {
+        fn clone(&self) -> Self {
+            unsafe {
+                Attribute {
+                    raw: pango2_sys::pango2_attribute_copy(self.raw),
+                }
+            }
+        }
+    }
+
+    impl Drop for Attribute {
+        fn drop(&mut self) {
+            unsafe {
+                pango2_sys::pango2_attribute_destroy(self.raw);
+            }
+        }
+    }
+
+    /// Glyph id Pango uses where no glyph is drawn, e.g. for line breaks.
+    pub const GLYPH_EMPTY: u32 = 0x0FFFFFFF;
+    /// Flag of glyph ids Pango draws as hex box for a character missing in
+    /// the font, the other bits are the character.
+    pub const GLYPH_UNKNOWN_FLAG: u32 = 0x10000000;
+
+    /// A shaped glyph, sizes are in Pango units.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct GlyphInfo {
+        pub glyph: u32,
+        /// Advance to the next glyph.
+        pub width: i32,
+        pub x_offset: i32,
+        pub y_offset: i32,
+    }
+
+    impl GlyphInfo {
+        /// Whether the glyph id refers to a glyph of the font.
+        pub fn is_font_glyph(&self) -> bool {
+            self.glyph != GLYPH_EMPTY && self.glyph & GLYPH_UNKNOWN_FLAG == 0
+        }
+    }
+
+    /// The glyphs of a run of a layout, the run starts at `x` on the
+    /// baseline at `baseline`, both in Pango units.
+    pub struct GlyphRun {
+        pub x: i32,
+        pub baseline: i32,
+        pub glyphs: Vec<GlyphInfo>,
+        font: harfbuzz::Font,
+    }
+
+    impl GlyphRun {
+        /// The font the glyphs were shaped with, its scale is in Pango units.
+        pub fn font(&self) -> &harfbuzz::Font {
+            &self.font
+        }
+    }
+
+    unsafe fn glyph_infos(glyphs: *const pango2_sys::Pango2GlyphString) -> Vec<GlyphInfo> {
+        let glyphs = &*glyphs;
+        if glyphs.glyphs.is_null() {
+            return Vec::new();
+        }
+        slice::from_raw_parts(glyphs.glyphs, glyphs.num_glyphs as usize)
+            .iter()
+            .map(|info| GlyphInfo {
+                glyph: info.glyph,
+                width: info.geometry.width,
+                x_offset: info.geometry.x_offset,
+                y_offset: info.geometry.y_offset,
+            })
+            .collect()
+    }
+
+    /// Returns the foreground color set on the text of a run, if any.
+    unsafe fn run_foreground(run: *mut pango2_sys::Pango2Run) -> Option<Color> {
+        // the attribute types are not exposed as constants, take the type
+        // from an attribute of the same kind
+        let foreground_type = {
+            let attribute = Attribute::foreground(Color::new(0, 0, 0, 0xffff));
+            pango2_sys::pango2_attribute_type(attribute.raw)
+        };
+        let item = pango2_sys::pango2_run_get_item(run);
+        let analysis = pango2_sys::pango2_item_get_analysis(item);
+        let mut list = pango2_sys::pango2_analysis_get_extra_attributes(analysis);
+        while !list.is_null() {
+            let attribute = (*list).data as *mut pango2_sys::Pango2Attribute;
+            if pango2_sys::pango2_attribute_type(attribute) == foreground_type {
+                return Some((*pango2_sys::pango2_attribute_get_color(attribute)).into());
+            }
+            list = (*list).next;
+        }
+        None
+    }
+
+    /// A color with 16 bit channels, as used by Pango.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct Color {
+        pub red: u16,
+        pub green: u16,
+        pub blue: u16,
+        pub alpha: u16,
+    }
+
+    impl Color {
+        pub fn new(red: u16, green: u16, blue: u16, alpha: u16) -> Self {
+            Color {
+                red,
+                green,
+                blue,
+                alpha,
+            }
+        }
+
+        /// Parses a color specification like `#fe0` or `rebeccapurple`,
+        /// see `pango2_color_parse`.
+        pub fn parse(spec: &str) -> Option<Self> {
+            unsafe {
+                let cspec = CString::new(spec).ok()?;
+                let mut color = Color::new(0, 0, 0, 0xffff).into();
+                if pango2_sys::pango2_color_parse(&mut color, cspec.as_ptr()) != 0 {
+                    Some(color.into())
+                } else {
+                    None
+                }
+            }
+        }
+    }
+
+    impl From<pango2_sys::Pango2Color> for Color {
+        fn from(color: pango2_sys::Pango2Color) -> Self {
+            Color::new(color.red, color.green, color.blue, color.alpha)
+        }
+    }
+
+    impl From<Color> for pango2_sys::Pango2Color {
+        fn from(color: Color) -> Self {
+            pango2_sys::Pango2Color {
+                red: color.red,
+                green: color.green,
+                blue: color.blue,
+                alpha: color.alpha,
+            }
+        }
+    }
+
+    impl fmt::Display for Color {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            let color: pango2_sys::Pango2Color = (*self).into();
+            let string = unsafe { take_string(pango2_sys::pango2_color_to_string(&color)) };
+            f.write_str(&string)
+        }
+    }
+
+    /// A rectangle in Pango units, e.g. the ink or logical extents of a layout.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub struct Rectangle {
+        pub x: i32,
+        pub y: i32,
+        pub width: i32,
+        pub height: i32,
+    }
+
+    impl From<pango2_sys::Pango2Rectangle> for Rectangle {
+        fn from(rect: pango2_sys::Pango2Rectangle) -> Self {
+            Rectangle {
+                x: rect.x,
+                y: rect.y,
+                width: rect.width,
+                height: rect.height,
+            }
+        }
+    }
+
+    impl From<Rectangle> for pango2_sys::Pango2Rectangle {
+        fn from(rect: Rectangle) -> Self {
+            pango2_sys::Pango2Rectangle {
+                x: rect.x,
+                y: rect.y,
+                width: rect.width,
+                height: rect.height,
+            }
+        }
+    }
+
+    /// Style of underlines, overlines and strikethrough lines.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum LineStyle {
+        None,
+        Solid,
+        Double,
+        Dashed,
+        Dotted,
+        Wavy,
+    }
+
+    impl From<LineStyle> for pango2_sys::Pango2LineStyle {
+        fn from(style: LineStyle) -> Self {
+            match style {
+                LineStyle::None => pango2_sys::Pango2LineStyle_PANGO2_LINE_STYLE_NONE,
+                LineStyle::Solid => pango2_sys::Pango2LineStyle_PANGO2_LINE_STYLE_SOLID,
+                LineStyle::Double => pango2_sys::Pango2LineStyle_PANGO2_LINE_STYLE_DOUBLE,
+                LineStyle::Dashed => pango2_sys::Pango2LineStyle_PANGO2_LINE_STYLE_DASHED,
+                LineStyle::Dotted => pango2_sys::Pango2LineStyle_PANGO2_LINE_STYLE_DOTTED,
+                LineStyle::Wavy => pango2_sys::Pango2LineStyle_PANGO2_LINE_STYLE_WAVY,
+            }
+        }
+    }
+
+    impl From<pango2_sys::Pango2LineStyle> for LineStyle {
+        fn from(style: pango2_sys::Pango2LineStyle) -> Self {
+            match style {
+                pango2_sys::Pango2LineStyle_PANGO2_LINE_STYLE_SOLID => LineStyle::Solid,
+                pango2_sys::Pango2LineStyle_PANGO2_LINE_STYLE_DOUBLE => LineStyle::Double,
+                pango2_sys::Pango2LineStyle_PANGO2_LINE_STYLE_DASHED => LineStyle::Dashed,
+                pango2_sys::Pango2LineStyle_PANGO2_LINE_STYLE_DOTTED => LineStyle::Dotted,
+                pango2_sys::Pango2LineStyle_PANGO2_LINE_STYLE_WAVY => LineStyle::Wavy,
+                _ => LineStyle::None,
+            }
+        }
+    }
+
+    /// The part of the text a [`Renderer`] callback draws, each part has
+    /// its own color.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum RenderPart {
+        Foreground,
+        Background,
+        Underline,
+        Strikethrough,
+        Overline,
+    }
+
+    impl From<RenderPart> for pango2_sys::Pango2RenderPart {
+        fn from(part: RenderPart) -> Self {
+            match part {
+                RenderPart::Foreground => {
+                    pango2_sys::Pango2RenderPart_PANGO2_RENDER_PART_FOREGROUND
+                }
+                RenderPart::Background => {
+                    pango2_sys::Pango2RenderPart_PANGO2_RENDER_PART_BACKGROUND
+                }
+                RenderPart::Underline => pango2_sys::Pango2RenderPart_PANGO2_RENDER_PART_UNDERLINE,
+                RenderPart::Strikethrough => {
+                    pango2_sys::Pango2RenderPart_PANGO2_RENDER_PART_STRIKETHROUGH
+                }
+                RenderPart::Overline => pango2_sys::Pango2RenderPart_PANGO2_RENDER_PART_OVERLINE,
+            }
+        }
+    }
+
+    impl From<pango2_sys::Pango2RenderPart> for RenderPart {
+        fn from(part: pango2_sys::Pango2RenderPart) -> Self {
+            match part {
+                pango2_sys::Pango2RenderPart_PANGO2_RENDER_PART_BACKGROUND => {
+                    RenderPart::Background
+                }
+                pango2_sys::Pango2RenderPart_PANGO2_RENDER_PART_UNDERLINE => RenderPart::Underline,
+                pango2_sys::Pango2RenderPart_PANGO2_RENDER_PART_STRIKETHROUGH => {
+                    RenderPart::Strikethrough
+                }
+                pango2_sys::Pango2RenderPart_PANGO2_RENDER_PART_OVERLINE => RenderPart::Overline,
+                _ => RenderPart::Foreground,
+            }
+        }
+    }
+
+    /// A trapezoid with horizontal top and bottom edges in device units, the
+    /// top edge runs from `x11` to `x21` at `y1`, the bottom edge from `x12`
+    /// to `x22` at `y2`.
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub struct Trapezoid {
+        pub y1: f64,
+        pub x11: f64,
+        pub x21: f64,
+        pub y2: f64,
+        pub x12: f64,
+        pub x22: f64,
+    }
+
+    /// Draws text with a custom backend through a [`CustomRenderer`]. Pango
+    /// splits the text into runs and calls the methods for the glyphs,
+    /// backgrounds and decoration lines, positions are in Pango units in
+    /// user space. All methods default to doing nothing.
+    pub trait Renderer {
+        /// Called before the parts of a run are drawn, the colors of the
+        /// run are set at this point.
+        fn prepare_run(&mut self, _run: &RenderRun<'_>) {}
+
+        /// Draws glyphs starting at `x` on the baseline at `y`.
+        fn draw_glyphs(&mut self, _font: &harfbuzz::Font, _glyphs: &[GlyphInfo], _x: i32, _y: i32) {
+        }
+
+        /// Fills a rectangle, e.g. the background of a run.
+        fn draw_rectangle(&mut self, _part: RenderPart, _rect: Rectangle) {}
+
+        /// Draws an underline, overline or strikethrough line filling `rect`.
+        fn draw_styled_line(&mut self, _part: RenderPart, _style: LineStyle, _rect: Rectangle) {}
+
+        /// Fills a trapezoid, Pango uses these for shapes that are not
+        /// axis aligned in device space.
+        fn draw_trapezoid(&mut self, _part: RenderPart, _trapezoid: Trapezoid) {}
+    }
+
+    /// The run about to be drawn, passed to [`Renderer::prepare_run`].
+    pub struct RenderRun<'a> {
+        renderer: *mut pango2_sys::Pango2Renderer,
+        run: *mut pango2_sys::Pango2Run,
+        _lifetime: PhantomData<&'a ()>,
+    }
+
+    impl RenderRun<'_> {
+        /// The color of a part of the run, `None` draws the part in the
+        /// color of the backend's choice.
+        pub fn color(&self, part: RenderPart) -> Option<Color> {
+            unsafe {
+                let color = pango2_sys::pango2_renderer_get_color(self.renderer, part.into());
+                (!color.is_null()).then(|| (*color).into())
+            }
+        }
+
+        pub fn glyphs(&self) -> Vec<GlyphInfo> {
+            unsafe { glyph_infos(pango2_sys::pango2_run_get_glyphs(self.run)) }
+        }
+    }
+
+    /// A `Pango2Renderer` forwarding its drawing to a [`Renderer`]. A panic
+    /// of the renderer is kept and resumed once Pango returns, unwinding
+    /// through Pango would abort the process. The remaining callbacks of
+    /// that drawing are skipped.
+    pub struct CustomRenderer<R: Renderer + 'static> {
+        raw: *mut pango2_sys::Pango2Renderer,
+        // both are boxed and owned by the wrapper, the callbacks reach them
+        // through the instance, so no `Box` may alias them
+        renderer: NonNull<R>,
+        state: NonNull<RendererState>,
+    }
+
+    /// What the instance of the `Pango2Renderer` subclass points to.
+    struct RendererState {
+        imp: NonNull<dyn Renderer>,
+        panic: Option<Box<dyn Any + Send>>,
+    }
+
+    impl<R: Renderer + 'static> CustomRenderer<R> {
+        pub fn new(renderer: R) -> Self {
+            unsafe {
+                let renderer = NonNull::new_unchecked(Box::into_raw(Box::new(renderer)));
+                let state = NonNull::new_unchecked(Box::into_raw(Box::new(RendererState {
+                    imp: renderer,
+                    panic: None,
+                })));
+                let (renderer_type, offset) = custom_renderer_type();
+                let raw =
+                    g_object_new_with_properties(renderer_type, 0, ptr::null_mut(), ptr::null())
+                        as *mut pango2_sys::Pango2Renderer;
+                *renderer_slot(raw, offset) = Some(state);
+                CustomRenderer {
+                    raw,
+                    renderer,
+                    state,
+                }
+            }
+        }
+
+        /// Resumes a panic the renderer raised while Pango was drawing.
+        fn resume_panic(&mut self) {
+            if let Some(payload) = unsafe { (*self.state.as_ptr()).panic.take() } {
+                panic::resume_unwind(payload);
+            }
+        }
+
+        pub fn renderer(&self) -> &R {
+            unsafe { self.renderer.as_ref() }
+        }
+
+        pub fn renderer_mut(&mut self) -> &mut R {
+            unsafe { self.renderer.as_mut() }
+        }
+
+        /// Draws the lines of a layout with its top left corner at `x`, `y`
+        /// in Pango units.
+        pub fn draw_layout(&mut self, layout: &Pango2Layout, x: i32, y: i32) {
+            unsafe {
+                let lines = pango2_sys::pango2_layout_get_lines(layout.raw);
+                pango2_sys::pango2_renderer_draw_lines(self.raw, lines, x, y);
+            }
+            self.resume_panic();
+        }
+
+        /// Draws a line with the start of its baseline at `x`, `y` in Pango
+        /// units.
+        pub fn draw_line(&mut self, line: &Pango2Line, x: i32, y: i32) {
+            unsafe {
+                pango2_sys::pango2_renderer_draw_line(self.raw, line.raw, x, y);
+            }
+            self.resume_panic();
+        }
+
+        /// Overrides the color of a part for the following drawing, Pango
+        /// sets the colors again for each run.
+        pub fn set_color(&mut self, part: RenderPart, color: Option<Color>) {
+            let color = color.map(pango2_sys::Pango2Color::from);
+            unsafe {
+                pango2_sys::pango2_renderer_set_color(
+                    self.raw,
+                    part.into(),
+                    color
+                        .as_ref()
+                        .map_or(ptr::null(), |color| color as *const _),
+                );
+            }
+        }
+
+        /// Sets the transformation from user space to device space, `None`
+        /// resets it to the identity.
+        pub fn set_matrix(&mut self, matrix: Option<Matrix>) {
+            let matrix = matrix.map(pango2_sys::Pango2Matrix::from);
+            unsafe {
+                pango2_sys::pango2_renderer_set_matrix(
+                    self.raw,
+                    matrix
+                        .as_ref()
+                        .map_or(ptr::null(), |matrix| matrix as *const _),
+                );
+            }
+        }
+
+        pub fn matrix(&self) -> Option<Matrix> {
+            unsafe {
+                let matrix = pango2_sys::pango2_renderer_get_matrix(self.raw);
+                (!matrix.is_null()).then(|| (*matrix).into())
+            }
+        }
+
+        /// Tells the renderer that the state of a part changed outside of
+        /// [`CustomRenderer::set_color`].
+        pub fn part_changed(&mut self, part: RenderPart) {
+            unsafe {
+                pango2_sys::pango2_renderer_part_changed(self.raw, part.into());
+            }
+        }
+    }
+
+    impl<R: Renderer + 'static> Drop for CustomRenderer<R> {
+        fn drop(&mut self) {
+            unsafe {
+                // the object may outlive the wrapper when someone else holds
+                // a reference, it must not reach the renderer anymore
+                let (_, offset) = custom_renderer_type();
+                *renderer_slot(self.raw, offset) = None;
+                g_object_unref(self.raw as *mut GObject);
+                drop(Box::from_raw(self.state.as_ptr()));
+                drop(Box::from_raw(self.renderer.as_ptr()));
+            }
+        }
+    }
+
+    /// The parent class, `prepare_run` chains up to it so Pango sets the
+    /// colors and decorations of the run.
+    static RENDERER_PARENT_CLASS: AtomicPtr<pango2_sys::Pango2RendererClass> =
+        AtomicPtr::new(ptr::null_mut());
+
+    /// Registers the `Pango2Renderer` subclass on first use, returns its type
+    /// and the offset of the [`Renderer`] pointer in the instance.
+    fn custom_renderer_type() -> (GType, usize) {
+        static RENDERER_TYPE: OnceLock<(GType, usize)> = OnceLock::new();
+        *RENDERER_TYPE.get_or_init(|| unsafe {
+            let parent_type = pango2_sys::pango2_renderer_get_type() as GType;
+            let mut query: GTypeQuery = std::mem::zeroed();
+            g_type_query(parent_type, &mut query);
+            let slot = mem::size_of::<Option<NonNull<RendererState>>>();
+            let align = mem::align_of::<Option<NonNull<RendererState>>>();
+            let offset = (query.instance_size as usize).div_ceil(align) * align;
+            let class_size =
+                (query.class_size as usize).max(mem::size_of::<pango2_sys::Pango2RendererClass>());
+            let renderer_type = g_type_register_static_simple(
+                parent_type,
+                c"Pango2RustRenderer".as_ptr(),
+                class_size as c_uint,
+                Some(renderer_class_init),
+                (offset + slot) as c_uint,
+                None,
+                0,
+            );
+            (renderer_type, offset)
+        })
+    }
+
+    unsafe fn renderer_slot(
+        renderer: *mut pango2_sys::Pango2Renderer,
+        offset: usize,
+    ) -> *mut Option<NonNull<RendererState>> {
+        (renderer as *mut u8).add(offset) as *mut Option<NonNull<RendererState>>
+    }
+
+    /// Calls the [`Renderer`] of the instance, unless it is gone or panicked
+    /// before. A panic is kept in the state for [`CustomRenderer`].
+    unsafe fn with_renderer(
+        renderer: *mut pango2_sys::Pango2Renderer,
+        call: impl FnOnce(&mut dyn Renderer),
+    ) {
+        let (_, offset) = custom_renderer_type();
+        let state = match *renderer_slot(renderer, offset) {
+            Some(state) => &mut *state.as_ptr(),
+            None => return,
+        };
+        if state.panic.is_some() {
+            return;
+        }
+        let imp = &mut *state.imp.as_ptr();
+        if let Err(payload) = panic::catch_unwind(AssertUnwindSafe(|| call(imp))) {
+            state.panic = Some(payload);
+        }
+    }
+
+    unsafe extern "C" fn renderer_class_init(class: gpointer, _data: gpointer) {
+        RENDERER_PARENT_CLASS.store(
+            g_type_class_peek_parent(class) as *mut pango2_sys::Pango2RendererClass,
+            Ordering::Release,
+        );
+        let class = &mut *(class as *mut pango2_sys::Pango2RendererClass);
+        class.prepare_run = Some(prepare_run);
+        class.draw_glyphs = Some(draw_glyphs);
+        class.draw_rectangle = Some(draw_rectangle);
+        class.draw_styled_line = Some(draw_styled_line);
+        class.draw_trapezoid = Some(draw_trapezoid);
+    }
+
+    unsafe extern "C" fn prepare_run(
+        renderer: *mut pango2_sys::Pango2Renderer,
+        run: *mut pango2_sys::Pango2Run,
+    ) {
+        let parent = RENDERER_PARENT_CLASS.load(Ordering::Acquire);
+        if let Some(parent_prepare_run) = parent.as_ref().and_then(|parent| parent.prepare_run) {
+            parent_prepare_run(renderer, run);
+        }
+        with_renderer(renderer, |imp| {
+            imp.prepare_run(&RenderRun {
+                renderer,
+                run,
+                _lifetime: PhantomData,
+            })
+        });
+    }
+
+    unsafe extern "C" fn draw_glyphs(
+        renderer: *mut pango2_sys::Pango2Renderer,
+        font: *mut pango2_sys::Pango2Font,
+        glyphs: *mut pango2_sys::Pango2GlyphString,
+        x: c_int,
+        y: c_int,
+    ) {
+        with_renderer(renderer, |imp| {
+            let font = harfbuzz::Font::from_raw(pango2_sys::pango2_font_get_hb_font(font));
+            imp.draw_glyphs(&font, &glyph_infos(glyphs), x, y);
+        });
+    }
+
+    unsafe extern "C" fn draw_rectangle(
+        renderer: *mut pango2_sys::Pango2Renderer,
+        part: pango2_sys::Pango2RenderPart,
+        x: c_int,
+        y: c_int,
+        width: c_int,
+        height: c_int,
+    ) {
+        with_renderer(renderer, |imp| {
+            imp.draw_rectangle(
+                part.into(),
+                Rectangle {
+                    x,
+                    y,
+                    width,
+                    height,
+                },
+            )
+        });
+    }
+
+    unsafe extern "C" fn draw_styled_line(
+        renderer: *mut pango2_sys::Pango2Renderer,
+        part: pango2_sys::Pango2RenderPart,
+        style: pango2_sys::Pango2LineStyle,
+        x: c_int,
+        y: c_int,
+        width: c_int,
+        height: c_int,
+    ) {
+        with_renderer(renderer, |imp| {
+            imp.draw_styled_line(
+                part.into(),
+                style.into(),
+                Rectangle {
+                    x,
+                    y,
+                    width,
+                    height,
+                },
+            )
+        });
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    unsafe extern "C" fn draw_trapezoid(
+        renderer: *mut pango2_sys::Pango2Renderer,
+        part: pango2_sys::Pango2RenderPart,
+        y1: f64,
+        x11: f64,
+        x21: f64,
+        y2: f64,
+        x12: f64,
+        x22: f64,
+    ) {
+        with_renderer(renderer, |imp| {
+            imp.draw_trapezoid(
+                part.into(),
+                Trapezoid {
+                    y1,
+                    x11,
+                    x21,
+                    y2,
+                    x12,
+                    x22,
+                },
+            )
+        });
+    }
+
+    /// Font descriptions, attribute lists, colors and tab arrays are persisted in their
+    /// Pango string form, so the serialized data stays readable by Pango.
+    #[cfg(feature = "serde")]
+    mod serde_impls {
+        use super::{AttrList, Color, FontDescription, TabArray};
+        use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+        use std::ffi::CString;
+
+        macro_rules! impl_string_serde {
+            ($type:ty, $parse:expr, $expected:literal) => {
+                impl Serialize for $type {
+                    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                        serializer.collect_str(self)
+                    }
+                }
+
+                impl<'de> Deserialize<'de> for $type {
+                    fn deserialize<D: Deserializer<'de>>(
+                        deserializer: D,
+                    ) -> Result<Self, D::Error> {
+                        let string = String::deserialize(deserializer)?;
+                        // Pango takes C strings, which end at the first NUL
+                        CString::new(string.as_str()).map_err(|error| {
+                            D::Error::custom(format!("invalid {}: {}", $expected, error))
+                        })?;
+                        let parse: fn(&str) -> Option<$type> = $parse;
+                        parse(&string).ok_or_else(|| {
+                            D::Error::custom(format!("invalid {}: {:?}", $expected, string))
+                        })
+                    }
+                }
+            };
+        }
+
+        impl_string_serde!(
+            FontDescription,
+            |string| Some(FontDescription::from_string(string)),
+            "font description"
+        );
+        impl_string_serde!(AttrList, AttrList::from_string, "attribute list");
+        impl_string_serde!(Color, Color::parse, "color");
+        impl_string_serde!(TabArray, TabArray::from_string, "tab array");
+    }
+}
+
+pub mod cairo {
+    use std::{
+        any::Any,
+        ffi::{c_double, c_int, c_uchar, c_uint, c_void, CStr, CString},
+        fmt,
+        io::{self, Read, Write},
+        marker::PhantomData,
+        ops::{Deref, DerefMut},
+        panic::{self, AssertUnwindSafe},
+        ptr, slice,
+    };
+
+    #[cfg(any(feature = "pdf", feature = "svg", feature = "ps"))]
+    use std::{ffi::c_char, ptr::NonNull};
+
+    #[cfg(feature = "pdf")]
+    use std::ops::BitOr;
+
+    #[derive(Debug)]
+    pub enum CairoError {
+        SurfaceCreateError,
+        PathError,
+        /// The surface failed while writing its remaining output.
+        SurfaceFinishError,
+        /// Writing to or reading from a stream failed.
+        IoError(io::Error),
+        /// Cairo could not encode the surface as PNG, e.g. because it is not
+        /// an image surface.
+        PngError,
+    }
+
+    /// Tag for hyperlinks, see [`CairoContext::tag_begin`].
+    pub const TAG_LINK: &str = "Link";
+    /// Tag for link destinations, see [`CairoContext::tag_begin`].
+    pub const TAG_DEST: &str = "cairo.dest";
+
+    /// A segment of a path in user space coordinates, displayed as an SVG
+    /// path command with coordinates rounded to two decimals.
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum PathSegment {
+        MoveTo(f32, f32),
+        LineTo(f32, f32),
+        CurveTo(f32, f32, f32, f32, f32, f32),
+        ClosePath,
+    }
+
+    impl fmt::Display for PathSegment {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            // adding zero turns a negative zero into zero
+            let n = |value: f32| (value * 100.0).round() / 100.0 + 0.0;
+            match *self {
+                PathSegment::MoveTo(x, y) => write!(f, "M {} {}", n(x), n(y)),
+                PathSegment::LineTo(x, y) => write!(f, "L {} {}", n(x), n(y)),
+                PathSegment::CurveTo(x1, y1, x2, y2, x3, y3) => write!(
+                    f,
+                    "C {} {} {} {} {} {}",
+                    n(x1),
+                    n(y1),
+                    n(x2),
+                    n(y2),
+                    n(x3),
+                    n(y3)
+                ),
+                PathSegment::ClosePath => write!(f, "Z"),
+            }
+        }
+    }
+
+    /// Memory layout of the pixels of an image surface.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum Format {
+        Invalid,
+        /// Premultiplied alpha, red, green and blue in a native-endian `u32`.
+        Argb32,
+        /// Like `Argb32` with the upper 8 bits unused.
+        Rgb24,
+        /// 8 bit alpha only, e.g. for glyph masks.
+        A8,
+        /// 1 bit alpha only, packed into native-endian `u32`s.
+        A1,
+        Rgb16_565,
+        Rgb30,
+        /// Red, green and blue as `f32`.
+        Rgb96f,
+        /// Premultiplied red, green, blue and alpha as `f32`.
+        Rgba128f,
+    }
+
+    impl Format {
+        /// Returns the stride cairo uses for rows of `width` pixels, `None`
+        /// if the format or width is not supported.
+        pub fn stride_for_width(self, width: i32) -> Option<usize> {
+            let stride =
+                unsafe { cairo_sys::cairo_format_stride_for_width(self.into(), width as c_int) };
+            (stride >= 0).then_some(stride as usize)
+        }
+    }
+
+    impl From<Format> for cairo_sys::cairo_format_t {
+        fn from(format: Format) -> Self {
+            match format {
+                Format::Invalid => cairo_sys::_cairo_format_CAIRO_FORMAT_INVALID,
+                Format::Argb32 => cairo_sys::_cairo_format_CAIRO_FORMAT_ARGB32,
+                Format::Rgb24 => cairo_sys::_cairo_format_CAIRO_FORMAT_RGB24,
+                Format::A8 => cairo_sys::_cairo_format_CAIRO_FORMAT_A8,
+                Format::A1 => cairo_sys::_cairo_format_CAIRO_FORMAT_A1,
+                Format::Rgb16_565 => cairo_sys::_cairo_format_CAIRO_FORMAT_RGB16_565,
+                Format::Rgb30 => cairo_sys::_cairo_format_CAIRO_FORMAT_RGB30,
+                Format::Rgb96f => cairo_sys::_cairo_format_CAIRO_FORMAT_RGB96F,
+                Format::Rgba128f => cairo_sys::_cairo_format_CAIRO_FORMAT_RGBA128F,
+            }
+        }
+    }
+
+    impl From<cairo_sys::cairo_format_t> for Format {
+        fn from(format: cairo_sys::cairo_format_t) -> Self {
+            match format {
+                cairo_sys::_cairo_format_CAIRO_FORMAT_ARGB32 => Format::Argb32,
+                cairo_sys::_cairo_format_CAIRO_FORMAT_RGB24 => Format::Rgb24,
+                cairo_sys::_cairo_format_CAIRO_FORMAT_A8 => Format::A8,
+                cairo_sys::_cairo_format_CAIRO_FORMAT_A1 => Format::A1,
+                cairo_sys::_cairo_format_CAIRO_FORMAT_RGB16_565 => Format::Rgb16_565,
+                cairo_sys::_cairo_format_CAIRO_FORMAT_RGB30 => Format::Rgb30,
+                cairo_sys::_cairo_format_CAIRO_FORMAT_RGB96F => Format::Rgb96f,
+                cairo_sys::_cairo_format_CAIRO_FORMAT_RGBA128F => Format::Rgba128f,
+                _ => Format::Invalid,
+            }
+        }
+    }
+
+    pub struct CairoSurface {
+        raw: *mut cairo_sys::cairo_surface_t,
+    }
+
+    impl CairoSurface {
+        pub fn new_image_surface(width: i32, height: i32) -> Result<Self, CairoError> {
+            Self::new_image_surface_with_format(Format::Argb32, width, height)
+        }
+
+        pub fn new_image_surface_with_format(
+            format: Format,
+            width: i32,
+            height: i32,
+        ) -> Result<Self, CairoError> {
+            unsafe {
+                let raw = cairo_sys::cairo_image_surface_create(
+                    format.into(),
+                    width as c_int,
+                    height as c_int,
+                );
+                let status = cairo_sys::cairo_surface_status(raw);
+                if status == cairo_sys::_cairo_status_CAIRO_STATUS_SUCCESS {
+                    Ok(Self { raw })
+                } else {
+                    Err(CairoError::SurfaceCreateError)
+                }
+            }
+        }
+
+        pub fn raw(&self) -> &*mut cairo_sys::cairo_surface_t {
+            &self.raw
+        }
+
+        /// Width in pixels, 0 for surfaces that are not image surfaces.
+        pub fn width(&self) -> i32 {
+            unsafe { cairo_sys::cairo_image_surface_get_width(self.raw) as i32 }
+        }
+
+        pub fn height(&self) -> i32 {
+            unsafe { cairo_sys::cairo_image_surface_get_height(self.raw) as i32 }
+        }
+
+        /// Number of bytes between the starts of two rows.
+        pub fn stride(&self) -> usize {
+            unsafe { cairo_sys::cairo_image_surface_get_stride(self.raw) as usize }
+        }
+
+        pub fn format(&self) -> Format {
+            unsafe { cairo_sys::cairo_image_surface_get_format(self.raw).into() }
+        }
+
+        /// Returns the pixels of an image surface, `stride * height` bytes,
+        /// with pending drawing flushed. Returns `None` for other surfaces
+        /// and while the surface is referenced elsewhere, e.g. by a
+        /// [`CairoContext`] or a pattern, as those could draw into the
+        /// pixels while they are borrowed. Drop them first. Cairo can hold
+        /// references of its own too, e.g. a recording surface keeps one
+        /// to every surface it painted until it is dropped.
+        pub fn data(&mut self) -> Option<&[u8]> {
+            if self.is_shared() {
+                return None;
+            }
+            let (data, len) = self.pixels()?;
+            unsafe { Some(slice::from_raw_parts(data, len)) }
+        }
+
+        /// Returns the pixels for modification like [`CairoSurface::data`],
+        /// cairo is told about the changes when the returned guard is
+        /// dropped.
+        pub fn data_mut(&mut self) -> Option<ImageData<'_>> {
+            if self.is_shared() {
+                return None;
+            }
+            let (data, len) = self.pixels()?;
+            unsafe {
+                Some(ImageData {
+                    surface: self,
+                    data: slice::from_raw_parts_mut(data, len),
+                })
+            }
+        }
+
+        /// Returns true if cairo objects other than this one hold a
+        /// reference to the surface.
+        fn is_shared(&self) -> bool {
+            unsafe { cairo_sys::cairo_surface_get_reference_count(self.raw) > 1 }
+        }
+
+        /// Flushes pending drawing and returns the address and length of the
+        /// pixels of an image surface. They must not be accessed while
+        /// anything can draw into the surface.
+        fn pixels(&self) -> Option<(*mut u8, usize)> {
+            unsafe {
+                cairo_sys::cairo_surface_flush(self.raw);
+                let data = cairo_sys::cairo_image_surface_get_data(self.raw);
+                if data.is_null() {
+                    None
+                } else {
+                    Some((data, self.stride() * self.height() as usize))
+                }
+            }
+        }
+
+        /// Converts the pixels of an `Argb32` or `Rgb24` surface into
+        /// straight RGBA with 8 bits per channel and no row padding.
+        /// Works while contexts reference the surface, as the pixels are
+        /// copied before they can draw again.
+        pub fn to_rgba8(&self) -> Option<Vec<u8>> {
+            let (data, len) = self.pixels()?;
+            let data = unsafe { slice::from_raw_parts(data, len) };
+            let (width, height) = (self.width() as usize, self.height() as usize);
+            match self.format() {
+                Format::Argb32 => argb32_to_rgba8(data, width, height, self.stride()),
+                Format::Rgb24 => rgb24_to_rgba8(data, width, height, self.stride()),
+                _ => None,
+            }
+        }
+
+        /// Reads a PNG image into a new image surface.
+        pub fn read_png<R: Read>(reader: R) -> Result<Self, CairoError> {
+            let mut stream = ReadStream::new(reader);
+            unsafe {
+                let raw = cairo_sys::cairo_image_surface_create_from_png_stream(
+                    Some(ReadStream::read),
+                    stream.closure(),
+                );
+                let surface = Self { raw };
+                stream.finish()?;
+                if cairo_sys::cairo_surface_status(raw)
+                    == cairo_sys::_cairo_status_CAIRO_STATUS_SUCCESS
+                {
+                    Ok(surface)
+                } else {
+                    Err(CairoError::SurfaceCreateError)
+                }
+            }
+        }
+
+        /// Writes the surface as PNG image to `writer`.
+        pub fn write_png<W: Write>(&self, writer: W) -> Result<(), CairoError> {
+            let mut stream = WriteStream::new(writer);
+            unsafe {
+                let status = cairo_sys::cairo_surface_write_to_png_stream(
+                    self.raw,
+                    Some(WriteStream::write),
+                    stream.closure(),
+                );
+                stream.finish()?;
+                if status == cairo_sys::_cairo_status_CAIRO_STATUS_SUCCESS {
+                    Ok(())
+                } else {
+                    Err(CairoError::PngError)
+                }
+            }
+        }
+
+        pub fn write_to_png(&self, path: &str) -> Result<(), CairoError> {
+            unsafe {
+                let cpath = CString::new(path).unwrap();
+                let status = cairo_sys::cairo_surface_write_to_png(self.raw, cpath.as_ptr());
+                if status == cairo_sys::_cairo_status_CAIRO_STATUS_SUCCESS {
+                    Ok(())
+                } else {
+                    Err(CairoError::SurfaceCreateError)
+                }
+            }
+        }
+    }
+
+    impl Drop for CairoSurface {
+        fn drop(&mut self) {
+            unsafe {
+                cairo_sys::cairo_surface_destroy(self.raw);
+            }
+        }
+    }
+
+    /// Mutable access to the pixels of an image surface, marks the surface
+    /// dirty when dropped.
+    pub struct ImageData<'a> {
+        surface: &'a CairoSurface,
+        data: &'a mut [u8],
+    }
+
+    impl Deref for ImageData<'_> {
+        type Target = [u8];
+
+        fn deref(&self) -> &[u8] {
+            self.data
+        }
+    }
+
+    impl DerefMut for ImageData<'_> {
+        fn deref_mut(&mut self) -> &mut [u8] {
+            self.data
+        }
+    }
+
+    impl Drop for ImageData<'_> {
+        fn drop(&mut self) {
+            unsafe {
+                cairo_sys::cairo_surface_mark_dirty(self.surface.raw);
+            }
+        }
+    }
+
+    /// Converts premultiplied, native-endian ARGB32 pixels into straight
+    /// RGBA bytes, rows of `width` pixels are read every `stride` bytes.
+    /// Returns `None` if a row doesn't fit into the stride or `data` is
+    /// shorter than `stride * height` bytes.
+    pub fn argb32_to_rgba8(
+        data: &[u8],
+        width: usize,
+        height: usize,
+        stride: usize,
+    ) -> Option<Vec<u8>> {
+        convert_pixels(data, width, height, stride, |pixel| {
+            let alpha = pixel >> 24;
+            let unpremultiply =
+                |channel: u32| (channel * 255 + alpha / 2).checked_div(alpha).unwrap_or(0) as u8;
+            [
+                unpremultiply((pixel >> 16) & 0xff),
+                unpremultiply((pixel >> 8) & 0xff),
+                unpremultiply(pixel & 0xff),
+                alpha as u8,
+            ]
+        })
+    }
+
+    /// Converts native-endian RGB24 pixels into opaque RGBA bytes like
+    /// [`argb32_to_rgba8`], the unused upper byte is ignored.
+    pub fn rgb24_to_rgba8(
+        data: &[u8],
+        width: usize,
+        height: usize,
+        stride: usize,
+    ) -> Option<Vec<u8>> {
+        convert_pixels(data, width, height, stride, |pixel| {
+            [(pixel >> 16) as u8, (pixel >> 8) as u8, pixel as u8, 0xff]
+        })
+    }
+
+    /// Maps every native-endian 32 bit pixel to RGBA bytes.
+    fn convert_pixels(
+        data: &[u8],
+        width: usize,
+        height: usize,
+        stride: usize,
+        convert: impl Fn(u32) -> [u8; 4],
+    ) -> Option<Vec<u8>> {
+        let row_length = width.checked_mul(4)?;
+        if stride < row_length || data.len() < stride.checked_mul(height)? {
+            return None;
+        }
+        let mut rgba = Vec::with_capacity(row_length * height);
+        for row in 0..height {
+            let start = row * stride;
+            for pixel in data[start..start + row_length].chunks_exact(4) {
+                let pixel = u32::from_ne_bytes([pixel[0], pixel[1], pixel[2], pixel[3]]);
+                rgba.extend_from_slice(&convert(pixel));
+            }
+        }
+        Some(rgba)
+    }
+
+    /// An image surface drawing into memory owned by the caller. Cairo only
+    /// draws into the memory inside [`with_context`](Self::with_context), the
+    /// surface is not handed out otherwise.
+    pub struct BorrowedImageSurface<'a> {
+        surface: CairoSurface,
+        _data: PhantomData<&'a mut [u8]>,
+    }
+
+    impl<'a> BorrowedImageSurface<'a> {
+        /// Creates a surface over `data`, which must hold `height` rows of
+        /// `stride` bytes. The stride must be at least
+        /// [`Format::stride_for_width`] and a multiple of 4, `data` must be
+        /// aligned for the pixels of the format, e.g. to 4 bytes for
+        /// `Format::Argb32`.
+        pub fn new(
+            data: &'a mut [u8],
+            format: Format,
+            width: i32,
+            height: i32,
+            stride: usize,
+        ) -> Result<Self, CairoError> {
+            let min_stride = format
+                .stride_for_width(width)
+                .ok_or(CairoError::SurfaceCreateError)?;
+            // cairo accesses the pixels in place as 16 or 32 bit values
+            let alignment = match format {
+                Format::A8 => 1,
+                Format::Rgb16_565 => 2,
+                _ => 4,
+            };
+            if height < 0
+                || stride < min_stride
+                || data.len() < stride * height as usize
+                || data.as_ptr().align_offset(alignment) != 0
+            {
+                return Err(CairoError::SurfaceCreateError);
+            }
+            unsafe {
+                let raw = cairo_sys::cairo_image_surface_create_for_data(
+                    data.as_mut_ptr(),
+                    format.into(),
+                    width as c_int,
+                    height as c_int,
+                    stride as c_int,
+                );
+                let surface = CairoSurface { raw };
+                if cairo_sys::cairo_surface_status(raw)
+                    == cairo_sys::_cairo_status_CAIRO_STATUS_SUCCESS
+                {
+                    Ok(Self {
+                        surface,
+                        _data: PhantomData,
+                    })
+                } else {
+                    Err(CairoError::SurfaceCreateError)
+                }
+            }
+        }
+
+        /// Calls `f` with a context drawing on the surface. If anything
+        /// still references the surface once `f` returns or panics, e.g. a
+        /// leaked context, the surface is finished so that it can no longer
+        /// draw into the memory, and later calls draw nothing.
+        pub fn with_context<R>(&mut self, f: impl FnOnce(&CairoContext) -> R) -> R {
+            /// Finishes a surface that is still shared when dropped, unwinding
+            /// included.
+            struct FinishIfShared<'s>(&'s CairoSurface);
+
+            impl Drop for FinishIfShared<'_> {
+                fn drop(&mut self) {
+                    unsafe {
+                        cairo_sys::cairo_surface_flush(self.0.raw);
+                        if self.0.is_shared() {
+                            cairo_sys::cairo_surface_finish(self.0.raw);
+                        }
+                    }
+                }
+            }
+
+            let _finish = FinishIfShared(&self.surface);
+            let context = CairoContext::create(&self.surface);
+            f(&context)
+        }
+    }
+
+    /// What a surface stores, see [`RecordingSurface::new`].
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum Content {
+        Color,
+        Alpha,
+        ColorAlpha,
+    }
+
+    impl From<Content> for cairo_sys::cairo_content_t {
+        fn from(content: Content) -> Self {
+            match content {
+                Content::Color => cairo_sys::_cairo_content_CAIRO_CONTENT_COLOR,
+                Content::Alpha => cairo_sys::_cairo_content_CAIRO_CONTENT_ALPHA,
+                Content::ColorAlpha => cairo_sys::_cairo_content_CAIRO_CONTENT_COLOR_ALPHA,
+            }
+        }
+    }
+
+    impl From<cairo_sys::cairo_content_t> for Content {
+        fn from(content: cairo_sys::cairo_content_t) -> Self {
+            match content {
+                cairo_sys::_cairo_content_CAIRO_CONTENT_COLOR => Content::Color,
+                cairo_sys::_cairo_content_CAIRO_CONTENT_ALPHA => Content::Alpha,
+                _ => Content::ColorAlpha,
+            }
+        }
+    }
+
+    /// A rectangle in user space or device units.
+    #[derive(Debug, Clone, Copy, PartialEq, Default)]
+    pub struct Rectangle {
+        pub x: f32,
+        pub y: f32,
+        pub width: f32,
+        pub height: f32,
+    }
+
+    impl From<Rectangle> for cairo_sys::cairo_rectangle_t {
+        fn from(rect: Rectangle) -> Self {
+            cairo_sys::cairo_rectangle_t {
+                x: rect.x as c_double,
+                y: rect.y as c_double,
+                width: rect.width as c_double,
+                height: rect.height as c_double,
+            }
+        }
+    }
+
+    impl From<cairo_sys::cairo_rectangle_t> for Rectangle {
+        fn from(rect: cairo_sys::cairo_rectangle_t) -> Self {
+            Rectangle {
+                x: rect.x as f32,
+                y: rect.y as f32,
+                width: rect.width as f32,
+                height: rect.height as f32,
+            }
+        }
+    }
+
+    /// A surface keeping the drawing operations instead of pixels. Replaying
+    /// it onto another surface draws the recorded operations again, so text
+    /// stays sharp at any scale and position.
+    pub struct RecordingSurface {
+        surface: CairoSurface,
+    }
+
+    impl RecordingSurface {
+        /// Creates a surface recording everything drawn inside `extents`,
+        /// `None` records without bounds.
+        pub fn new(content: Content, extents: Option<Rectangle>) -> Result<Self, CairoError> {
+            let extents = extents.map(cairo_sys::cairo_rectangle_t::from);
+            unsafe {
+                let raw = cairo_sys::cairo_recording_surface_create(
+                    content.into(),
+                    extents
+                        .as_ref()
+                        .map_or(ptr::null(), |extents| extents as *const _),
+                );
+                let surface = CairoSurface { raw };
+                if cairo_sys::cairo_surface_status(raw)
+                    == cairo_sys::_cairo_status_CAIRO_STATUS_SUCCESS
+                {
+                    Ok(Self { surface })
+                } else {
+                    Err(CairoError::SurfaceCreateError)
+                }
+            }
+        }
+
+        /// The bounds passed to [`RecordingSurface::new`], `None` for
+        /// unbounded surfaces.
+        pub fn extents(&self) -> Option<Rectangle> {
+            let mut extents = Rectangle::default().into();
+            unsafe {
+                if cairo_sys::cairo_recording_surface_get_extents(self.surface.raw, &mut extents)
+                    != 0
+                {
+                    Some(extents.into())
+                } else {
+                    None
+                }
+            }
+        }
+
+        /// The bounding box of everything drawn so far, in the units of the
+        /// recording. Empty recordings give a rectangle of size zero. The
+        /// box is conservative, cairo bounds glyphs by their extents and
+        /// strokes by their pen, so it can be larger than the painted
+        /// pixels, see [`RecordingSurface::exact_ink_extents`].
+        pub fn ink_extents(&self) -> Rectangle {
+            let (mut x, mut y, mut width, mut height) = (0.0, 0.0, 0.0, 0.0);
+            unsafe {
+                cairo_sys::cairo_recording_surface_ink_extents(
+                    self.surface.raw,
+                    &mut x,
+                    &mut y,
+                    &mut width,
+                    &mut height,
+                );
+            }
+            Rectangle {
+                x: x as f32,
+                y: y as f32,
+                width: width as f32,
+                height: height as f32,
+            }
+        }
+
+        /// Draws the recording onto the target of `context` with its origin
+        /// at `x`, `y` in user space, the transformation and clip of the
+        /// context apply. To draw the recording in another color use it as
+        /// mask with [`CairoContext::mask_surface`].
+        pub fn replay(&self, context: &CairoContext, x: f32, y: f32) {
+            context.save();
+            context.set_source_surface(&self.surface, x, y);
+            context.paint();
+            context.restore();
+        }
+
+        /// The pixels with a non-zero alpha when the recording is
+        /// rasterized at `scale`, in whole pixels of the scaled recording.
+        /// Recordings without ink give a rectangle of size zero.
+        pub fn exact_ink_extents(&self, scale: f32) -> Result<Rectangle, CairoError> {
+            let (mut image, left, top) = self.rasterize(scale)?;
+            Ok(match alpha_bounds(&mut image) {
+                Some((x0, y0, x1, y1)) => Rectangle {
+                    x: (left + x0) as f32,
+                    y: (top + y0) as f32,
+                    width: (x1 - x0) as f32,
+                    height: (y1 - y0) as f32,
+                },
+                None => Rectangle::default(),
+            })
+        }
+
+        /// Rasterizes the recording at `scale` into an `Argb32` image
+        /// cropped to [`RecordingSurface::exact_ink_extents`]. Returns the
+        /// image and the position of its top left pixel in the scaled
+        /// recording, recordings without ink give an empty image.
+        pub fn to_image_surface(&self, scale: f32) -> Result<(CairoSurface, i32, i32), CairoError> {
+            let (mut image, left, top) = self.rasterize(scale)?;
+            let (x0, y0, x1, y1) = match alpha_bounds(&mut image) {
+                Some(bounds) => bounds,
+                None => return Ok((CairoSurface::new_image_surface(0, 0)?, left, top)),
+            };
+            let cropped = CairoSurface::new_image_surface(x1 - x0, y1 - y0)?;
+            let context = CairoContext::create(&cropped);
+            context.set_source_surface(&image, -x0 as f32, -y0 as f32);
+            context.set_operator(Operator::Source);
+            context.paint();
+            drop(context);
+            Ok((cropped, left + x0, top + y0))
+        }
+
+        /// Rasterizes the recording at `scale` into an image covering the
+        /// conservative ink extents. Returns the image and the position of
+        /// its top left pixel.
+        fn rasterize(&self, scale: f32) -> Result<(CairoSurface, i32, i32), CairoError> {
+            let ink = self.ink_extents();
+            let left = (ink.x * scale).floor() as i32;
+            let top = (ink.y * scale).floor() as i32;
+            let right = ((ink.x + ink.width) * scale).ceil() as i32;
+            let bottom = ((ink.y + ink.height) * scale).ceil() as i32;
+            let image = CairoSurface::new_image_surface(right - left, bottom - top)?;
+            let context = CairoContext::create(&image);
+            context.translate(-left as f32, -top as f32);
+            context.scale(scale, scale);
+            self.replay(&context, 0.0, 0.0);
+            drop(context);
+            Ok((image, left, top))
+        }
+    }
+
+    /// The smallest box, right and bottom exclusive, holding every pixel
+    /// of an `Argb32` image with a non-zero alpha.
+    fn alpha_bounds(image: &mut CairoSurface) -> Option<(i32, i32, i32, i32)> {
+        let (width, stride) = (image.width() as usize, image.stride());
+        let data = image.data()?;
+        let mut bounds: Option<(usize, usize, usize, usize)> = None;
+        for (y, row) in data.chunks(stride).enumerate() {
+            for (x, pixel) in row[..width * 4].chunks_exact(4).enumerate() {
+                if u32::from_ne_bytes([pixel[0], pixel[1], pixel[2], pixel[3]]) >> 24 == 0 {
+                    continue;
+                }
+                bounds = Some(match bounds {
+                    Some((x0, y0, x1, y1)) => (x0.min(x), y0.min(y), x1.max(x + 1), y1.max(y + 1)),
+                    None => (x, y, x + 1, y + 1),
+                });
+            }
+        }
+        bounds.map(|(x0, y0, x1, y1)| (x0 as i32, y0 as i32, x1 as i32, y1 as i32))
+    }
+
+    impl Deref for RecordingSurface {
+        type Target = CairoSurface;
+
+        fn deref(&self) -> &CairoSurface {
+            &self.surface
+        }
+    }
+
+    /// Adapts a writer to `cairo_write_func_t`. Errors and panics of the
+    /// writer are kept and handed to the caller once cairo returns, unwinding
+    /// through cairo would abort the process.
+    struct WriteStream<'a> {
+        writer: Box<dyn Write + 'a>,
+        error: Option<io::Error>,
+        panic: Option<Box<dyn Any + Send>>,
+    }
+
+    impl<'a> WriteStream<'a> {
+        fn new(writer: impl Write + 'a) -> Box<Self> {
+            Box::new(Self {
+                writer: Box::new(writer),
+                error: None,
+                panic: None,
+            })
+        }
+
+        fn closure(&mut self) -> *mut c_void {
+            self as *mut Self as *mut c_void
+        }
+
+        unsafe extern "C" fn write(
+            closure: *mut c_void,
+            data: *const c_uchar,
+            length: c_uint,
+        ) -> cairo_sys::cairo_status_t {
+            let stream = &mut *(closure as *mut WriteStream);
+            if stream.error.is_some() || stream.panic.is_some() {
+                return cairo_sys::_cairo_status_CAIRO_STATUS_WRITE_ERROR;
+            }
+            let data = slice::from_raw_parts(data, length as usize);
+            match panic::catch_unwind(AssertUnwindSafe(|| stream.writer.write_all(data))) {
+                Ok(Ok(())) => cairo_sys::_cairo_status_CAIRO_STATUS_SUCCESS,
+                Ok(Err(error)) => {
+                    stream.error = Some(error);
+                    cairo_sys::_cairo_status_CAIRO_STATUS_WRITE_ERROR
+                }
+                Err(payload) => {
+                    stream.panic = Some(payload);
+                    cairo_sys::_cairo_status_CAIRO_STATUS_WRITE_ERROR
+                }
+            }
+        }
+
+        /// Resumes a panic of the writer, otherwise returns its error and
+        /// flushes it.
+        fn finish(&mut self) -> Result<(), CairoError> {
+            if let Some(payload) = self.panic.take() {
+                panic::resume_unwind(payload);
+            }
+            if let Some(error) = self.error.take() {
+                return Err(CairoError::IoError(error));
+            }
+            self.writer.flush().map_err(CairoError::IoError)
+        }
+    }
+
+    /// Adapts a reader to `cairo_read_func_t`, see [`WriteStream`].
+    struct ReadStream<'a> {
+        reader: Box<dyn Read + 'a>,
+        error: Option<io::Error>,
+        panic: Option<Box<dyn Any + Send>>,
+    }
+
+    impl<'a> ReadStream<'a> {
+        fn new(reader: impl Read + 'a) -> Box<Self> {
+            Box::new(Self {
+                reader: Box::new(reader),
+                error: None,
+                panic: None,
+            })
+        }
+
+        fn closure(&mut self) -> *mut c_void {
+            self as *mut Self as *mut c_void
+        }
+
+        unsafe extern "C" fn read(
+            closure: *mut c_void,
+            data: *mut c_uchar,
+            length: c_uint,
+        ) -> cairo_sys::cairo_status_t {
+            let stream = &mut *(closure as *mut ReadStream);
+            if stream.error.is_some() || stream.panic.is_some() {
+                return cairo_sys::_cairo_status_CAIRO_STATUS_READ_ERROR;
+            }
+            let data = slice::from_raw_parts_mut(data, length as usize);
+            match panic::catch_unwind(AssertUnwindSafe(|| stream.reader.read_exact(data))) {
+                Ok(Ok(())) => cairo_sys::_cairo_status_CAIRO_STATUS_SUCCESS,
+                Ok(Err(error)) => {
+                    stream.error = Some(error);
+                    cairo_sys::_cairo_status_CAIRO_STATUS_READ_ERROR
+                }
+                Err(payload) => {
+                    stream.panic = Some(payload);
+                    cairo_sys::_cairo_status_CAIRO_STATUS_READ_ERROR
+                }
+            }
+        }
+
+        /// Resumes a panic of the reader, otherwise returns its error.
+        fn finish(&mut self) -> Result<(), CairoError> {
+            if let Some(payload) = self.panic.take() {
+                panic::resume_unwind(payload);
+            }
+            match self.error.take() {
+                Some(error) => Err(CairoError::IoError(error)),
+                None => Ok(()),
+            }
+        }
+    }
+
+    /// A surface writing a document to a file or a stream, shared by the
+    /// PDF, SVG and PostScript surfaces.
+    #[cfg(any(feature = "pdf", feature = "svg", feature = "ps"))]
+    struct DocumentSurface {
+        surface: CairoSurface,
+        /// The stream of `for_stream`, owned by the cairo surface.
+        stream: Option<NonNull<WriteStream<'static>>>,
+    }
+
+    /// Key of the stream a document surface writes to, see
+    /// [`DocumentSurface::for_stream`].
+    #[cfg(any(feature = "pdf", feature = "svg", feature = "ps"))]
+    static STREAM_KEY: cairo_sys::cairo_user_data_key_t =
+        cairo_sys::cairo_user_data_key_t { unused: 0 };
+
+    #[cfg(any(feature = "pdf", feature = "svg", feature = "ps"))]
+    impl DocumentSurface {
+        /// Creates the surface with `create` for a path, `create_for_stream`
+        /// for a writer.
+        unsafe fn new(
+            path: &str,
+            width: f32,
+            height: f32,
+            create: unsafe extern "C" fn(
+                *const c_char,
+                c_double,
+                c_double,
+            ) -> *mut cairo_sys::cairo_surface_t,
+        ) -> Result<Self, CairoError> {
+            let cpath = CString::new(path).map_err(|_| CairoError::SurfaceCreateError)?;
+            let raw = create(cpath.as_ptr(), width as c_double, height as c_double);
+            Self::checked(raw, None)
+        }
+
+        /// Contexts keep the surface alive and cairo writes the remaining
+        /// output when it is destroyed, so the surface owns the stream and
+        /// frees it with its user data.
+        unsafe fn for_stream(
+            writer: impl Write + 'static,
+            width: f32,
+            height: f32,
+            create_for_stream: unsafe extern "C" fn(
+                cairo_sys::cairo_write_func_t,
+                *mut c_void,
+                c_double,
+                c_double,
+            ) -> *mut cairo_sys::cairo_surface_t,
+        ) -> Result<Self, CairoError> {
+            unsafe extern "C" fn destroy_stream(stream: *mut c_void) {
+                drop(Box::from_raw(stream as *mut WriteStream<'static>));
+            }
+
+            let stream = Box::into_raw(WriteStream::new(writer));
+            let raw = create_for_stream(
+                Some(WriteStream::write),
+                stream as *mut c_void,
+                width as c_double,
+                height as c_double,
+            );
+            let status = cairo_sys::cairo_surface_set_user_data(
+                raw,
+                &STREAM_KEY,
+                stream as *mut c_void,
+                Some(destroy_stream),
+            );
+            if status != cairo_sys::_cairo_status_CAIRO_STATUS_SUCCESS {
+                // error surfaces keep no user data and never write
+                destroy_stream(stream as *mut c_void);
+                cairo_sys::cairo_surface_destroy(raw);
+                return Err(CairoError::SurfaceCreateError);
+            }
+            Self::checked(raw, NonNull::new(stream))
+        }
+
+        unsafe fn checked(
+            raw: *mut cairo_sys::cairo_surface_t,
+            stream: Option<NonNull<WriteStream<'static>>>,
+        ) -> Result<Self, CairoError> {
+            let surface = CairoSurface { raw };
+            if cairo_sys::cairo_surface_status(raw) == cairo_sys::_cairo_status_CAIRO_STATUS_SUCCESS
+            {
+                Ok(Self { surface, stream })
+            } else {
+                Err(CairoError::SurfaceCreateError)
+            }
+        }
+
+        fn show_page(&self) {
+            unsafe {
+                cairo_sys::cairo_surface_show_page(self.surface.raw);
+            }
+        }
+
+        fn finish(&mut self) -> Result<(), CairoError> {
+            unsafe {
+                cairo_sys::cairo_surface_finish(self.surface.raw);
+            }
+            if let Some(stream) = self.stream {
+                // the surface is finished, cairo no longer uses the stream
+                unsafe { (*stream.as_ptr()).finish()? };
+            }
+            let status = unsafe { cairo_sys::cairo_surface_status(self.surface.raw) };
+            if status == cairo_sys::_cairo_status_CAIRO_STATUS_SUCCESS {
+                Ok(())
+            } else {
+                Err(CairoError::SurfaceFinishError)
+            }
+        }
+    }
+
+    #[cfg(any(feature = "pdf", feature = "svg", feature = "ps"))]
+    impl Drop for DocumentSurface {
+        fn drop(&mut self) {
+            // contexts may keep the surface alive, finishing it writes the
+            // document when the surface is dropped rather than with them
+            unsafe {
+                cairo_sys::cairo_surface_finish(self.surface.raw);
+            }
+        }
+    }
+
+    /// Generates the constructors and page handling shared by the document
+    /// surfaces, the surface derefs to a [`CairoSurface`] to draw on it.
+    #[cfg(any(feature = "pdf", feature = "svg", feature = "ps"))]
+    macro_rules! impl_document_surface {
+        ($surface:ident, $create:path, $create_for_stream:path) => {
+            impl $surface {
+                /// Creates a surface writing to the file at `path`, sizes
+                /// are in points (1/72 inch).
+                pub fn new(path: &str, width: f32, height: f32) -> Result<Self, CairoError> {
+                    unsafe {
+                        Ok(Self {
+                            inner: DocumentSurface::new(path, width, height, $create)?,
+                        })
+                    }
+                }
+
+                /// Creates a surface writing to `writer`, sizes are in points.
+                /// The writer is dropped once cairo destroys the surface,
+                /// which contexts drawing on it may delay.
+                pub fn for_stream(
+                    writer: impl Write + 'static,
+                    width: f32,
+                    height: f32,
+                ) -> Result<Self, CairoError> {
+                    unsafe {
+                        Ok(Self {
+                            inner: DocumentSurface::for_stream(
+                                writer,
+                                width,
+                                height,
+                                $create_for_stream,
+                            )?,
+                        })
+                    }
+                }
+
+                /// Ends the current page and starts a new one.
+                pub fn show_page(&self) {
+                    self.inner.show_page();
+                }
+
+                /// Writes the remaining output. Dropping the surface finishes
+                /// it too, but without reporting errors.
+                pub fn finish(mut self) -> Result<(), CairoError> {
+                    self.inner.finish()
+                }
+            }
+
+            impl Deref for $surface {
+                type Target = CairoSurface;
+
+                fn deref(&self) -> &CairoSurface {
+                    &self.inner.surface
+                }
+            }
+        };
+    }
+
+    #[cfg(feature = "pdf")]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum PdfVersion {
+        V1_4,
+        V1_5,
+        V1_6,
+        V1_7,
+    }
+
+    #[cfg(feature = "pdf")]
+    impl From<PdfVersion> for cairo_sys::cairo_pdf_version_t {
+        fn from(version: PdfVersion) -> Self {
+            match version {
+                PdfVersion::V1_4 => cairo_sys::_cairo_pdf_version_CAIRO_PDF_VERSION_1_4,
+                PdfVersion::V1_5 => cairo_sys::_cairo_pdf_version_CAIRO_PDF_VERSION_1_5,
+                PdfVersion::V1_6 => cairo_sys::_cairo_pdf_version_CAIRO_PDF_VERSION_1_6,
+                PdfVersion::V1_7 => cairo_sys::_cairo_pdf_version_CAIRO_PDF_VERSION_1_7,
+            }
+        }
+    }
+
+    /// Document information of a PDF, dates are in ISO 8601 format.
+    #[cfg(feature = "pdf")]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum PdfMetadata {
+        Title,
+        Author,
+        Subject,
+        Keywords,
+        Creator,
+        CreateDate,
+        ModDate,
+    }
+
+    #[cfg(feature = "pdf")]
+    impl From<PdfMetadata> for cairo_sys::cairo_pdf_metadata_t {
+        fn from(metadata: PdfMetadata) -> Self {
+            match metadata {
+                PdfMetadata::Title => cairo_sys::_cairo_pdf_metadata_CAIRO_PDF_METADATA_TITLE,
+                PdfMetadata::Author => cairo_sys::_cairo_pdf_metadata_CAIRO_PDF_METADATA_AUTHOR,
+                PdfMetadata::Subject => cairo_sys::_cairo_pdf_metadata_CAIRO_PDF_METADATA_SUBJECT,
+                PdfMetadata::Keywords => cairo_sys::_cairo_pdf_metadata_CAIRO_PDF_METADATA_KEYWORDS,
+                PdfMetadata::Creator => cairo_sys::_cairo_pdf_metadata_CAIRO_PDF_METADATA_CREATOR,
+                PdfMetadata::CreateDate => {
+                    cairo_sys::_cairo_pdf_metadata_CAIRO_PDF_METADATA_CREATE_DATE
+                }
+                PdfMetadata::ModDate => cairo_sys::_cairo_pdf_metadata_CAIRO_PDF_METADATA_MOD_DATE,
+            }
+        }
+    }
+
+    #[cfg(feature = "pdf")]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    pub struct PdfOutlineFlags(cairo_sys::cairo_pdf_outline_flags_t);
+
+    #[cfg(feature = "pdf")]
+    impl PdfOutlineFlags {
+        pub const NONE: Self = Self(0);
+        /// Show the children of the item expanded.
+        pub const OPEN: Self =
+            Self(cairo_sys::_cairo_pdf_outline_flags_CAIRO_PDF_OUTLINE_FLAG_OPEN);
+        pub const BOLD: Self =
+            Self(cairo_sys::_cairo_pdf_outline_flags_CAIRO_PDF_OUTLINE_FLAG_BOLD);
+        pub const ITALIC: Self =
+            Self(cairo_sys::_cairo_pdf_outline_flags_CAIRO_PDF_OUTLINE_FLAG_ITALIC);
+    }
+
+    #[cfg(feature = "pdf")]
+    impl BitOr for PdfOutlineFlags {
+        type Output = Self;
+
+        fn bitor(self, rhs: Self) -> Self {
+            Self(self.0 | rhs.0)
+        }
+    }
+
+    #[cfg(feature = "pdf")]
+    pub struct PdfSurface {
+        inner: DocumentSurface,
+    }
+
+    #[cfg(feature = "pdf")]
+    impl_document_surface!(
+        PdfSurface,
+        cairo_sys::cairo_pdf_surface_create,
+        cairo_sys::cairo_pdf_surface_create_for_stream
+    );
+
+    #[cfg(feature = "pdf")]
+    impl PdfSurface {
+        /// Parent id of top level outline items.
+        pub const OUTLINE_ROOT: i32 = cairo_sys::CAIRO_PDF_OUTLINE_ROOT as i32;
+
+        /// Changes the size of the next pages, call it before drawing on a page.
+        pub fn set_size(&self, width: f32, height: f32) {
+            unsafe {
+                cairo_sys::cairo_pdf_surface_set_size(
+                    self.inner.surface.raw,
+                    width as c_double,
+                    height as c_double,
+                );
+            }
+        }
+
+        pub fn restrict_to_version(&self, version: PdfVersion) {
+            unsafe {
+                cairo_sys::cairo_pdf_surface_restrict_to_version(
+                    self.inner.surface.raw,
+                    version.into(),
+                );
+            }
+        }
+
+        pub fn set_metadata(&self, metadata: PdfMetadata, value: &str) {
+            unsafe {
+                let cvalue = CString::new(value).unwrap();
+                cairo_sys::cairo_pdf_surface_set_metadata(
+                    self.inner.surface.raw,
+                    metadata.into(),
+                    cvalue.as_ptr(),
+                );
+            }
+        }
+
+        pub fn set_custom_metadata(&self, name: &str, value: &str) {
+            unsafe {
+                let cname = CString::new(name).unwrap();
+                let cvalue = CString::new(value).unwrap();
+                cairo_sys::cairo_pdf_surface_set_custom_metadata(
+                    self.inner.surface.raw,
+                    cname.as_ptr(),
+                    cvalue.as_ptr(),
+                );
+            }
+        }
+
+        /// Sets the label shown for the current page, e.g. `"iv"`.
+        pub fn set_page_label(&self, label: &str) {
+            unsafe {
+                let clabel = CString::new(label).unwrap();
+                cairo_sys::cairo_pdf_surface_set_page_label(
+                    self.inner.surface.raw,
+                    clabel.as_ptr(),
+                );
+            }
+        }
+
+        /// Adds an item to the document outline and returns its id, to be
+        /// used as `parent` of nested items. `link` uses the attribute
+        /// syntax of link tags, e.g. `"page=3"` or `"dest='chapter-1'"`.
+        pub fn add_outline(
+            &self,
+            parent: i32,
+            name: &str,
+            link: &str,
+            flags: PdfOutlineFlags,
+        ) -> i32 {
+            unsafe {
+                let cname = CString::new(name).unwrap();
+                let clink = CString::new(link).unwrap();
+                cairo_sys::cairo_pdf_surface_add_outline(
+                    self.inner.surface.raw,
+                    parent as c_int,
+                    cname.as_ptr(),
+                    clink.as_ptr(),
+                    flags.0,
+                ) as i32
+            }
+        }
+    }
+
+    #[cfg(feature = "svg")]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum SvgVersion {
+        V1_1,
+        V1_2,
+    }
+
+    #[cfg(feature = "svg")]
+    impl From<SvgVersion> for cairo_sys::cairo_svg_version_t {
+        fn from(version: SvgVersion) -> Self {
+            match version {
+                SvgVersion::V1_1 => cairo_sys::_cairo_svg_version_CAIRO_SVG_VERSION_1_1,
+                SvgVersion::V1_2 => cairo_sys::_cairo_svg_version_CAIRO_SVG_VERSION_1_2,
+            }
+        }
+    }
+
+    /// Unit of the width and height attributes of the SVG document.
+    #[cfg(feature = "svg")]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum SvgUnit {
+        User,
+        Em,
+        Ex,
+        Px,
+        In,
+        Cm,
+        Mm,
+        Pt,
+        Pc,
+        Percent,
+    }
+
+    #[cfg(feature = "svg")]
+    impl From<SvgUnit> for cairo_sys::cairo_svg_unit_t {
+        fn from(unit: SvgUnit) -> Self {
+            match unit {
+                SvgUnit::User => cairo_sys::_cairo_svg_unit_CAIRO_SVG_UNIT_USER,
+                SvgUnit::Em => cairo_sys::_cairo_svg_unit_CAIRO_SVG_UNIT_EM,
+                SvgUnit::Ex => cairo_sys::_cairo_svg_unit_CAIRO_SVG_UNIT_EX,
+                SvgUnit::Px => cairo_sys::_cairo_svg_unit_CAIRO_SVG_UNIT_PX,
+                SvgUnit::In => cairo_sys::_cairo_svg_unit_CAIRO_SVG_UNIT_IN,
+                SvgUnit::Cm => cairo_sys::_cairo_svg_unit_CAIRO_SVG_UNIT_CM,
+                SvgUnit::Mm => cairo_sys::_cairo_svg_unit_CAIRO_SVG_UNIT_MM,
+                SvgUnit::Pt => cairo_sys::_cairo_svg_unit_CAIRO_SVG_UNIT_PT,
+                SvgUnit::Pc => cairo_sys::_cairo_svg_unit_CAIRO_SVG_UNIT_PC,
+                SvgUnit::Percent => cairo_sys::_cairo_svg_unit_CAIRO_SVG_UNIT_PERCENT,
+            }
+        }
+    }
+
+    /// An SVG surface, every page after the first is written as a separate
+    /// `<page>` of an SVG 1.2 page set.
+    #[cfg(feature = "svg")]
+    pub struct SvgSurface {
+        inner: DocumentSurface,
+    }
+
+    #[cfg(feature = "svg")]
+    impl_document_surface!(
+        SvgSurface,
+        cairo_sys::cairo_svg_surface_create,
+        cairo_sys::cairo_svg_surface_create_for_stream
+    );
+
+    #[cfg(feature = "svg")]
+    impl SvgSurface {
+        pub fn restrict_to_version(&self, version: SvgVersion) {
+            unsafe {
+                cairo_sys::cairo_svg_surface_restrict_to_version(
+                    self.inner.surface.raw,
+                    version.into(),
+                );
+            }
+        }
+
+        pub fn set_document_unit(&self, unit: SvgUnit) {
+            unsafe {
+                cairo_sys::cairo_svg_surface_set_document_unit(self.inner.surface.raw, unit.into());
+            }
+        }
+    }
+
+    #[cfg(feature = "ps")]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum PsLevel {
+        Level2,
+        Level3,
+    }
+
+    #[cfg(feature = "ps")]
+    impl From<PsLevel> for cairo_sys::cairo_ps_level_t {
+        fn from(level: PsLevel) -> Self {
+            match level {
+                PsLevel::Level2 => cairo_sys::_cairo_ps_level_CAIRO_PS_LEVEL_2,
+                PsLevel::Level3 => cairo_sys::_cairo_ps_level_CAIRO_PS_LEVEL_3,
+            }
+        }
+    }
+
+    #[cfg(feature = "ps")]
+    pub struct PsSurface {
+        inner: DocumentSurface,
+    }
+
+    #[cfg(feature = "ps")]
+    impl_document_surface!(
+        PsSurface,
+        cairo_sys::cairo_ps_surface_create,
+        cairo_sys::cairo_ps_surface_create_for_stream
+    );
+
+    #[cfg(feature = "ps")]
+    impl PsSurface {
+        /// Changes the size of the next pages, call it before drawing on a page.
+        pub fn set_size(&self, width: f32, height: f32) {
+            unsafe {
+                cairo_sys::cairo_ps_surface_set_size(
+                    self.inner.surface.raw,
+                    width as c_double,
+                    height as c_double,
+                );
+            }
+        }
+
+        pub fn restrict_to_level(&self, level: PsLevel) {
+            unsafe {
+                cairo_sys::cairo_ps_surface_restrict_to_level(self.inner.surface.raw, level.into());
+            }
+        }
+
+        /// Writes Encapsulated PostScript, the document must have a single page.
+        pub fn set_eps(&self, eps: bool) {
+            unsafe {
+                cairo_sys::cairo_ps_surface_set_eps(self.inner.surface.raw, eps as c_int);
+            }
+        }
+
+        pub fn is_eps(&self) -> bool {
+            unsafe { cairo_sys::cairo_ps_surface_get_eps(self.inner.surface.raw) != 0 }
+        }
+
+        /// Adds a DSC comment such as `"%%Title: Report"` to the header, or
+        /// to the setup sections after `dsc_begin_setup`/`dsc_begin_page_setup`.
+        pub fn dsc_comment(&self, comment: &str) {
+            unsafe {
+                let ccomment = CString::new(comment).unwrap();
+                cairo_sys::cairo_ps_surface_dsc_comment(self.inner.surface.raw, ccomment.as_ptr());
+            }
+        }
+
+        pub fn dsc_begin_setup(&self) {
+            unsafe {
+                cairo_sys::cairo_ps_surface_dsc_begin_setup(self.inner.surface.raw);
+            }
+        }
+
+        pub fn dsc_begin_page_setup(&self) {
+            unsafe {
+                cairo_sys::cairo_ps_surface_dsc_begin_page_setup(self.inner.surface.raw);
+            }
+        }
+    }
+
+    /// Options for rendering fonts, applied to Pango with
+    /// `Pango2Context::set_font_options`.
+    pub struct FontOptions {
+        raw: *mut cairo_sys::cairo_font_options_t,
+    }
+
+    impl FontOptions {
+        pub fn new() -> Self {
+            unsafe {
+                Self {
+                    raw: cairo_sys::cairo_font_options_create(),
+                }
+            }
+        }
+
+        /// Copies font options not owned by the caller.
+        pub(crate) unsafe fn copy_from(
+            raw: *const cairo_sys::cairo_font_options_t,
+        ) -> Option<Self> {
+            if raw.is_null() {
+                None
+            } else {
+                Some(Self {
+                    raw: cairo_sys::cairo_font_options_copy(raw),
+                })
+            }
+        }
+
+        pub fn raw(&self) -> &*mut cairo_sys::cairo_font_options_t {
+            &self.raw
+        }
+
+        /// Sets how glyph edges are smoothed. `Antialias::None` gives hard
+        /// one-bit edges, `Antialias::Subpixel` renders per color channel
+        /// in the order given by `set_subpixel_order`.
+        pub fn set_antialias(&self, antialias: Antialias) {
+            unsafe {
+                cairo_sys::cairo_font_options_set_antialias(self.raw, antialias.into());
+            }
+        }
+
+        pub fn antialias(&self) -> Antialias {
+            unsafe { cairo_sys::cairo_font_options_get_antialias(self.raw).into() }
+        }
+
+        /// Sets the order of color elements within a display pixel, only
+        /// used with `Antialias::Subpixel`.
+        pub fn set_subpixel_order(&self, order: SubpixelOrder) {
+            unsafe {
+                cairo_sys::cairo_font_options_set_subpixel_order(self.raw, order.into());
+            }
+        }
+
+        pub fn subpixel_order(&self) -> SubpixelOrder {
+            unsafe { cairo_sys::cairo_font_options_get_subpixel_order(self.raw).into() }
+        }
+
+        /// Sets how strongly outlines are fitted to the pixel grid.
+        /// `HintStyle::None` keeps the designed shapes, which is what print
+        /// output wants; `HintStyle::Full` gives the crispest small text.
+        pub fn set_hint_style(&self, style: HintStyle) {
+            unsafe {
+                cairo_sys::cairo_font_options_set_hint_style(self.raw, style.into());
+            }
+        }
+
+        pub fn hint_style(&self) -> HintStyle {
+            unsafe { cairo_sys::cairo_font_options_get_hint_style(self.raw).into() }
+        }
+
+        /// Sets whether font metrics are rounded to whole device units.
+        /// Rounded metrics keep glyphs on the pixel grid, but advances then
+        /// no longer scale linearly with the font size.
+        pub fn set_hint_metrics(&self, metrics: HintMetrics) {
+            unsafe {
+                cairo_sys::cairo_font_options_set_hint_metrics(self.raw, metrics.into());
+            }
+        }
+
+        pub fn hint_metrics(&self) -> HintMetrics {
+            unsafe { cairo_sys::cairo_font_options_get_hint_metrics(self.raw).into() }
+        }
+
+        /// Sets whether color fonts are drawn with their colors or as
+        /// plain masks in the source color.
+        pub fn set_color_mode(&self, mode: ColorMode) {
+            unsafe {
+                cairo_sys::cairo_font_options_set_color_mode(self.raw, mode.into());
+            }
+        }
+
+        pub fn color_mode(&self) -> ColorMode {
+            unsafe { cairo_sys::cairo_font_options_get_color_mode(self.raw).into() }
+        }
+
+        /// Sets variation axis values for variable fonts, in the form
+        /// `"wght=700,wdth=75"`. `None` clears any variations.
+        pub fn set_variations(&self, variations: Option<&str>) {
+            unsafe {
+                match variations {
+                    Some(variations) => {
+                        let cvariations = CString::new(variations).unwrap();
+                        cairo_sys::cairo_font_options_set_variations(
+                            self.raw,
+                            cvariations.as_ptr(),
+                        );
+                    }
+                    None => cairo_sys::cairo_font_options_set_variations(self.raw, ptr::null()),
+                }
+            }
+        }
+
+        pub fn variations(&self) -> Option<String> {
+            unsafe {
+                let variations = cairo_sys::cairo_font_options_get_variations(self.raw);
+                if variations.is_null() {
+                    None
+                } else {
+                    Some(CStr::from_ptr(variations).to_string_lossy().to_string())
+                }
+            }
+        }
+
+        /// Overrides `self` with the options of `other` that are not set to
+        /// their default value.
+        pub fn merge(&self, other: &FontOptions) {
+            unsafe {
+                cairo_sys::cairo_font_options_merge(self.raw, other.raw);
+            }
+        }
+
+        /// Selects the palette of color fonts by index.
+        pub fn set_color_palette(&self, index: u32) {
+            unsafe {
+                cairo_sys::cairo_font_options_set_color_palette(self.raw, index as c_uint);
+            }
+        }
+
+        pub fn color_palette(&self) -> u32 {
+            unsafe { cairo_sys::cairo_font_options_get_color_palette(self.raw) as u32 }
+        }
+
+        /// Overrides the color of a palette entry, e.g. to recolor an icon
+        /// font. Entries without an override use the selected palette. The
+        /// override applies to all text of a context, to recolor a range use
+        /// `harfbuzz::Face::with_custom_palettes`.
+        pub fn set_custom_palette_color(&self, index: u32, r: f32, g: f32, b: f32, a: f32) {
+            unsafe {
+                cairo_sys::cairo_font_options_set_custom_palette_color(
+                    self.raw,
+                    index as c_uint,
+                    r as c_double,
+                    g as c_double,
+                    b as c_double,
+                    a as c_double,
+                );
+            }
+        }
+
+        pub fn custom_palette_color(&self, index: u32) -> Option<(f32, f32, f32, f32)> {
+            unsafe {
+                let (mut r, mut g, mut b, mut a) = (0.0, 0.0, 0.0, 0.0);
+                let status = cairo_sys::cairo_font_options_get_custom_palette_color(
+                    self.raw,
+                    index as c_uint,
+                    &mut r,
+                    &mut g,
+                    &mut b,
+                    &mut a,
+                );
+                if status == cairo_sys::_cairo_status_CAIRO_STATUS_SUCCESS {
+                    Some((r as f32, g as f32, b as f32, a as f32))
+                } else {
+                    None
+                }
+            }
+        }
+    }
+
+    impl Default for FontOptions {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    impl Clone for FontOptions {
+        fn clone(&self) -> Self {
+            unsafe {
+                Self {
+                    raw: cairo_sys::cairo_font_options_copy(self.raw),
+                }
+            }
+        }
+    }
+
+    impl PartialEq for FontOptions {
+        fn eq(&self, other: &Self) -> bool {
+            unsafe { cairo_sys::cairo_font_options_equal(self.raw, other.raw) != 0 }
+        }
+    }
+
+    impl Drop for FontOptions {
+        fn drop(&mut self) {
+            unsafe {
+                cairo_sys::cairo_font_options_destroy(self.raw);
+            }
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum Antialias {
+        Default,
+        None,
+        Gray,
+        Subpixel,
+        Fast,
+        Good,
+        Best,
+    }
+
+    impl From<Antialias> for cairo_sys::cairo_antialias_t {
+        fn from(antialias: Antialias) -> Self {
+            match antialias {
+                Antialias::Default => cairo_sys::_cairo_antialias_CAIRO_ANTIALIAS_DEFAULT,
+                Antialias::None => cairo_sys::_cairo_antialias_CAIRO_ANTIALIAS_NONE,
+                Antialias::Gray => cairo_sys::_cairo_antialias_CAIRO_ANTIALIAS_GRAY,
+                Antialias::Subpixel => cairo_sys::_cairo_antialias_CAIRO_ANTIALIAS_SUBPIXEL,
+                Antialias::Fast => cairo_sys::_cairo_antialias_CAIRO_ANTIALIAS_FAST,
+                Antialias::Good => cairo_sys::_cairo_antialias_CAIRO_ANTIALIAS_GOOD,
+                Antialias::Best => cairo_sys::_cairo_antialias_CAIRO_ANTIALIAS_BEST,
+            }
+        }
+    }
+
+    impl From<cairo_sys::cairo_antialias_t> for Antialias {
+        fn from(antialias: cairo_sys::cairo_antialias_t) -> Self {
+            match antialias {
+                cairo_sys::_cairo_antialias_CAIRO_ANTIALIAS_NONE => Antialias::None,
+                cairo_sys::_cairo_antialias_CAIRO_ANTIALIAS_GRAY => Antialias::Gray,
+                cairo_sys::_cairo_antialias_CAIRO_ANTIALIAS_SUBPIXEL => Antialias::Subpixel,
+                cairo_sys::_cairo_antialias_CAIRO_ANTIALIAS_FAST => Antialias::Fast,
+                cairo_sys::_cairo_antialias_CAIRO_ANTIALIAS_GOOD => Antialias::Good,
+                cairo_sys::_cairo_antialias_CAIRO_ANTIALIAS_BEST => Antialias::Best,
+                _ => Antialias::Default,
+            }
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum SubpixelOrder {
+        Default,
+        Rgb,
+        Bgr,
+        Vrgb,
+        Vbgr,
+    }
+
+    impl From<SubpixelOrder> for cairo_sys::cairo_subpixel_order_t {
+        fn from(order: SubpixelOrder) -> Self {
+            match order {
+                SubpixelOrder::Default => {
+                    cairo_sys::_cairo_subpixel_order_CAIRO_SUBPIXEL_ORDER_DEFAULT
+                }
+                SubpixelOrder::Rgb => cairo_sys::_cairo_subpixel_order_CAIRO_SUBPIXEL_ORDER_RGB,
+                SubpixelOrder::Bgr => cairo_sys::_cairo_subpixel_order_CAIRO_SUBPIXEL_ORDER_BGR,
+                SubpixelOrder::Vrgb => cairo_sys::_cairo_subpixel_order_CAIRO_SUBPIXEL_ORDER_VRGB,
+                SubpixelOrder::Vbgr => cairo_sys::_cairo_subpixel_order_CAIRO_SUBPIXEL_ORDER_VBGR,
+            }
+        }
+    }
+
+    impl From<cairo_sys::cairo_subpixel_order_t> for SubpixelOrder {
+        fn from(order: cairo_sys::cairo_subpixel_order_t) -> Self {
+            match order {
+                cairo_sys::_cairo_subpixel_order_CAIRO_SUBPIXEL_ORDER_RGB => SubpixelOrder::Rgb,
+                cairo_sys::_cairo_subpixel_order_CAIRO_SUBPIXEL_ORDER_BGR => SubpixelOrder::Bgr,
+                cairo_sys::_cairo_subpixel_order_CAIRO_SUBPIXEL_ORDER_VRGB => SubpixelOrder::Vrgb,
+                cairo_sys::_cairo_subpixel_order_CAIRO_SUBPIXEL_ORDER_VBGR => SubpixelOrder::Vbgr,
+                _ => SubpixelOrder::Default,
+            }
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum HintStyle {
+        Default,
+        None,
+        Slight,
+        Medium,
+        Full,
+    }
+
+    impl From<HintStyle> for cairo_sys::cairo_hint_style_t {
+        fn from(style: HintStyle) -> Self {
+            match style {
+                HintStyle::Default => cairo_sys::_cairo_hint_style_CAIRO_HINT_STYLE_DEFAULT,
+                HintStyle::None => cairo_sys::_cairo_hint_style_CAIRO_HINT_STYLE_NONE,
+                HintStyle::Slight => cairo_sys::_cairo_hint_style_CAIRO_HINT_STYLE_SLIGHT,
+                HintStyle::Medium => cairo_sys::_cairo_hint_style_CAIRO_HINT_STYLE_MEDIUM,
+                HintStyle::Full => cairo_sys::_cairo_hint_style_CAIRO_HINT_STYLE_FULL,
+            }
+        }
+    }
+
+    impl From<cairo_sys::cairo_hint_style_t> for HintStyle {
+        fn from(style: cairo_sys::cairo_hint_style_t) -> Self {
+            match style {
+                cairo_sys::_cairo_hint_style_CAIRO_HINT_STYLE_NONE => HintStyle::None,
+                cairo_sys::_cairo_hint_style_CAIRO_HINT_STYLE_SLIGHT => HintStyle::Slight,
+                cairo_sys::_cairo_hint_style_CAIRO_HINT_STYLE_MEDIUM => HintStyle::Medium,
+                cairo_sys::_cairo_hint_style_CAIRO_HINT_STYLE_FULL => HintStyle::Full,
+                _ => HintStyle::Default,
+            }
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum HintMetrics {
+        Default,
+        Off,
+        On,
+    }
+
+    impl From<HintMetrics> for cairo_sys::cairo_hint_metrics_t {
+        fn from(metrics: HintMetrics) -> Self {
+            match metrics {
+                HintMetrics::Default => cairo_sys::_cairo_hint_metrics_CAIRO_HINT_METRICS_DEFAULT,
+                HintMetrics::Off => cairo_sys::_cairo_hint_metrics_CAIRO_HINT_METRICS_OFF,
+                HintMetrics::On => cairo_sys::_cairo_hint_metrics_CAIRO_HINT_METRICS_ON,
+            }
+        }
+    }
+
+    impl From<cairo_sys::cairo_hint_metrics_t> for HintMetrics {
+        fn from(metrics: cairo_sys::cairo_hint_metrics_t) -> Self {
+            match metrics {
+                cairo_sys::_cairo_hint_metrics_CAIRO_HINT_METRICS_OFF => HintMetrics::Off,
+                cairo_sys::_cairo_hint_metrics_CAIRO_HINT_METRICS_ON => HintMetrics::On,
+                _ => HintMetrics::Default,
+            }
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum ColorMode {
+        Default,
+        NoColor,
+        Color,
+    }
+
+    impl From<ColorMode> for cairo_sys::cairo_color_mode_t {
+        fn from(mode: ColorMode) -> Self {
+            match mode {
+                ColorMode::Default => cairo_sys::_cairo_color_mode_CAIRO_COLOR_MODE_DEFAULT,
+                ColorMode::NoColor => cairo_sys::_cairo_color_mode_CAIRO_COLOR_MODE_NO_COLOR,
+                ColorMode::Color => cairo_sys::_cairo_color_mode_CAIRO_COLOR_MODE_COLOR,
+            }
+        }
+    }
+
+    impl From<cairo_sys::cairo_color_mode_t> for ColorMode {
+        fn from(mode: cairo_sys::cairo_color_mode_t) -> Self {
+            match mode {
+                cairo_sys::_cairo_color_mode_CAIRO_COLOR_MODE_NO_COLOR => ColorMode::NoColor,
+                cairo_sys::_cairo_color_mode_CAIRO_COLOR_MODE_COLOR => ColorMode::Color,
+                _ => ColorMode::Default,
+            }
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum LineCap {
+        Butt,
+        Round,
+        Square,
+    }
+
+    impl From<LineCap> for cairo_sys::cairo_line_cap_t {
+        fn from(cap: LineCap) -> Self {
+            match cap {
+                LineCap::Butt => cairo_sys::_cairo_line_cap_CAIRO_LINE_CAP_BUTT,
+                LineCap::Round => cairo_sys::_cairo_line_cap_CAIRO_LINE_CAP_ROUND,
+                LineCap::Square => cairo_sys::_cairo_line_cap_CAIRO_LINE_CAP_SQUARE,
+            }
+        }
+    }
+
+    impl From<cairo_sys::cairo_line_cap_t> for LineCap {
+        fn from(cap: cairo_sys::cairo_line_cap_t) -> Self {
+            match cap {
+                cairo_sys::_cairo_line_cap_CAIRO_LINE_CAP_ROUND => LineCap::Round,
+                cairo_sys::_cairo_line_cap_CAIRO_LINE_CAP_SQUARE => LineCap::Square,
+                _ => LineCap::Butt,
+            }
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum LineJoin {
+        Miter,
+        Round,
+        Bevel,
+    }
+
+    impl From<LineJoin> for cairo_sys::cairo_line_join_t {
+        fn from(join: LineJoin) -> Self {
+            match join {
+                LineJoin::Miter => cairo_sys::_cairo_line_join_CAIRO_LINE_JOIN_MITER,
+                LineJoin::Round => cairo_sys::_cairo_line_join_CAIRO_LINE_JOIN_ROUND,
+                LineJoin::Bevel => cairo_sys::_cairo_line_join_CAIRO_LINE_JOIN_BEVEL,
+            }
+        }
+    }
+
+    impl From<cairo_sys::cairo_line_join_t> for LineJoin {
+        fn from(join: cairo_sys::cairo_line_join_t) -> Self {
+            match join {
+                cairo_sys::_cairo_line_join_CAIRO_LINE_JOIN_ROUND => LineJoin::Round,
+                cairo_sys::_cairo_line_join_CAIRO_LINE_JOIN_BEVEL => LineJoin::Bevel,
+                _ => LineJoin::Miter,
+            }
+        }
+    }
+
+    /// Decides which areas of a self-intersecting path are inside.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum FillRule {
+        Winding,
+        EvenOdd,
+    }
+
+    impl From<FillRule> for cairo_sys::cairo_fill_rule_t {
+        fn from(rule: FillRule) -> Self {
+            match rule {
+                FillRule::Winding => cairo_sys::_cairo_fill_rule_CAIRO_FILL_RULE_WINDING,
+                FillRule::EvenOdd => cairo_sys::_cairo_fill_rule_CAIRO_FILL_RULE_EVEN_ODD,
+            }
+        }
+    }
+
+    impl From<cairo_sys::cairo_fill_rule_t> for FillRule {
+        fn from(rule: cairo_sys::cairo_fill_rule_t) -> Self {
+            match rule {
+                cairo_sys::_cairo_fill_rule_CAIRO_FILL_RULE_EVEN_ODD => FillRule::EvenOdd,
+                _ => FillRule::Winding,
+            }
+        }
+    }
+
+    /// An affine transformation, `x' = xx * x + xy * y + x0` and
+    /// `y' = yx * x + yy * y + y0`.
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub struct Matrix {
+        pub xx: f32,
+        pub yx: f32,
+        pub xy: f32,
+        pub yy: f32,
+        pub x0: f32,
+        pub y0: f32,
+    }
+
+    impl Matrix {
+        pub const IDENTITY: Matrix = Matrix {
+            xx: 1.0,
+            yx: 0.0,
+            xy: 0.0,
+            yy: 1.0,
+            x0: 0.0,
+            y0: 0.0,
+        };
+    }
+
+    impl From<Matrix> for cairo_sys::cairo_matrix_t {
+        fn from(matrix: Matrix) -> Self {
+            cairo_sys::cairo_matrix_t {
+                xx: matrix.xx as c_double,
+                yx: matrix.yx as c_double,
+                xy: matrix.xy as c_double,
+                yy: matrix.yy as c_double,
+                x0: matrix.x0 as c_double,
+                y0: matrix.y0 as c_double,
+            }
+        }
+    }
+
+    impl From<cairo_sys::cairo_matrix_t> for Matrix {
+        fn from(matrix: cairo_sys::cairo_matrix_t) -> Self {
+            Matrix {
+                xx: matrix.xx as f32,
+                yx: matrix.yx as f32,
+                xy: matrix.xy as f32,
+                yy: matrix.yy as f32,
+                x0: matrix.x0 as f32,
+                y0: matrix.y0 as f32,
+            }
+        }
+    }
+
+    /// How a pattern is continued outside of its natural area.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum Extend {
+        None,
+        Repeat,
+        Reflect,
+        Pad,
+    }
+
+    impl From<Extend> for cairo_sys::cairo_extend_t {
+        fn from(extend: Extend) -> Self {
+            match extend {
+                Extend::None => cairo_sys::_cairo_extend_CAIRO_EXTEND_NONE,
+                Extend::Repeat => cairo_sys::_cairo_extend_CAIRO_EXTEND_REPEAT,
+                Extend::Reflect => cairo_sys::_cairo_extend_CAIRO_EXTEND_REFLECT,
+                Extend::Pad => cairo_sys::_cairo_extend_CAIRO_EXTEND_PAD,
+            }
+        }
+    }
+
+    impl From<cairo_sys::cairo_extend_t> for Extend {
+        fn from(extend: cairo_sys::cairo_extend_t) -> Self {
+            match extend {
+                cairo_sys::_cairo_extend_CAIRO_EXTEND_REPEAT => Extend::Repeat,
+                cairo_sys::_cairo_extend_CAIRO_EXTEND_REFLECT => Extend::Reflect,
+                cairo_sys::_cairo_extend_CAIRO_EXTEND_PAD => Extend::Pad,
+                _ => Extend::None,
+            }
+        }
+    }
+
+    /// How surface patterns are sampled when they are scaled or transformed.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum Filter {
+        Fast,
+        Good,
+        Best,
+        Nearest,
+        Bilinear,
+        Gaussian,
+    }
+
+    impl From<Filter> for cairo_sys::cairo_filter_t {
+        fn from(filter: Filter) -> Self {
+            match filter {
+                Filter::Fast => cairo_sys::_cairo_filter_CAIRO_FILTER_FAST,
+                Filter::Good => cairo_sys::_cairo_filter_CAIRO_FILTER_GOOD,
+                Filter::Best => cairo_sys::_cairo_filter_CAIRO_FILTER_BEST,
+                Filter::Nearest => cairo_sys::_cairo_filter_CAIRO_FILTER_NEAREST,
+                Filter::Bilinear => cairo_sys::_cairo_filter_CAIRO_FILTER_BILINEAR,
+                Filter::Gaussian => cairo_sys::_cairo_filter_CAIRO_FILTER_GAUSSIAN,
+            }
+        }
+    }
+
+    impl From<cairo_sys::cairo_filter_t> for Filter {
+        fn from(filter: cairo_sys::cairo_filter_t) -> Self {
+            match filter {
+                cairo_sys::_cairo_filter_CAIRO_FILTER_FAST => Filter::Fast,
+                cairo_sys::_cairo_filter_CAIRO_FILTER_BEST => Filter::Best,
+                cairo_sys::_cairo_filter_CAIRO_FILTER_NEAREST => Filter::Nearest,
+                cairo_sys::_cairo_filter_CAIRO_FILTER_BILINEAR => Filter::Bilinear,
+                cairo_sys::_cairo_filter_CAIRO_FILTER_GAUSSIAN => Filter::Gaussian,
+                _ => Filter::Good,
+            }
+        }
+    }
+
+    /// How drawing operations combine the source with the destination.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum Operator {
+        Clear,
+        Source,
+        Over,
+        In,
+        Out,
+        Atop,
+        Dest,
+        DestOver,
+        DestIn,
+        DestOut,
+        DestAtop,
+        Xor,
+        Add,
+        Saturate,
+        Multiply,
+        Screen,
+        Overlay,
+        Darken,
+        Lighten,
+        ColorDodge,
+        ColorBurn,
+        HardLight,
+        SoftLight,
+        Difference,
+        Exclusion,
+        HslHue,
+        HslSaturation,
+        HslColor,
+        HslLuminosity,
+    }
+
+    impl From<Operator> for cairo_sys::cairo_operator_t {
+        fn from(operator: Operator) -> Self {
+            match operator {
+                Operator::Clear => cairo_sys::_cairo_operator_CAIRO_OPERATOR_CLEAR,
+                Operator::Source => cairo_sys::_cairo_operator_CAIRO_OPERATOR_SOURCE,
+                Operator::Over => cairo_sys::_cairo_operator_CAIRO_OPERATOR_OVER,
+                Operator::In => cairo_sys::_cairo_operator_CAIRO_OPERATOR_IN,
+                Operator::Out => cairo_sys::_cairo_operator_CAIRO_OPERATOR_OUT,
+                Operator::Atop => cairo_sys::_cairo_operator_CAIRO_OPERATOR_ATOP,
+                Operator::Dest => cairo_sys::_cairo_operator_CAIRO_OPERATOR_DEST,
+                Operator::DestOver => cairo_sys::_cairo_operator_CAIRO_OPERATOR_DEST_OVER,
+                Operator::DestIn => cairo_sys::_cairo_operator_CAIRO_OPERATOR_DEST_IN,
+                Operator::DestOut => cairo_sys::_cairo_operator_CAIRO_OPERATOR_DEST_OUT,
+                Operator::DestAtop => cairo_sys::_cairo_operator_CAIRO_OPERATOR_DEST_ATOP,
+                Operator::Xor => cairo_sys::_cairo_operator_CAIRO_OPERATOR_XOR,
+                Operator::Add => cairo_sys::_cairo_operator_CAIRO_OPERATOR_ADD,
+                Operator::Saturate => cairo_sys::_cairo_operator_CAIRO_OPERATOR_SATURATE,
+                Operator::Multiply => cairo_sys::_cairo_operator_CAIRO_OPERATOR_MULTIPLY,
+                Operator::Screen => cairo_sys::_cairo_operator_CAIRO_OPERATOR_SCREEN,
+                Operator::Overlay => cairo_sys::_cairo_operator_CAIRO_OPERATOR_OVERLAY,
+                Operator::Darken => cairo_sys::_cairo_operator_CAIRO_OPERATOR_DARKEN,
+                Operator::Lighten => cairo_sys::_cairo_operator_CAIRO_OPERATOR_LIGHTEN,
+                Operator::ColorDodge => cairo_sys::_cairo_operator_CAIRO_OPERATOR_COLOR_DODGE,
+                Operator::ColorBurn => cairo_sys::_cairo_operator_CAIRO_OPERATOR_COLOR_BURN,
+                Operator::HardLight => cairo_sys::_cairo_operator_CAIRO_OPERATOR_HARD_LIGHT,
+                Operator::SoftLight => cairo_sys::_cairo_operator_CAIRO_OPERATOR_SOFT_LIGHT,
+                Operator::Difference => cairo_sys::_cairo_operator_CAIRO_OPERATOR_DIFFERENCE,
+                Operator::Exclusion => cairo_sys::_cairo_operator_CAIRO_OPERATOR_EXCLUSION,
+                Operator::HslHue => cairo_sys::_cairo_operator_CAIRO_OPERATOR_HSL_HUE,
+                Operator::HslSaturation => cairo_sys::_cairo_operator_CAIRO_OPERATOR_HSL_SATURATION,
+                Operator::HslColor => cairo_sys::_cairo_operator_CAIRO_OPERATOR_HSL_COLOR,
+                Operator::HslLuminosity => cairo_sys::_cairo_operator_CAIRO_OPERATOR_HSL_LUMINOSITY,
+            }
+        }
+    }
+
+    impl From<cairo_sys::cairo_operator_t> for Operator {
+        fn from(operator: cairo_sys::cairo_operator_t) -> Self {
+            match operator {
+                cairo_sys::_cairo_operator_CAIRO_OPERATOR_CLEAR => Operator::Clear,
+                cairo_sys::_cairo_operator_CAIRO_OPERATOR_SOURCE => Operator::Source,
+                cairo_sys::_cairo_operator_CAIRO_OPERATOR_IN => Operator::In,
+                cairo_sys::_cairo_operator_CAIRO_OPERATOR_OUT => Operator::Out,
+                cairo_sys::_cairo_operator_CAIRO_OPERATOR_ATOP => Operator::Atop,
+                cairo_sys::_cairo_operator_CAIRO_OPERATOR_DEST => Operator::Dest,
+                cairo_sys::_cairo_operator_CAIRO_OPERATOR_DEST_OVER => Operator::DestOver,
+                cairo_sys::_cairo_operator_CAIRO_OPERATOR_DEST_IN => Operator::DestIn,
+                cairo_sys::_cairo_operator_CAIRO_OPERATOR_DEST_OUT => Operator::DestOut,
+                cairo_sys::_cairo_operator_CAIRO_OPERATOR_DEST_ATOP => Operator::DestAtop,
+                cairo_sys::_cairo_operator_CAIRO_OPERATOR_XOR => Operator::Xor,
+                cairo_sys::_cairo_operator_CAIRO_OPERATOR_ADD => Operator::Add,
+                cairo_sys::_cairo_operator_CAIRO_OPERATOR_SATURATE => Operator::Saturate,
+                cairo_sys::_cairo_operator_CAIRO_OPERATOR_MULTIPLY => Operator::Multiply,
+                cairo_sys::_cairo_operator_CAIRO_OPERATOR_SCREEN => Operator::Screen,
+                cairo_sys::_cairo_operator_CAIRO_OPERATOR_OVERLAY => Operator::Overlay,
+                cairo_sys::_cairo_operator_CAIRO_OPERATOR_DARKEN => Operator::Darken,
+                cairo_sys::_cairo_operator_CAIRO_OPERATOR_LIGHTEN => Operator::Lighten,
+                cairo_sys::_cairo_operator_CAIRO_OPERATOR_COLOR_DODGE => Operator::ColorDodge,
+                cairo_sys::_cairo_operator_CAIRO_OPERATOR_COLOR_BURN => Operator::ColorBurn,
+                cairo_sys::_cairo_operator_CAIRO_OPERATOR_HARD_LIGHT => Operator::HardLight,
+                cairo_sys::_cairo_operator_CAIRO_OPERATOR_SOFT_LIGHT => Operator::SoftLight,
+                cairo_sys::_cairo_operator_CAIRO_OPERATOR_DIFFERENCE => Operator::Difference,
+                cairo_sys::_cairo_operator_CAIRO_OPERATOR_EXCLUSION => Operator::Exclusion,
+                cairo_sys::_cairo_operator_CAIRO_OPERATOR_HSL_HUE => Operator::HslHue,
+                cairo_sys::_cairo_operator_CAIRO_OPERATOR_HSL_SATURATION => Operator::HslSaturation,
+                cairo_sys::_cairo_operator_CAIRO_OPERATOR_HSL_COLOR => Operator::HslColor,
+                cairo_sys::_cairo_operator_CAIRO_OPERATOR_HSL_LUMINOSITY => Operator::HslLuminosity,
+                _ => Operator::Over,
+            }
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum PatternType {
+        Solid,
+        Surface,
+        Linear,
+        Radial,
+        Mesh,
+        RasterSource,
+    }
+
+    impl From<cairo_sys::cairo_pattern_type_t> for PatternType {
+        fn from(pattern_type: cairo_sys::cairo_pattern_type_t) -> Self {
+            match pattern_type {
+                cairo_sys::_cairo_pattern_type_CAIRO_PATTERN_TYPE_SURFACE => PatternType::Surface,
+                cairo_sys::_cairo_pattern_type_CAIRO_PATTERN_TYPE_LINEAR => PatternType::Linear,
+                cairo_sys::_cairo_pattern_type_CAIRO_PATTERN_TYPE_RADIAL => PatternType::Radial,
+                cairo_sys::_cairo_pattern_type_CAIRO_PATTERN_TYPE_MESH => PatternType::Mesh,
+                cairo_sys::_cairo_pattern_type_CAIRO_PATTERN_TYPE_RASTER_SOURCE => {
+                    PatternType::RasterSource
+                }
+                _ => PatternType::Solid,
+            }
+        }
+    }
+
+    /// A source or mask for drawing operations: a solid color, a gradient,
+    /// a mesh or a surface. Clones share the same pattern.
+    pub struct Pattern {
+        raw: *mut cairo_sys::cairo_pattern_t,
+    }
+
+    impl Pattern {
+        pub fn rgb(r: f32, g: f32, b: f32) -> Self {
+            unsafe {
+                Self {
+                    raw: cairo_sys::cairo_pattern_create_rgb(
+                        r as c_double,
+                        g as c_double,
+                        b as c_double,
+                    ),
+                }
+            }
+        }
+
+        pub fn rgba(r: f32, g: f32, b: f32, a: f32) -> Self {
+            unsafe {
+                Self {
+                    raw: cairo_sys::cairo_pattern_create_rgba(
+                        r as c_double,
+                        g as c_double,
+                        b as c_double,
+                        a as c_double,
+                    ),
+                }
+            }
+        }
+
+        /// A gradient along the line from `(x0, y0)` to `(x1, y1)`, add the
+        /// colors with [`Pattern::add_color_stop_rgba`].
+        pub fn linear(x0: f32, y0: f32, x1: f32, y1: f32) -> Self {
+            unsafe {
+                Self {
+                    raw: cairo_sys::cairo_pattern_create_linear(
+                        x0 as c_double,
+                        y0 as c_double,
+                        x1 as c_double,
+                        y1 as c_double,
+                    ),
+                }
+            }
+        }
+
+        /// A gradient between the circle at `(cx0, cy0)` with radius `r0`
+        /// and the circle at `(cx1, cy1)` with radius `r1`.
+        pub fn radial(cx0: f32, cy0: f32, r0: f32, cx1: f32, cy1: f32, r1: f32) -> Self {
+            unsafe {
+                Self {
+                    raw: cairo_sys::cairo_pattern_create_radial(
+                        cx0 as c_double,
+                        cy0 as c_double,
+                        r0 as c_double,
+                        cx1 as c_double,
+                        cy1 as c_double,
+                        r1 as c_double,
+                    ),
+                }
+            }
+        }
+
+        /// An empty mesh, patches are added between
+        /// [`Pattern::begin_patch`] and [`Pattern::end_patch`].
+        pub fn mesh() -> Self {
+            unsafe {
+                Self {
+                    raw: cairo_sys::cairo_pattern_create_mesh(),
+                }
+            }
+        }
+
+        pub fn for_surface(surface: &CairoSurface) -> Self {
+            unsafe {
+                Self {
+                    raw: cairo_sys::cairo_pattern_create_for_surface(surface.raw),
+                }
+            }
+        }
+
+        /// Takes ownership of a pattern reference.
+        pub(crate) unsafe fn from_raw(raw: *mut cairo_sys::cairo_pattern_t) -> Self {
+            Self { raw }
+        }
+
+        pub fn raw(&self) -> &*mut cairo_sys::cairo_pattern_t {
+            &self.raw
+        }
+
+        pub fn pattern_type(&self) -> PatternType {
+            unsafe { cairo_sys::cairo_pattern_get_type(self.raw).into() }
+        }
+
+        /// Adds a color at `offset` between 0 and 1 along a linear or radial
+        /// gradient.
+        pub fn add_color_stop_rgb(&self, offset: f32, r: f32, g: f32, b: f32) {
+            unsafe {
+                cairo_sys::cairo_pattern_add_color_stop_rgb(
+                    self.raw,
+                    offset as c_double,
+                    r as c_double,
+                    g as c_double,
+                    b as c_double,
+                );
+            }
+        }
+
+        pub fn add_color_stop_rgba(&self, offset: f32, r: f32, g: f32, b: f32, a: f32) {
+            unsafe {
+                cairo_sys::cairo_pattern_add_color_stop_rgba(
+                    self.raw,
+                    offset as c_double,
+                    r as c_double,
+                    g as c_double,
+                    b as c_double,
+                    a as c_double,
+                );
+            }
+        }
+
+        pub fn color_stop_count(&self) -> usize {
+            unsafe {
+                let mut count = 0;
+                cairo_sys::cairo_pattern_get_color_stop_count(self.raw, &mut count);
+                count as usize
+            }
+        }
+
+        pub fn begin_patch(&self) {
+            unsafe {
+                cairo_sys::cairo_mesh_pattern_begin_patch(self.raw);
+            }
+        }
+
+        pub fn end_patch(&self) {
+            unsafe {
+                cairo_sys::cairo_mesh_pattern_end_patch(self.raw);
+            }
+        }
+
+        pub fn mesh_move_to(&self, x: f32, y: f32) {
+            unsafe {
+                cairo_sys::cairo_mesh_pattern_move_to(self.raw, x as c_double, y as c_double);
+            }
+        }
+
+        pub fn mesh_line_to(&self, x: f32, y: f32) {
+            unsafe {
+                cairo_sys::cairo_mesh_pattern_line_to(self.raw, x as c_double, y as c_double);
+            }
+        }
+
+        pub fn mesh_curve_to(&self, x1: f32, y1: f32, x2: f32, y2: f32, x3: f32, y3: f32) {
+            unsafe {
+                cairo_sys::cairo_mesh_pattern_curve_to(
+                    self.raw,
+                    x1 as c_double,
+                    y1 as c_double,
+                    x2 as c_double,
+                    y2 as c_double,
+                    x3 as c_double,
+                    y3 as c_double,
+                );
+            }
+        }
+
+        /// Sets one of the four control points of the current patch.
+        pub fn set_control_point(&self, point: u32, x: f32, y: f32) {
+            unsafe {
+                cairo_sys::cairo_mesh_pattern_set_control_point(
+                    self.raw,
+                    point as c_uint,
+                    x as c_double,
+                    y as c_double,
+                );
+            }
+        }
+
+        /// Sets the color of one of the four corners of the current patch.
+        pub fn set_corner_color_rgba(&self, corner: u32, r: f32, g: f32, b: f32, a: f32) {
+            unsafe {
+                cairo_sys::cairo_mesh_pattern_set_corner_color_rgba(
+                    self.raw,
+                    corner as c_uint,
+                    r as c_double,
+                    g as c_double,
+                    b as c_double,
+                    a as c_double,
+                );
+            }
+        }
+
+        pub fn set_extend(&self, extend: Extend) {
+            unsafe {
+                cairo_sys::cairo_pattern_set_extend(self.raw, extend.into());
+            }
+        }
+
+        pub fn extend(&self) -> Extend {
+            unsafe { cairo_sys::cairo_pattern_get_extend(self.raw).into() }
+        }
+
+        pub fn set_filter(&self, filter: Filter) {
+            unsafe {
+                cairo_sys::cairo_pattern_set_filter(self.raw, filter.into());
+            }
+        }
+
+        pub fn filter(&self) -> Filter {
+            unsafe { cairo_sys::cairo_pattern_get_filter(self.raw).into() }
+        }
+
+        /// Sets the transformation from user space to pattern space, e.g. a
+        /// scale of 2 shows a surface pattern at half its size.
+        pub fn set_matrix(&self, matrix: &Matrix) {
+            unsafe {
+                let matrix = (*matrix).into();
+                cairo_sys::cairo_pattern_set_matrix(self.raw, &matrix);
+            }
+        }
+
+        pub fn matrix(&self) -> Matrix {
+            unsafe {
+                let mut matrix = Matrix::IDENTITY.into();
+                cairo_sys::cairo_pattern_get_matrix(self.raw, &mut matrix);
+                matrix.into()
+            }
+        }
+    }
+
+    impl Clone for Pattern {
+        fn clone(&self) -> Self {
+            unsafe {
+                Self {
+                    raw: cairo_sys::cairo_pattern_reference(self.raw),
+                }
+            }
+        }
+    }
+
+    impl Drop for Pattern {
+        fn drop(&mut self) {
+            unsafe {
+                cairo_sys::cairo_pattern_destroy(self.raw);
+            }
+        }
+    }
+
+    pub struct CairoContext {
+        raw: *mut cairo_sys::cairo_t,
+    }
+
+    impl CairoContext {
+        pub fn create(surface: &CairoSurface) -> Self {
+            unsafe {
+                Self {
+                    raw: cairo_sys::cairo_create(surface.raw),
+                }
+            }
+        }
+
+        pub fn raw(&self) -> &*mut cairo_sys::cairo_t {
+            &self.raw
+        }
+
+        pub fn set_source_rgb(&self, r: f32, g: f32, b: f32) {
+            unsafe {
+                cairo_sys::cairo_set_source_rgb(
+                    self.raw,
+                    r as c_double,
+                    g as c_double,
+                    b as c_double,
+                );
+            }
+        }
+
+        pub fn set_source_rgba(&self, r: f32, g: f32, b: f32, a: f32) {
+            unsafe {
+                cairo_sys::cairo_set_source_rgba(
+                    self.raw,
+                    r as c_double,
+                    g as c_double,
+                    b as c_double,
+                    a as c_double,
+                );
+            }
+        }
+
+        pub fn paint(&self) {
+            unsafe {
+                cairo_sys::cairo_paint(self.raw);
+            }
+        }
+
+        pub fn paint_with_alpha(&self, alpha: f32) {
+            unsafe {
+                cairo_sys::cairo_paint_with_alpha(self.raw, alpha as c_double);
+            }
+        }
+
+        pub fn set_operator(&self, operator: Operator) {
+            unsafe {
+                cairo_sys::cairo_set_operator(self.raw, operator.into());
+            }
+        }
+
+        pub fn operator(&self) -> Operator {
+            unsafe { cairo_sys::cairo_get_operator(self.raw).into() }
+        }
+
+        /// Sets the pattern used by drawing operations, a gradient source
+        /// fills text drawn with [`Pango2Layout::paint`](crate::pango2::Pango2Layout::paint).
+        pub fn set_source(&self, source: &Pattern) {
+            unsafe {
+                cairo_sys::cairo_set_source(self.raw, source.raw);
+            }
+        }
+
+        pub fn source(&self) -> Pattern {
+            unsafe {
+                Pattern::from_raw(cairo_sys::cairo_pattern_reference(
+                    cairo_sys::cairo_get_source(self.raw),
+                ))
+            }
+        }
+
+        /// Paints the source using the alpha channel of the pattern as mask.
+        pub fn mask(&self, pattern: &Pattern) {
+            unsafe {
+                cairo_sys::cairo_mask(self.raw, pattern.raw);
+            }
+        }
+
+        /// Uses the surface as source with its origin at `x`, `y` in user
+        /// space.
+        pub fn set_source_surface(&self, surface: &CairoSurface, x: f32, y: f32) {
+            unsafe {
+                cairo_sys::cairo_set_source_surface(
+                    self.raw,
+                    surface.raw,
+                    x as c_double,
+                    y as c_double,
+                );
+            }
+        }
+
+        pub fn mask_surface(&self, surface: &CairoSurface, x: f32, y: f32) {
+            unsafe {
+                cairo_sys::cairo_mask_surface(self.raw, surface.raw, x as c_double, y as c_double);
+            }
+        }
+
+        pub fn save(&self) {
+            unsafe {
+                cairo_sys::cairo_save(self.raw);
+            }
+        }
+
+        pub fn restore(&self) {
+            unsafe {
+                cairo_sys::cairo_restore(self.raw);
+            }
+        }
+
+        pub fn translate(&self, tx: f32, ty: f32) {
+            unsafe {
+                cairo_sys::cairo_translate(self.raw, tx as c_double, ty as c_double);
+            }
+        }
+
+        /// Rotates the user space by the angle in radians, positive angles
+        /// rotate from the positive x axis towards the positive y axis.
+        pub fn rotate(&self, angle: f32) {
+            unsafe {
+                cairo_sys::cairo_rotate(self.raw, angle as c_double);
+            }
+        }
+
+        pub fn scale(&self, sx: f32, sy: f32) {
+            unsafe {
+                cairo_sys::cairo_scale(self.raw, sx as c_double, sy as c_double);
+            }
+        }
+
+        /// Applies the transformation on top of the current one.
+        pub fn transform(&self, matrix: &Matrix) {
+            unsafe {
+                let matrix = (*matrix).into();
+                cairo_sys::cairo_transform(self.raw, &matrix);
+            }
+        }
+
+        /// Replaces the current transformation.
+        pub fn set_matrix(&self, matrix: &Matrix) {
+            unsafe {
+                let matrix = (*matrix).into();
+                cairo_sys::cairo_set_matrix(self.raw, &matrix);
+            }
+        }
+
+        pub fn matrix(&self) -> Matrix {
+            unsafe {
+                let mut matrix = Matrix::IDENTITY.into();
+                cairo_sys::cairo_get_matrix(self.raw, &mut matrix);
+                matrix.into()
+            }
+        }
+
+        pub fn identity_matrix(&self) {
+            unsafe {
+                cairo_sys::cairo_identity_matrix(self.raw);
+            }
+        }
+
+        pub fn move_to(&self, x: f32, y: f32) {
+            unsafe {
+                cairo_sys::cairo_move_to(self.raw, x as c_double, y as c_double);
+            }
+        }
+
+        pub fn rel_move_to(&self, dx: f32, dy: f32) {
+            unsafe {
+                cairo_sys::cairo_rel_move_to(self.raw, dx as c_double, dy as c_double);
+            }
+        }
+
+        pub fn line_to(&self, x: f32, y: f32) {
+            unsafe {
+                cairo_sys::cairo_line_to(self.raw, x as c_double, y as c_double);
+            }
+        }
+
+        pub fn rel_line_to(&self, dx: f32, dy: f32) {
+            unsafe {
+                cairo_sys::cairo_rel_line_to(self.raw, dx as c_double, dy as c_double);
+            }
+        }
+
+        /// Adds a cubic Bézier spline from the current point to `(x3, y3)`.
+        pub fn curve_to(&self, x1: f32, y1: f32, x2: f32, y2: f32, x3: f32, y3: f32) {
+            unsafe {
+                cairo_sys::cairo_curve_to(
+                    self.raw,
+                    x1 as c_double,
+                    y1 as c_double,
+                    x2 as c_double,
+                    y2 as c_double,
+                    x3 as c_double,
+                    y3 as c_double,
+                );
+            }
+        }
+
+        /// Adds a circular arc in the direction of increasing angles, angles
+        /// are in radians.
+        pub fn arc(&self, xc: f32, yc: f32, radius: f32, angle1: f32, angle2: f32) {
+            unsafe {
+                cairo_sys::cairo_arc(
+                    self.raw,
+                    xc as c_double,
+                    yc as c_double,
+                    radius as c_double,
+                    angle1 as c_double,
+                    angle2 as c_double,
+                );
+            }
+        }
+
+        /// Adds a circular arc in the direction of decreasing angles.
+        pub fn arc_negative(&self, xc: f32, yc: f32, radius: f32, angle1: f32, angle2: f32) {
+            unsafe {
+                cairo_sys::cairo_arc_negative(
+                    self.raw,
+                    xc as c_double,
+                    yc as c_double,
+                    radius as c_double,
+                    angle1 as c_double,
+                    angle2 as c_double,
+                );
+            }
+        }
+
+        pub fn rectangle(&self, x: f32, y: f32, width: f32, height: f32) {
+            unsafe {
+                cairo_sys::cairo_rectangle(
+                    self.raw,
+                    x as c_double,
+                    y as c_double,
+                    width as c_double,
+                    height as c_double,
+                );
+            }
+        }
+
+        pub fn close_path(&self) {
+            unsafe {
+                cairo_sys::cairo_close_path(self.raw);
+            }
+        }
+
+        pub fn current_point(&self) -> Option<(f32, f32)> {
+            unsafe {
+                if cairo_sys::cairo_has_current_point(self.raw) == 0 {
+                    return None;
+                }
+                let (mut x, mut y) = (0.0, 0.0);
+                cairo_sys::cairo_get_current_point(self.raw, &mut x, &mut y);
+                Some((x as f32, y as f32))
+            }
+        }
+
+        /// Fills the current path and clears it.
+        pub fn fill(&self) {
+            unsafe {
+                cairo_sys::cairo_fill(self.raw);
+            }
+        }
+
+        pub fn fill_preserve(&self) {
+            unsafe {
+                cairo_sys::cairo_fill_preserve(self.raw);
+            }
+        }
+
+        /// Strokes the current path and clears it.
+        pub fn stroke(&self) {
+            unsafe {
+                cairo_sys::cairo_stroke(self.raw);
+            }
+        }
+
+        pub fn stroke_preserve(&self) {
+            unsafe {
+                cairo_sys::cairo_stroke_preserve(self.raw);
+            }
+        }
+
+        pub fn set_fill_rule(&self, fill_rule: FillRule) {
+            unsafe {
+                cairo_sys::cairo_set_fill_rule(self.raw, fill_rule.into());
+            }
+        }
+
+        pub fn fill_rule(&self) -> FillRule {
+            unsafe { cairo_sys::cairo_get_fill_rule(self.raw).into() }
+        }
+
+        pub fn set_line_width(&self, width: f32) {
+            unsafe {
+                cairo_sys::cairo_set_line_width(self.raw, width as c_double);
+            }
+        }
+
+        pub fn line_width(&self) -> f32 {
+            unsafe { cairo_sys::cairo_get_line_width(self.raw) as f32 }
+        }
+
+        pub fn set_line_cap(&self, line_cap: LineCap) {
+            unsafe {
+                cairo_sys::cairo_set_line_cap(self.raw, line_cap.into());
+            }
+        }
+
+        pub fn line_cap(&self) -> LineCap {
+            unsafe { cairo_sys::cairo_get_line_cap(self.raw).into() }
+        }
+
+        pub fn set_line_join(&self, line_join: LineJoin) {
+            unsafe {
+                cairo_sys::cairo_set_line_join(self.raw, line_join.into());
+            }
+        }
+
+        pub fn line_join(&self) -> LineJoin {
+            unsafe { cairo_sys::cairo_get_line_join(self.raw).into() }
+        }
+
+        /// Sets alternating on and off lengths for strokes, an empty slice
+        /// disables dashing.
+        pub fn set_dash(&self, dashes: &[f32], offset: f32) {
+            let dashes: Vec<c_double> = dashes.iter().map(|dash| *dash as c_double).collect();
+            unsafe {
+                cairo_sys::cairo_set_dash(
+                    self.raw,
+                    dashes.as_ptr(),
+                    dashes.len() as c_int,
+                    offset as c_double,
+                );
+            }
+        }
+
+        /// Intersects the clip with the current path and clears the path.
+        pub fn clip(&self) {
+            unsafe {
+                cairo_sys::cairo_clip(self.raw);
+            }
+        }
+
+        pub fn clip_preserve(&self) {
+            unsafe {
+                cairo_sys::cairo_clip_preserve(self.raw);
+            }
+        }
+
+        pub fn reset_clip(&self) {
+            unsafe {
+                cairo_sys::cairo_reset_clip(self.raw);
+            }
+        }
+
+        /// Redirects drawing to an intermediate surface until the matching
+        /// [`CairoContext::pop_group_to_source`].
+        pub fn push_group(&self) {
+            unsafe {
+                cairo_sys::cairo_push_group(self.raw);
+            }
+        }
+
+        /// Ends the group and makes its content the source, e.g. to paint it
+        /// with [`CairoContext::paint_with_alpha`].
+        pub fn pop_group_to_source(&self) {
+            unsafe {
+                cairo_sys::cairo_pop_group_to_source(self.raw);
+            }
+        }
+
+        /// Starts a tagged region, e.g. a link with `tag_begin(TAG_LINK,
+        /// "uri='https://example.org'")` or a link destination with
+        /// `tag_begin(TAG_DEST, "name='chapter-1'")`. Tags are used by PDF
+        /// surfaces and ignored by other surfaces.
+        pub fn tag_begin(&self, tag_name: &str, attributes: &str) {
+            unsafe {
+                let ctag_name = CString::new(tag_name).unwrap();
+                let cattributes = CString::new(attributes).unwrap();
+                cairo_sys::cairo_tag_begin(self.raw, ctag_name.as_ptr(), cattributes.as_ptr());
+            }
+        }
+
+        pub fn tag_end(&self, tag_name: &str) {
+            unsafe {
+                let ctag_name = CString::new(tag_name).unwrap();
+                cairo_sys::cairo_tag_end(self.raw, ctag_name.as_ptr());
+            }
+        }
+
+        /// Ends the group and returns its content as a pattern.
+        pub fn pop_group(&self) -> Pattern {
+            unsafe { Pattern::from_raw(cairo_sys::cairo_pop_group(self.raw)) }
+        }
+
+        /// Clears the current path.
+        pub fn new_path(&self) {
+            unsafe {
+                cairo_sys::cairo_new_path(self.raw);
+            }
+        }
+
+        /// Returns a copy of the current path.
+        pub fn copy_path(&self) -> Result<Vec<PathSegment>, CairoError> {
+            unsafe { Self::take_path(cairo_sys::cairo_copy_path(self.raw)) }
+        }
+
+        /// Returns a copy of the current path with curves approximated by
+        /// line segments, within the tolerance of the context.
+        pub fn copy_path_flat(&self) -> Result<Vec<PathSegment>, CairoError> {
+            unsafe { Self::take_path(cairo_sys::cairo_copy_path_flat(self.raw)) }
+        }
+
+        unsafe fn take_path(
+            path: *mut cairo_sys::cairo_path_t,
+        ) -> Result<Vec<PathSegment>, CairoError> {
+            if (*path).status != cairo_sys::_cairo_status_CAIRO_STATUS_SUCCESS {
+                cairo_sys::cairo_path_destroy(path);
+                return Err(CairoError::PathError);
+            }
+            let data = if (*path).data.is_null() {
+                &[]
+            } else {
+                std::slice::from_raw_parts((*path).data, (*path).num_data as usize)
+            };
+            let point = |i: usize| (data[i].point.x as f32, data[i].point.y as f32);
+            let mut segments = Vec::new();
+            let mut i = 0;
+            // each segment is a header followed by its points
+            while i < data.len() {
+                let header = data[i].header;
+                segments.push(match header.type_ {
+                    cairo_sys::_cairo_path_data_type_CAIRO_PATH_MOVE_TO => {
+                        let (x, y) = point(i + 1);
+                        PathSegment::MoveTo(x, y)
+                    }
+                    cairo_sys::_cairo_path_data_type_CAIRO_PATH_LINE_TO => {
+                        let (x, y) = point(i + 1);
+                        PathSegment::LineTo(x, y)
+                    }
+                    cairo_sys::_cairo_path_data_type_CAIRO_PATH_CURVE_TO => {
+                        let (x1, y1) = point(i + 1);
+                        let (x2, y2) = point(i + 2);
+                        let (x3, y3) = point(i + 3);
+                        PathSegment::CurveTo(x1, y1, x2, y2, x3, y3)
+                    }
+                    _ => PathSegment::ClosePath,
+                });
+                i += header.length as usize;
+            }
+            cairo_sys::cairo_path_destroy(path);
+            Ok(segments)
+        }
+    }
+
+    impl Drop for CairoContext {
+        fn drop(&mut self) {
+            unsafe {
+                cairo_sys::cairo_destroy(self.raw);
+            }
+        }
+    }
+}
+
+/// Rasterizes glyphs into texture pages for drawing text as textured quads.
+///
+/// Glyphs are drawn with cairo at a fixed pixel size and packed into square
+/// pages of shelves, every glyph keeps a transparent border of [`PADDING`]
+/// pixels so linear filtering does not bleed into its neighbours.
+pub mod atlas {
+    use super::cairo::{self, CairoContext, CairoError, CairoSurface, Format, Pattern};
+    use super::harfbuzz::{self, ColorLine, GlyphPainter, OutlineSink, PaintExtend};
+    use super::pango2;
+    use super::sdf::SdfGenerator;
+    use std::collections::{HashMap, HashSet};
+
+    /// Font units per pixel, the font is scaled to `size * UNITS_PER_PIXEL`
+    /// so outlines keep their precision.
+    pub const UNITS_PER_PIXEL: f32 = 64.0;
+    /// Transparent pixels around every glyph of a page.
+    pub const PADDING: i32 = 1;
+
+    #[derive(Debug)]
+    pub enum AtlasError {
+        /// Pages are either `Format::A8` or `Format::Argb32`.
+        UnsupportedFormat,
+        /// The glyph with the id does not fit into an empty page.
+        GlyphTooLarge(u32),
+        /// The color glyph with the id uses a sweep gradient or an image
+        /// other than PNG, which cairo can't paint.
+        UnsupportedPaint(u32),
+        SurfaceError(CairoError),
+    }
+
+    impl From<CairoError> for AtlasError {
+        fn from(error: CairoError) -> Self {
+            AtlasError::SurfaceError(error)
+        }
+    }
+
+    /// Texture coordinates of a glyph on its page, from 0 to 1 with v
+    /// growing downwards.
+    #[derive(Debug, Clone, Copy, PartialEq, Default)]
+    pub struct UvRect {
+        pub u0: f32,
+        pub v0: f32,
+        pub u1: f32,
+        pub v1: f32,
+    }
+
+    /// A rasterized glyph. The bearings are the offset in pixels from the
+    /// pen position on the baseline to the top left corner of the bitmap,
+    /// y grows downwards. Glyphs without ink have a size of zero.
+    #[derive(Debug, Clone, Copy, PartialEq, Default)]
+    pub struct AtlasGlyph {
+        pub page: usize,
+        pub uv: UvRect,
+        pub width: i32,
+        pub height: i32,
+        pub bearing_x: i32,
+        pub bearing_y: i32,
+    }
+
+    /// A glyph of a layout placed in pixels relative to the layout origin.
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub struct GlyphQuad {
+        pub page: usize,
+        pub x: i32,
+        pub y: i32,
+        pub width: i32,
+        pub height: i32,
+        pub uv: UvRect,
+    }
+
+    struct Shelf {
+        y: i32,
+        height: i32,
+        x: i32,
+    }
+
+    struct Page {
+        surface: CairoSurface,
+        shelves: Vec<Shelf>,
+    }
+
+    /// Creates a font for the face with a scale of [`UNITS_PER_PIXEL`]
+    /// units per pixel at `size` pixels per em.
+    pub fn scaled_font(face: &harfbuzz::Face, size: f32) -> harfbuzz::Font {
+        let font = harfbuzz::Font::new(face);
+        let scale = (size * UNITS_PER_PIXEL).round() as i32;
+        font.set_scale(scale, scale);
+        font
+    }
+
+    pub struct GlyphAtlas {
+        font: harfbuzz::Font,
+        format: Format,
+        distance_field: Option<SdfGenerator>,
+        page_size: i32,
+        subpixel_positions: u32,
+        pages: Vec<Page>,
+        // keyed by glyph, subpixel positions and position
+        glyphs: HashMap<(u32, u32, u32), AtlasGlyph>,
+        // color glyphs that can't be painted at any position
+        unsupported: HashSet<u32>,
+    }
+
+    impl GlyphAtlas {
+        /// Creates an empty atlas for glyphs of `face` at `size` pixels per
+        /// em. `Format::A8` pages hold coverage masks, `Format::Argb32`
+        /// pages hold premultiplied color glyphs painted with a white
+        /// foreground.
+        pub fn new(
+            face: &harfbuzz::Face,
+            size: f32,
+            format: Format,
+            page_size: i32,
+        ) -> Result<Self, AtlasError> {
+            if format != Format::A8 && format != Format::Argb32 {
+                return Err(AtlasError::UnsupportedFormat);
+            }
+            Ok(Self {
+                font: scaled_font(face, size),
+                format,
+                distance_field: None,
+                page_size,
+                subpixel_positions: 1,
+                pages: Vec::new(),
+                glyphs: HashMap::new(),
+                unsupported: HashSet::new(),
+            })
+        }
+
+        /// Creates an empty atlas of distance fields made by `generator`,
+        /// the pages have the format of [`SdfGenerator::format`]. The
+        /// bearings and sizes of the glyphs include the spread.
+        pub fn with_distance_field(
+            face: &harfbuzz::Face,
+            size: f32,
+            generator: SdfGenerator,
+            page_size: i32,
+        ) -> Self {
+            Self {
+                font: scaled_font(face, size),
+                format: generator.format(),
+                distance_field: Some(generator),
+                page_size,
+                subpixel_positions: 1,
+                pages: Vec::new(),
+                glyphs: HashMap::new(),
+                unsupported: HashSet::new(),
+            }
+        }
+
+        /// Sets how many horizontal subpixel offsets are rasterized per
+        /// glyph, 1 snaps glyphs to whole pixels. Glyphs that are already
+        /// in the atlas stay on their pages but are rasterized again for
+        /// the new positions.
+        pub fn set_subpixel_positions(&mut self, positions: u32) {
+            self.subpixel_positions = positions.max(1);
+        }
+
+        /// The font glyphs are rasterized with, its scale is in
+        /// [`UNITS_PER_PIXEL`] units per pixel. Variations and synthetic
+        /// bold or slant set on it apply to glyphs rasterized afterwards.
+        pub fn font(&self) -> &harfbuzz::Font {
+            &self.font
+        }
+
+        pub fn format(&self) -> Format {
+            self.format
+        }
+
+        pub fn page_count(&self) -> usize {
+            self.pages.len()
+        }
+
+        /// The pixels of a page, upload them with [`GlyphAtlas::page_data`]
+        /// or [`CairoSurface::to_rgba8`].
+        pub fn page(&self, index: usize) -> Option<&CairoSurface> {
+            self.pages.get(index).map(|page| &page.surface)
+        }
+
+        /// The raw pixels of a page, see [`CairoSurface::data`].
+        pub fn page_data(&mut self, index: usize) -> Option<&[u8]> {
+            self.pages.get_mut(index)?.surface.data()
+        }
+
+        /// Returns the glyph positioned at the fractional pixel offset
+        /// `x_offset`, rasterizing it on first use. Only the fraction is
+        /// used and rounded down to the nearest subpixel position.
+        pub fn glyph(&mut self, glyph: u32, x_offset: f32) -> Result<AtlasGlyph, AtlasError> {
+            let fraction = x_offset - x_offset.floor();
+            let variant = ((fraction * self.subpixel_positions as f32) as u32)
+                .min(self.subpixel_positions - 1);
+            let key = (glyph, self.subpixel_positions, variant);
+            if let Some(atlas_glyph) = self.glyphs.get(&key) {
+                return Ok(*atlas_glyph);
+            }
+            if self.unsupported.contains(&glyph) {
+                return Err(AtlasError::UnsupportedPaint(glyph));
+            }
+            let atlas_glyph =
+                self.rasterize(glyph, variant as f32 / self.subpixel_positions as f32)?;
+            self.glyphs.insert(key, atlas_glyph);
+            Ok(atlas_glyph)
+        }
+
+        /// Places the glyphs of a layout, rasterizing missing ones. Runs
+        /// shaped with another face than the atlas face, glyphs Pango
+        /// draws as hex boxes and color glyphs that can't be painted are
+        /// skipped. The layout is expected to use the same size as the
+        /// atlas.
+        pub fn layout_quads(
+            &mut self,
+            layout: &pango2::Pango2Layout,
+        ) -> Result<Vec<GlyphQuad>, AtlasError> {
+            let to_pixels = |units: i32| units as f32 / pango2::SCALE as f32;
+            let mut quads = Vec::new();
+            for run in layout.glyph_runs() {
+                if !run.font().same_face(&self.font) {
+                    continue;
+                }
+                let baseline = to_pixels(run.baseline);
+                let mut pen_x = run.x;
+                for info in &run.glyphs {
+                    if info.is_font_glyph() {
+                        let x = to_pixels(pen_x + info.x_offset);
+                        let atlas_glyph = match self.glyph(info.glyph, x) {
+                            Ok(atlas_glyph) => atlas_glyph,
+                            Err(AtlasError::UnsupportedPaint(_)) => AtlasGlyph::default(),
+                            Err(error) => return Err(error),
+                        };
+                        if atlas_glyph.width > 0 && atlas_glyph.height > 0 {
+                            quads.push(GlyphQuad {
+                                page: atlas_glyph.page,
+                                x: x.floor() as i32 + atlas_glyph.bearing_x,
+                                y: (baseline + to_pixels(info.y_offset)).round() as i32
+                                    + atlas_glyph.bearing_y,
+                                width: atlas_glyph.width,
+                                height: atlas_glyph.height,
+                                uv: atlas_glyph.uv,
+                            });
+                        }
+                    }
+                    pen_x += info.width;
+                }
+            }
+            Ok(quads)
+        }
+
+        fn rasterize(&mut self, glyph: u32, x_offset: f32) -> Result<AtlasGlyph, AtlasError> {
+            if let Some(generator) = self.distance_field {
+                return self.place_distance_field(generator, glyph, x_offset);
+            }
+            let extents = match self.font.glyph_extents(glyph) {
+                Some(extents) if extents.width != 0 && extents.height != 0 => extents,
+                _ => return Ok(AtlasGlyph::default()),
+            };
+            // the extents are in font units with y growing upwards
+            let left = (extents.x_bearing as f32 / UNITS_PER_PIXEL + x_offset).floor() as i32;
+            let right = ((extents.x_bearing + extents.width) as f32 / UNITS_PER_PIXEL + x_offset)
+                .ceil() as i32;
+            let top = (-extents.y_bearing as f32 / UNITS_PER_PIXEL).floor() as i32;
+            let bottom =
+                (-(extents.y_bearing + extents.height) as f32 / UNITS_PER_PIXEL).ceil() as i32;
+            let (width, height) = (right - left, bottom - top);
+
+            let (page, x, y) = if self.format == Format::A8 {
+                let (page, x, y) = self.allocate(glyph, width, height)?;
+                let context = CairoContext::create(&self.pages[page].surface);
+                context.rectangle(x as f32, y as f32, width as f32, height as f32);
+                context.clip();
+                context.translate((x - left) as f32 + x_offset, (y - top) as f32);
+                context.scale(1.0 / UNITS_PER_PIXEL, -1.0 / UNITS_PER_PIXEL);
+                self.font
+                    .draw_glyph(glyph, &mut CairoOutline::new(&context));
+                context.fill();
+                (page, x, y)
+            } else {
+                // paint on a surface of its own first, so that glyphs that
+                // can't be painted take no room on the pages
+                let bitmap = CairoSurface::new_image_surface(width, height)?;
+                let context = CairoContext::create(&bitmap);
+                context.translate(x_offset - left as f32, -top as f32);
+                context.scale(1.0 / UNITS_PER_PIXEL, -1.0 / UNITS_PER_PIXEL);
+                let mut painter = ColorRasterizer {
+                    font: &self.font,
+                    context: &context,
+                    unsupported: false,
+                };
+                let white = harfbuzz::Color {
+                    red: 255,
+                    green: 255,
+                    blue: 255,
+                    alpha: 255,
+                };
+                self.font.paint_glyph(glyph, &mut painter, 0, white);
+                if painter.unsupported {
+                    self.unsupported.insert(glyph);
+                    return Err(AtlasError::UnsupportedPaint(glyph));
+                }
+                drop(context);
+
+                let (page, x, y) = self.allocate(glyph, width, height)?;
+                let context = CairoContext::create(&self.pages[page].surface);
+                context.set_source_surface(&bitmap, x as f32, y as f32);
+                context.paint();
+                (page, x, y)
+            };
+
+            Ok(AtlasGlyph {
+                page,
+                uv: self.uv_rect(x, y, width, height),
+                width,
+                height,
+                bearing_x: left,
+                bearing_y: top,
+            })
+        }
+
+        /// Copies the distance field of a glyph into a page, MSDF channels
+        /// become the red, green and blue of the `Rgb24` pixels.
+        fn place_distance_field(
+            &mut self,
+            generator: SdfGenerator,
+            glyph: u32,
+            x_offset: f32,
+        ) -> Result<AtlasGlyph, AtlasError> {
+            let field = generator.generate(&self.font, glyph, x_offset);
+            if field.width == 0 || field.height == 0 {
+                return Ok(AtlasGlyph::default());
+            }
+            let (page, x, y) = self.allocate(glyph, field.width, field.height)?;
+            let surface = &mut self.pages[page].surface;
+            let stride = surface.stride();
+            // pages are image surfaces, so their data is always available
+            let mut data = surface.data_mut().unwrap();
+            let row_length = field.width as usize * field.channels;
+            for (row, values) in field.data.chunks(row_length).enumerate() {
+                let start = (y as usize + row) * stride;
+                if field.channels == 1 {
+                    let start = start + x as usize;
+                    data[start..start + values.len()].copy_from_slice(values);
+                } else {
+                    for (column, rgb) in values.chunks(3).enumerate() {
+                        let pixel = u32::from_be_bytes([0, rgb[0], rgb[1], rgb[2]]);
+                        let start = start + (x as usize + column) * 4;
+                        data[start..start + 4].copy_from_slice(&pixel.to_ne_bytes());
+                    }
+                }
+            }
+            drop(data);
+            Ok(AtlasGlyph {
+                page,
+                uv: self.uv_rect(x, y, field.width, field.height),
+                width: field.width,
+                height: field.height,
+                bearing_x: field.bearing_x,
+                bearing_y: field.bearing_y,
+            })
+        }
+
+        fn uv_rect(&self, x: i32, y: i32, width: i32, height: i32) -> UvRect {
+            let size = self.page_size as f32;
+            UvRect {
+                u0: x as f32 / size,
+                v0: y as f32 / size,
+                u1: (x + width) as f32 / size,
+                v1: (y + height) as f32 / size,
+            }
+        }
+
+        /// Finds room for a bitmap on the shelves of the last page, opening
+        /// a new page when it is full. Returns the page and the top left
+        /// corner inside the padding.
+        fn allocate(
+            &mut self,
+            glyph: u32,
+            width: i32,
+            height: i32,
+        ) -> Result<(usize, i32, i32), AtlasError> {
+            let (padded_width, padded_height) = (width + 2 * PADDING, height + 2 * PADDING);
+            if padded_width > self.page_size || padded_height > self.page_size {
+                return Err(AtlasError::GlyphTooLarge(glyph));
+            }
+            if let Some(page) = self.pages.last_mut() {
+                let page_size = self.page_size;
+                if let Some(shelf) = page.shelves.iter_mut().find(|shelf| {
+                    shelf.height >= padded_height && shelf.x + padded_width <= page_size
+                }) {
+                    let position = (shelf.x + PADDING, shelf.y + PADDING);
+                    shelf.x += padded_width;
+                    return Ok((self.pages.len() - 1, position.0, position.1));
+                }
+                let y = page
+                    .shelves
+                    .last()
+                    .map_or(0, |shelf| shelf.y + shelf.height);
+                if y + padded_height <= page_size {
+                    page.shelves.push(Shelf {
+                        y,
+                        height: padded_height,
+                        x: padded_width,
+                    });
+                    return Ok((self.pages.len() - 1, PADDING, y + PADDING));
+                }
+            }
+            let surface = CairoSurface::new_image_surface_with_format(
+                self.format,
+                self.page_size,
+                self.page_size,
+            )?;
+            self.pages.push(Page {
+                surface,
+                shelves: vec![Shelf {
+                    y: 0,
+                    height: padded_height,
+                    x: padded_width,
+                }],
+            });
+            Ok((self.pages.len() - 1, PADDING, PADDING))
+        }
+    }
+
+    /// Appends glyph outlines to the current path of a cairo context.
+    struct CairoOutline<'a> {
+        context: &'a CairoContext,
+        current: (f32, f32),
+    }
+
+    impl<'a> CairoOutline<'a> {
+        fn new(context: &'a CairoContext) -> Self {
+            context.new_path();
+            Self {
+                context,
+                current: (0.0, 0.0),
+            }
+        }
+    }
+
+    impl OutlineSink for CairoOutline<'_> {
+        fn move_to(&mut self, x: f32, y: f32) {
+            self.context.move_to(x, y);
+            self.current = (x, y);
+        }
+
+        fn line_to(&mut self, x: f32, y: f32) {
+            self.context.line_to(x, y);
+            self.current = (x, y);
+        }
+
+        fn quadratic_to(&mut self, control: (f32, f32), to: (f32, f32)) {
+            // cairo has no quadratic curves, raise the degree
+            let (x0, y0) = self.current;
+            self.cubic_to(
+                (
+                    x0 + 2.0 / 3.0 * (control.0 - x0),
+                    y0 + 2.0 / 3.0 * (control.1 - y0),
+                ),
+                (
+                    to.0 + 2.0 / 3.0 * (control.0 - to.0),
+                    to.1 + 2.0 / 3.0 * (control.1 - to.1),
+                ),
+                to,
+            );
+        }
+
+        fn cubic_to(&mut self, control1: (f32, f32), control2: (f32, f32), to: (f32, f32)) {
+            self.context
+                .curve_to(control1.0, control1.1, control2.0, control2.1, to.0, to.1);
+            self.current = to;
+        }
+
+        fn close_path(&mut self) {
+            self.context.close_path();
+        }
+    }
+
+    /// Paints color glyphs with cairo. Sweep gradients and images other
+    /// than PNG have no cairo equivalent, they set `unsupported` instead.
+    struct ColorRasterizer<'a> {
+        font: &'a harfbuzz::Font,
+        context: &'a CairoContext,
+        unsupported: bool,
+    }
+
+    impl ColorRasterizer<'_> {
+        fn set_source_color(&self, color: harfbuzz::Color) {
+            self.context.set_source_rgba(
+                color.red as f32 / 255.0,
+                color.green as f32 / 255.0,
+                color.blue as f32 / 255.0,
+                color.alpha as f32 / 255.0,
+            );
+        }
+
+        fn paint_color_line(&self, pattern: Pattern, color_line: &ColorLine) {
+            pattern.set_extend(match color_line.extend {
+                PaintExtend::Pad => cairo::Extend::Pad,
+                PaintExtend::Repeat => cairo::Extend::Repeat,
+                PaintExtend::Reflect => cairo::Extend::Reflect,
+            });
+            for stop in &color_line.stops {
+                pattern.add_color_stop_rgba(
+                    stop.offset,
+                    stop.color.red as f32 / 255.0,
+                    stop.color.green as f32 / 255.0,
+                    stop.color.blue as f32 / 255.0,
+                    stop.color.alpha as f32 / 255.0,
+                );
+            }
+            self.context.set_source(&pattern);
+            self.context.paint();
+        }
+    }
+
+    impl GlyphPainter for ColorRasterizer<'_> {
+        fn push_transform(&mut self, transform: harfbuzz::Transform) {
+            self.context.save();
+            self.context.transform(&cairo::Matrix {
+                xx: transform.xx,
+                yx: transform.yx,
+                xy: transform.xy,
+                yy: transform.yy,
+                x0: transform.dx,
+                y0: transform.dy,
+            });
+        }
+
+        fn pop_transform(&mut self) {
+            self.context.restore();
+        }
+
+        fn push_clip_glyph(&mut self, glyph: u32) {
+            self.context.save();
+            self.font
+                .draw_glyph(glyph, &mut CairoOutline::new(self.context));
+            self.context.clip();
+        }
+
+        fn push_clip_rectangle(&mut self, xmin: f32, ymin: f32, xmax: f32, ymax: f32) {
+            self.context.save();
+            self.context.new_path();
+            self.context.rectangle(xmin, ymin, xmax - xmin, ymax - ymin);
+            self.context.clip();
+        }
+
+        fn pop_clip(&mut self) {
+            self.context.restore();
+        }
+
+        fn color(&mut self, _is_foreground: bool, color: harfbuzz::Color) {
+            self.set_source_color(color);
+            self.context.paint();
+        }
+
+        fn linear_gradient(
+            &mut self,
+            color_line: &ColorLine,
+            p0: (f32, f32),
+            p1: (f32, f32),
+            p2: (f32, f32),
+        ) {
+            // the gradient runs perpendicular to p0 p2, project p1 onto the
+            // normal through p0 to get the end point cairo expects
+            let normal = (p0.1 - p2.1, p2.0 - p0.0);
+            let length = normal.0 * normal.0 + normal.1 * normal.1;
+            let end = if length == 0.0 {
+                p1
+            } else {
+                let t = ((p1.0 - p0.0) * normal.0 + (p1.1 - p0.1) * normal.1) / length;
+                (p0.0 + t * normal.0, p0.1 + t * normal.1)
+            };
+            self.paint_color_line(Pattern::linear(p0.0, p0.1, end.0, end.1), color_line);
+        }
+
+        fn radial_gradient(
+            &mut self,
+            color_line: &ColorLine,
+            c0: (f32, f32),
+            r0: f32,
+            c1: (f32, f32),
+            r1: f32,
+        ) {
+            self.paint_color_line(Pattern::radial(c0.0, c0.1, r0, c1.0, c1.1, r1), color_line);
+        }
+
+        fn sweep_gradient(
+            &mut self,
+            _color_line: &ColorLine,
+            _center: (f32, f32),
+            _start_angle: f32,
+            _end_angle: f32,
+        ) {
+            self.unsupported = true;
+        }
+
+        fn image(
+            &mut self,
+            image: &[u8],
+            width: u32,
+            height: u32,
+            format: harfbuzz::ImageFormat,
+            slant: f32,
+            extents: Option<harfbuzz::GlyphExtents>,
+        ) -> bool {
+            let decoded = match (format, extents) {
+                (harfbuzz::ImageFormat::Png, Some(extents)) if width > 0 && height > 0 => {
+                    CairoSurface::read_png(image)
+                        .ok()
+                        .map(|surface| (surface, extents))
+                }
+                _ => None,
+            };
+            let (surface, extents) = match decoded {
+                Some(decoded) => decoded,
+                None => {
+                    self.unsupported = true;
+                    return true;
+                }
+            };
+            // map the image onto the extents like hb-cairo does, they
+            // include the slant, which is applied to the context instead
+            let (x_bearing, y_bearing) = (extents.x_bearing as f32, extents.y_bearing as f32);
+            let extents_width = extents.width as f32 - extents.height as f32 * slant;
+            self.context.save();
+            self.context.transform(&cairo::Matrix {
+                xx: 1.0,
+                yx: 0.0,
+                xy: slant,
+                yy: 1.0,
+                x0: 0.0,
+                y0: 0.0,
+            });
+            self.context
+                .translate(x_bearing - y_bearing * slant, y_bearing);
+            self.context.scale(
+                extents_width / width as f32,
+                extents.height as f32 / height as f32,
+            );
+            self.context.set_source_surface(&surface, 0.0, 0.0);
+            self.context.paint();
+            self.context.restore();
+            true
+        }
+
+        fn push_group(&mut self) {
+            self.context.push_group();
+        }
+
+        fn pop_group(&mut self, mode: harfbuzz::CompositeMode) {
+            // the group is the source and everything painted before it the
+            // backdrop, every mode has a cairo operator of the same name
+            let operator = match mode {
+                harfbuzz::CompositeMode::Clear => cairo::Operator::Clear,
+                harfbuzz::CompositeMode::Src => cairo::Operator::Source,
+                harfbuzz::CompositeMode::Dest => cairo::Operator::Dest,
+                harfbuzz::CompositeMode::SrcOver => cairo::Operator::Over,
+                harfbuzz::CompositeMode::DestOver => cairo::Operator::DestOver,
+                harfbuzz::CompositeMode::SrcIn => cairo::Operator::In,
+                harfbuzz::CompositeMode::DestIn => cairo::Operator::DestIn,
+                harfbuzz::CompositeMode::SrcOut => cairo::Operator::Out,
+                harfbuzz::CompositeMode::DestOut => cairo::Operator::DestOut,
+                harfbuzz::CompositeMode::SrcAtop => cairo::Operator::Atop,
+                harfbuzz::CompositeMode::DestAtop => cairo::Operator::DestAtop,
+                harfbuzz::CompositeMode::Xor => cairo::Operator::Xor,
+                harfbuzz::CompositeMode::Plus => cairo::Operator::Add,
+                harfbuzz::CompositeMode::Screen => cairo::Operator::Screen,
+                harfbuzz::CompositeMode::Overlay => cairo::Operator::Overlay,
+                harfbuzz::CompositeMode::Darken => cairo::Operator::Darken,
+                harfbuzz::CompositeMode::Lighten => cairo::Operator::Lighten,
+                harfbuzz::CompositeMode::ColorDodge => cairo::Operator::ColorDodge,
+                harfbuzz::CompositeMode::ColorBurn => cairo::Operator::ColorBurn,
+                harfbuzz::CompositeMode::HardLight => cairo::Operator::HardLight,
+                harfbuzz::CompositeMode::SoftLight => cairo::Operator::SoftLight,
+                harfbuzz::CompositeMode::Difference => cairo::Operator::Difference,
+                harfbuzz::CompositeMode::Exclusion => cairo::Operator::Exclusion,
+                harfbuzz::CompositeMode::Multiply => cairo::Operator::Multiply,
+                harfbuzz::CompositeMode::HslHue => cairo::Operator::HslHue,
+                harfbuzz::CompositeMode::HslSaturation => cairo::Operator::HslSaturation,
+                harfbuzz::CompositeMode::HslColor => cairo::Operator::HslColor,
+                harfbuzz::CompositeMode::HslLuminosity => cairo::Operator::HslLuminosity,
+            };
+            self.context.pop_group_to_source();
+            self.context.set_operator(operator);
+            self.context.paint();
+            self.context.set_operator(cairo::Operator::Over);
+        }
+    }
+}
+
+/// Signed distance fields of glyph outlines, computed on the CPU.
+///
+/// Outlines are flattened into line segments and every pixel center stores
+/// its distance to the nearest edge, mapped so that 128 lies on the outline,
+/// larger values are inside and 0 or 255 are `spread` pixels away. Only
+/// additions, multiplications, divisions and square roots are used, which
+/// IEEE 754 rounds exactly, in a fixed order, so the same outline gives the
+/// same field on every platform. The outline itself comes from HarfBuzz and
+/// may change with its version.
+pub mod sdf {
+    use super::atlas::UNITS_PER_PIXEL;
+    use super::cairo::Format;
+    use super::harfbuzz::{self, PathBuilder, PathCommand};
+
+    /// Line segments each curve is split into.
+    const CURVE_SEGMENTS: usize = 16;
+    /// Sine of the smallest angle between two edges that counts as corner.
+    const CORNER_THRESHOLD: f64 = 0.141;
+
+    const RED: u8 = 1;
+    const GREEN: u8 = 2;
+    const BLUE: u8 = 4;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum FieldType {
+        /// One channel with the distance to the nearest edge.
+        Sdf,
+        /// Three channels with the distances to differently colored edges,
+        /// the median of the channels keeps corners sharp when sampled.
+        Msdf,
+    }
+
+    /// A distance field of a glyph. The bearings are the offset in pixels
+    /// from the pen position on the baseline to the top left pixel, y grows
+    /// downwards. Rows are `width * channels` bytes without padding.
+    #[derive(Debug, Clone, PartialEq, Eq, Default)]
+    pub struct DistanceField {
+        pub width: i32,
+        pub height: i32,
+        pub bearing_x: i32,
+        pub bearing_y: i32,
+        pub channels: usize,
+        pub data: Vec<u8>,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub struct SdfGenerator {
+        field_type: FieldType,
+        spread: f32,
+    }
+
+    impl SdfGenerator {
+        /// Creates a generator for fields that reach `spread` pixels from
+        /// the outline, the glyph bitmaps grow by the spread on every side.
+        pub fn new(field_type: FieldType, spread: f32) -> Self {
+            Self {
+                field_type,
+                spread: spread.max(f32::EPSILON),
+            }
+        }
+
+        pub fn field_type(&self) -> FieldType {
+            self.field_type
+        }
+
+        pub fn spread(&self) -> f32 {
+            self.spread
+        }
+
+        /// Format of the atlas pages holding the fields, `Format::A8` for
+        /// SDF and `Format::Rgb24` for MSDF.
+        pub fn format(&self) -> Format {
+            match self.field_type {
+                FieldType::Sdf => Format::A8,
+                FieldType::Msdf => Format::Rgb24,
+            }
+        }
+
+        /// Computes the field of a glyph shifted by `x_offset` pixels. The
+        /// scale of the font is expected in
+        /// [`UNITS_PER_PIXEL`](super::atlas::UNITS_PER_PIXEL) units per
+        /// pixel, see [`atlas::scaled_font`](super::atlas::scaled_font).
+        /// Glyphs without outline give an empty field.
+        pub fn generate(&self, font: &harfbuzz::Font, glyph: u32, x_offset: f32) -> DistanceField {
+            let mut builder = PathBuilder::new();
+            font.draw_glyph(glyph, &mut builder);
+            let contours = flatten(builder.commands(), x_offset as f64);
+            let points = contours
+                .iter()
+                .flat_map(|contour| contour.iter().flat_map(|edge| edge.points.iter()));
+            let (mut min, mut max) = ((f64::MAX, f64::MAX), (f64::MIN, f64::MIN));
+            for point in points {
+                min = (min.0.min(point.0), min.1.min(point.1));
+                max = (max.0.max(point.0), max.1.max(point.1));
+            }
+            let channels = match self.field_type {
+                FieldType::Sdf => 1,
+                FieldType::Msdf => 3,
+            };
+            if min.0 > max.0 {
+                return DistanceField {
+                    channels,
+                    ..DistanceField::default()
+                };
+            }
+
+            let spread = self.spread as f64;
+            let margin = spread.ceil() as i32;
+            let left = min.0.floor() as i32 - margin;
+            let top = min.1.floor() as i32 - margin;
+            let width = max.0.ceil() as i32 + margin - left;
+            let height = max.1.ceil() as i32 + margin - top;
+            let orientation = if signed_area(&contours) < 0.0 {
+                -1.0
+            } else {
+                1.0
+            };
+            let encode = |distance: f64| {
+                ((0.5 + distance / (2.0 * spread)).clamp(0.0, 1.0) * 255.0).round() as u8
+            };
+
+            let mut data = Vec::with_capacity((width * height) as usize * channels);
+            for row in 0..height {
+                for column in 0..width {
+                    let p = ((left + column) as f64 + 0.5, (top + row) as f64 + 0.5);
+                    let inside = winding(&contours, p) != 0;
+                    let distance = nearest_distance(&contours, p);
+                    let distance = if inside { distance } else { -distance };
+                    match self.field_type {
+                        FieldType::Sdf => data.push(encode(distance)),
+                        FieldType::Msdf => {
+                            let mut rgb = [RED, GREEN, BLUE].map(|channel| {
+                                channel_distance(&contours, p, channel, orientation)
+                            });
+                            // where the channels disagree with the outline,
+                            // e.g. next to a clash of edges, fall back to
+                            // the plain distance
+                            if (median(rgb) > 0.0) != inside {
+                                rgb = [distance; 3];
+                            }
+                            data.extend(rgb.map(encode));
+                        }
+                    }
+                }
+            }
+
+            DistanceField {
+                width,
+                height,
+                bearing_x: left,
+                bearing_y: top,
+                channels,
+                data,
+            }
+        }
+    }
+
+    /// A segment of the original outline as polyline, colored with the
+    /// channels it contributes to.
+    struct Edge {
+        points: Vec<(f64, f64)>,
+        color: u8,
+    }
+
+    /// Converts the outline into closed contours of edges in pixels with y
+    /// growing downwards, edge colors are assigned per contour.
+    fn flatten(commands: &[PathCommand], x_offset: f64) -> Vec<Vec<Edge>> {
+        let to_pixels = |x: f32, y: f32| {
+            (
+                x as f64 / UNITS_PER_PIXEL as f64 + x_offset,
+                -y as f64 / UNITS_PER_PIXEL as f64,
+            )
+        };
+        let mut contours = Vec::new();
+        let mut contour: Vec<Edge> = Vec::new();
+        let mut start = (0.0, 0.0);
+        let mut current = (0.0, 0.0);
+        let mut finish = |contour: &mut Vec<Edge>, current: (f64, f64), start: (f64, f64)| {
+            if current != start {
+                contour.push(Edge {
+                    points: vec![current, start],
+                    color: 0,
+                });
+            }
+            if !contour.is_empty() {
+                color_edges(contour);
+                contours.push(std::mem::take(contour));
+            }
+        };
+        for command in commands {
+            let points = match *command {
+                PathCommand::MoveTo(x, y) => {
+                    finish(&mut contour, current, start);
+                    start = to_pixels(x, y);
+                    current = start;
+                    continue;
+                }
+                PathCommand::LineTo(x, y) => vec![current, to_pixels(x, y)],
+                PathCommand::QuadraticTo(cx, cy, x, y) => {
+                    let (c, to) = (to_pixels(cx, cy), to_pixels(x, y));
+                    curve_points(|t| {
+                        let s = 1.0 - t;
+                        (
+                            s * s * current.0 + 2.0 * s * t * c.0 + t * t * to.0,
+                            s * s * current.1 + 2.0 * s * t * c.1 + t * t * to.1,
+                        )
+                    })
+                }
+                PathCommand::CubicTo(c1x, c1y, c2x, c2y, x, y) => {
+                    let (c1, c2, to) = (to_pixels(c1x, c1y), to_pixels(c2x, c2y), to_pixels(x, y));
+                    curve_points(|t| {
+                        let s = 1.0 - t;
+                        let (a, b, c, d) = (s * s * s, 3.0 * s * s * t, 3.0 * s * t * t, t * t * t);
+                        (
+                            a * current.0 + b * c1.0 + c * c2.0 + d * to.0,
+                            a * current.1 + b * c1.1 + c * c2.1 + d * to.1,
+                        )
+                    })
+                }
+                PathCommand::ClosePath => {
+                    finish(&mut contour, current, start);
+                    current = start;
+                    continue;
+                }
+            };
+            current = *points.last().unwrap();
+            if points.windows(2).any(|pair| pair[0] != pair[1]) {
+                contour.push(Edge { points, color: 0 });
+            }
+        }
+        finish(&mut contour, current, start);
+        contours
+    }
+
+    fn curve_points(point_at: impl Fn(f64) -> (f64, f64)) -> Vec<(f64, f64)> {
+        (0..=CURVE_SEGMENTS)
+            .map(|i| point_at(i as f64 / CURVE_SEGMENTS as f64))
+            .collect()
+    }
+
+    /// Colors the edges so that the two edges meeting at a corner never
+    /// share two channels, smooth joins keep the color.
+    fn color_edges(contour: &mut [Edge]) {
+        const CYAN: u8 = GREEN | BLUE;
+        const MAGENTA: u8 = RED | BLUE;
+        const YELLOW: u8 = RED | GREEN;
+        const WHITE: u8 = RED | GREEN | BLUE;
+
+        let count = contour.len();
+        let corners: Vec<usize> = (0..count)
+            .filter(|&i| {
+                let previous = &contour[(i + count - 1) % count].points;
+                let points = &contour[i].points;
+                is_corner(
+                    direction(previous[previous.len() - 2], previous[previous.len() - 1]),
+                    direction(points[0], points[1]),
+                )
+            })
+            .collect();
+
+        match corners.len() {
+            0 => contour.iter_mut().for_each(|edge| edge.color = WHITE),
+            // a teardrop, split the contour into three parts
+            1 => {
+                let colors = [MAGENTA, WHITE, YELLOW];
+                for i in 0..count {
+                    let edge = (corners[0] + i) % count;
+                    contour[edge].color = if count < 3 {
+                        WHITE
+                    } else {
+                        colors[i * 3 / count]
+                    };
+                }
+            }
+            splines => {
+                for (spline, &corner) in corners.iter().enumerate() {
+                    // the last spline of an odd count borders two others
+                    // with different colors
+                    let color = if spline == splines - 1 && splines % 2 == 1 {
+                        YELLOW
+                    } else if spline % 2 == 0 {
+                        CYAN
+                    } else {
+                        MAGENTA
+                    };
+                    let end = corners[(spline + 1) % splines];
+                    let mut edge = corner;
+                    loop {
+                        contour[edge].color = color;
+                        edge = (edge + 1) % count;
+                        if edge == end {
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Like `f64::hypot`, which calls the platform libm and isn't exactly
+    /// rounded everywhere.
+    fn length(dx: f64, dy: f64) -> f64 {
+        (dx * dx + dy * dy).sqrt()
+    }
+
+    fn direction(from: (f64, f64), to: (f64, f64)) -> (f64, f64) {
+        let length = length(to.0 - from.0, to.1 - from.1);
+        if length == 0.0 {
+            (0.0, 0.0)
+        } else {
+            ((to.0 - from.0) / length, (to.1 - from.1) / length)
+        }
+    }
+
+    fn is_corner(a: (f64, f64), b: (f64, f64)) -> bool {
+        a.0 * b.0 + a.1 * b.1 <= 0.0 || (a.0 * b.1 - a.1 * b.0).abs() > CORNER_THRESHOLD
+    }
+
+    fn segments(edge: &Edge) -> impl Iterator<Item = ((f64, f64), (f64, f64))> + '_ {
+        edge.points.windows(2).map(|pair| (pair[0], pair[1]))
+    }
+
+    fn signed_area(contours: &[Vec<Edge>]) -> f64 {
+        contours
+            .iter()
+            .flatten()
+            .flat_map(segments)
+            .map(|(a, b)| a.0 * b.1 - b.0 * a.1)
+            .sum::<f64>()
+            / 2.0
+    }
+
+    /// Nonzero winding number of the outline around `p`.
+    fn winding(contours: &[Vec<Edge>], p: (f64, f64)) -> i32 {
+        let mut winding = 0;
+        for (a, b) in contours.iter().flatten().flat_map(segments) {
+            let side = (b.0 - a.0) * (p.1 - a.1) - (p.0 - a.0) * (b.1 - a.1);
+            if a.1 <= p.1 && b.1 > p.1 && side > 0.0 {
+                winding += 1;
+            } else if a.1 > p.1 && b.1 <= p.1 && side < 0.0 {
+                winding -= 1;
+            }
+        }
+        winding
+    }
+
+    /// Parameter of the point of the line through `a` and `b` closest to
+    /// `p`, 0 at `a` and 1 at `b`.
+    fn project(p: (f64, f64), a: (f64, f64), b: (f64, f64)) -> f64 {
+        let d = (b.0 - a.0, b.1 - a.1);
+        let length = d.0 * d.0 + d.1 * d.1;
+        if length == 0.0 {
+            0.0
+        } else {
+            ((p.0 - a.0) * d.0 + (p.1 - a.1) * d.1) / length
+        }
+    }
+
+    fn distance_to_segment(p: (f64, f64), a: (f64, f64), b: (f64, f64)) -> f64 {
+        let t = project(p, a, b).clamp(0.0, 1.0);
+        length(p.0 - (a.0 + t * (b.0 - a.0)), p.1 - (a.1 + t * (b.1 - a.1)))
+    }
+
+    fn nearest_distance(contours: &[Vec<Edge>], p: (f64, f64)) -> f64 {
+        contours
+            .iter()
+            .flatten()
+            .flat_map(segments)
+            .map(|(a, b)| distance_to_segment(p, a, b))
+            .fold(f64::MAX, f64::min)
+    }
+
+    /// Signed pseudo-distance to the nearest edge with the channel, beyond
+    /// the ends of that edge the distance to its extended tangent is used.
+    /// Of two edges at the same distance, e.g. next to their shared corner,
+    /// the one `p` lies more perpendicular to is taken. Positive values are
+    /// inside for the given contour orientation.
+    fn channel_distance(
+        contours: &[Vec<Edge>],
+        p: (f64, f64),
+        channel: u8,
+        orientation: f64,
+    ) -> f64 {
+        let mut nearest = f64::MAX;
+        let mut nearest_orthogonality = 0.0;
+        let mut pseudo = -f64::MAX;
+        for edge in contours.iter().flatten() {
+            if edge.color & channel == 0 {
+                continue;
+            }
+            let last = edge.points.len() - 2;
+            for (index, (a, b)) in segments(edge).enumerate() {
+                let distance = distance_to_segment(p, a, b);
+                let length = length(b.0 - a.0, b.1 - a.1);
+                let cross = (b.0 - a.0) * (p.1 - a.1) - (b.1 - a.1) * (p.0 - a.0);
+                let orthogonality = if distance > 0.0 && length > 0.0 {
+                    (cross / (length * distance)).abs()
+                } else {
+                    1.0
+                };
+                if distance > nearest + 1e-9
+                    || (distance > nearest - 1e-9 && orthogonality <= nearest_orthogonality)
+                {
+                    continue;
+                }
+                nearest = distance;
+                nearest_orthogonality = orthogonality;
+                let t = project(p, a, b);
+                let extended = (index == 0 && t < 0.0) || (index == last && t > 1.0);
+                let sign = if cross * orientation < 0.0 { -1.0 } else { 1.0 };
+                pseudo = if extended && length > 0.0 {
+                    sign * (cross / length).abs()
+                } else {
+                    sign * distance
+                };
+            }
+        }
+        pseudo
+    }
+
+    fn median(values: [f64; 3]) -> f64 {
+        values[0]
+            .min(values[1])
+            .max(values[0].max(values[1]).min(values[2]))
     }
 }
\ No newline at end of file
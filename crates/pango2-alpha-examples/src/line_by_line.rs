@@ -0,0 +1,118 @@
+use std::{env, path::Path, process};
+use pango2_sys_examples::{freetype, harfbuzz, pango2, cairo};
+
+const MARGIN: f32 = 10.0;
+
+fn new_canvas(width: i32, height: i32) -> (cairo::CairoSurface, cairo::CairoContext) {
+    let surface = cairo::CairoSurface::new_image_surface(width, height).unwrap();
+    let context = cairo::CairoContext::create(&surface);
+    (surface, context)
+}
+
+fn units(value: i32) -> f32 {
+    value as f32 / pango2::SCALE as f32
+}
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    let filename = if args.len() > 1 {
+        &args[1]
+    } else {
+        "./fonts/NotoSerifDisplay/NotoSerifDisplay-VariableFont_wdth,wght.ttf"
+    };
+
+    if !Path::new(filename).exists() {
+        eprintln!("Error: provided font file '{}' does not exist, specify the location to the font as an argument", filename);
+        process::exit(1);
+    }
+
+    let lib = freetype::Library::init().unwrap();
+    let face = lib.face_from_file(filename, 0).unwrap();
+    let hb_face = harfbuzz::Face::from_ft(&face);
+    let font_map = pango2::Pango2FontMap::new();
+    font_map.add_face(&pango2::Pango2HbFace::from_hb_face(&hb_face, -2));
+    let pango_context = pango2::Pango2Context::from_font_map(&font_map);
+
+    // break a paragraph into lines of our own width and stack them
+    let text = "Pango2 breaks the paragraph into lines, we place every line ourselves.";
+    let attributes = pango2::AttrList::new();
+    attributes.insert(pango2::Attribute::family(&face.face_name()));
+    attributes.insert(pango2::Attribute::foreground(pango2::Color::parse("#c01c28").unwrap()).with_range(0, 6));
+    let breaker = pango2::Pango2LineBreaker::new(&pango_context);
+    breaker.add_text(text, Some(&attributes));
+    let lines = pango2::Pango2Lines::new();
+    let mut top = 0;
+    while let Some(line) = breaker.next_line(0, 250 * pango2::SCALE, pango2::WrapMode::Word, pango2::EllipsizeMode::None) {
+        let (_, logical) = line.extents();
+        lines.add_line(line, 0, top - logical.y);
+        top += logical.height;
+    }
+    println!("Broke the paragraph into {} lines", lines.line_count());
+    if lines.line_count() < 2 {
+        eprintln!("Error: the paragraph was not broken into lines");
+        process::exit(1);
+    }
+
+    let (_, logical) = lines.extents();
+    let width = units(logical.width).ceil() as i32 + 2 * MARGIN as i32;
+    let height = units(logical.height).ceil() as i32 + 2 * MARGIN as i32;
+
+    // all lines at once
+    let (mut all_surface, context) = new_canvas(width, height);
+    context.move_to(MARGIN, MARGIN);
+    lines.show(&context);
+    // the pixels of a surface can only be read once its context is gone
+    drop(context);
+
+    // line by line at their positions
+    let (mut line_surface, context) = new_canvas(width, height);
+    for (line, x, y) in lines.lines() {
+        context.move_to(MARGIN + units(x), MARGIN + units(y));
+        line.show(&context);
+    }
+    drop(context);
+
+    // run by run, advancing by the width of each run
+    let (mut run_surface, context) = new_canvas(width, height);
+    for (line, x, y) in lines.lines() {
+        let mut run_x = x;
+        for run in line.runs() {
+            context.move_to(MARGIN + units(run_x), MARGIN + units(y));
+            run.show(&context);
+            run_x += run.extents().1.width;
+        }
+    }
+    drop(context);
+
+    let all = all_surface.data().unwrap();
+    if !all.iter().any(|value| *value != 0) {
+        eprintln!("Error: nothing was drawn");
+        process::exit(1);
+    }
+    for (name, surface) in [("line", &mut line_surface), ("run", &mut run_surface)] {
+        let differing = all.iter().zip(surface.data().unwrap()).filter(|(a, b)| a.abs_diff(**b) > 2).count();
+        println!("Drawing {} by {}: {} bytes differ", name, name, differing);
+        if differing > 0 {
+            eprintln!("Error: drawing {} by {} differs from drawing all lines", name, name);
+            process::exit(1);
+        }
+    }
+
+    // an animation frame, the lines fade in one after the other
+    let (frame_surface, context) = new_canvas(width, height);
+    context.set_source_rgb(1.0, 1.0, 1.0);
+    context.paint();
+    let line_count = lines.line_count();
+    for (i, (line, x, y)) in lines.lines().into_iter().enumerate() {
+        let alpha = 1.0 - i as f32 / line_count as f32;
+        context.push_group();
+        context.move_to(MARGIN + units(x), MARGIN + units(y) + 8.0 * (1.0 - alpha));
+        line.show(&context);
+        context.pop_group_to_source();
+        context.paint_with_alpha(alpha);
+    }
+
+    let output = "line_by_line_test.png";
+    println!("Write animation frame to file: {}", output);
+    frame_surface.write_to_png(output).unwrap();
+}
@@ -0,0 +1,147 @@
+use std::{env, path::Path, process};
+use pango2_sys_examples::{freetype, harfbuzz, pango2, cairo};
+use harfbuzz::MetricsTag;
+
+fn to_pixels(units: i32) -> f32 {
+    units as f32 / pango2::SCALE as f32
+}
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    let filename = if args.len() > 1 {
+        &args[1]
+    } else {
+        "./fonts/NotoSerifDisplay/NotoSerifDisplay-VariableFont_wdth,wght.ttf"
+    };
+
+    if !Path::new(filename).exists() {
+        eprintln!("Error: provided font file '{}' does not exist, specify the location to the font as an argument", filename);
+        process::exit(1);
+    }
+
+    let lib = freetype::Library::init().unwrap();
+    let face = lib.face_from_file(filename, 0).unwrap();
+    let hb_face = harfbuzz::Face::from_ft(&face);
+    let font_map = pango2::Pango2FontMap::new();
+    font_map.add_face(&pango2::Pango2HbFace::from_hb_face(&hb_face, -2));
+    let pango_context = pango2::Pango2Context::from_font_map(&font_map);
+
+    let description = pango2::FontDescription::from_string(&format!("{} Regular 36", face.face_name()));
+    let font = match pango_context.load_font(&description) {
+        Some(font) => font.hb_font(),
+        None => {
+            eprintln!("Error: no font loaded for {}", description);
+            process::exit(1);
+        }
+    };
+
+    // the font of a Pango font is scaled to Pango units
+    let tags = [
+        MetricsTag::HorizontalAscender,
+        MetricsTag::HorizontalDescender,
+        MetricsTag::HorizontalLineGap,
+        MetricsTag::CapHeight,
+        MetricsTag::XHeight,
+        MetricsTag::SuperscriptEmYOffset,
+        MetricsTag::SubscriptEmYOffset,
+        MetricsTag::UnderlineOffset,
+        MetricsTag::StrikeoutOffset,
+    ];
+    for tag in tags {
+        println!("{:?}: {:?}", tag, font.metrics_position(tag).map(to_pixels));
+    }
+
+    let (cap_height, x_height) = match (
+        font.metrics_position(MetricsTag::CapHeight),
+        font.metrics_position(MetricsTag::XHeight),
+    ) {
+        (Some(cap_height), Some(x_height)) => (cap_height, x_height),
+        _ => {
+            eprintln!("Error: the font has no cap height or x-height in its OS/2 table");
+            process::exit(1);
+        }
+    };
+    let ascender = font.metrics_position_with_fallback(MetricsTag::HorizontalAscender);
+    let descender = font.metrics_position_with_fallback(MetricsTag::HorizontalDescender);
+    if !(ascender > cap_height && cap_height > x_height && x_height > 0 && descender < 0) {
+        eprintln!("Error: ascender, cap height, x-height and descender are out of order");
+        process::exit(1);
+    }
+    if font.metrics_position_with_fallback(MetricsTag::SuperscriptEmYOffset) <= 0 {
+        eprintln!("Error: superscripts are not raised");
+        process::exit(1);
+    }
+
+    let layout = pango2::Pango2Layout::new(&pango_context);
+    layout.set_text("Hxg vertical rhythm");
+    layout.set_font_description_string(&description.to_string());
+
+    // the flat top of the 'H' is at the cap height
+    let runs = layout.glyph_runs();
+    let extents = runs
+        .first()
+        .and_then(|run| run.glyphs.first())
+        .and_then(|glyph| font.glyph_extents(glyph.glyph));
+    match extents {
+        Some(extents) => {
+            println!("Top of the 'H' glyph: {}", to_pixels(extents.y_bearing));
+            if (extents.y_bearing - cap_height).abs() > cap_height / 50 {
+                eprintln!("Error: the cap height does not match the 'H' glyph");
+                process::exit(1);
+            }
+        }
+        None => {
+            eprintln!("Error: no extents for the 'H' glyph");
+            process::exit(1);
+        }
+    }
+
+    let regular = font.style();
+    println!("Regular style: {:?}", regular);
+    if (regular.weight - 400.0).abs() > 1.0 || regular.italic != 0.0 || regular.slant != 0.0 {
+        eprintln!("Error: unexpected style of the regular font");
+        process::exit(1);
+    }
+
+    let bold = harfbuzz::Font::new(&hb_face);
+    bold.set_variations("wght=700,wdth=75");
+    let style = bold.style();
+    println!("Bold condensed style: {:?}", style);
+    println!(
+        "Variation of cap height: {}, x-height: {}",
+        bold.metrics_variation(MetricsTag::CapHeight),
+        bold.metrics_variation(MetricsTag::XHeight)
+    );
+    if (style.weight - 700.0).abs() > 1.0 || (style.width - 75.0).abs() > 1.0 {
+        eprintln!("Error: the style does not reflect the font variations");
+        process::exit(1);
+    }
+
+    // draw guides at the baseline, x-height, cap height and ascender
+    let (_, logical) = layout.extents();
+    let (width, height) = (to_pixels(logical.width) as i32 + 20, to_pixels(logical.height) as i32 + 20);
+    let cairo_surface = cairo::CairoSurface::new_image_surface(width, height).unwrap();
+    let cairo_context = cairo::CairoContext::create(&cairo_surface);
+    pango_context.update_cairo_context(&cairo_context);
+    cairo_context.set_source_rgb(1.0, 1.0, 1.0);
+    cairo_context.paint();
+
+    let (x0, y0) = (10.0, 10.0);
+    let baseline = y0 + to_pixels(runs[0].baseline);
+    cairo_context.set_source_rgb(0.2, 0.4, 0.9);
+    cairo_context.set_line_width(1.0);
+    for position in [0, x_height, cap_height, ascender] {
+        let y = (baseline - to_pixels(position)).round() + 0.5;
+        cairo_context.move_to(0.0, y);
+        cairo_context.line_to(width as f32, y);
+        cairo_context.stroke();
+    }
+
+    cairo_context.set_source_rgb(0.0, 0.0, 0.0);
+    cairo_context.move_to(x0, y0);
+    layout.paint(&cairo_context);
+
+    let output = "ot_metrics_test.png";
+    println!("Write text with metric guides to file: {}", output);
+    cairo_surface.write_to_png(output).unwrap();
+}
@@ -0,0 +1,20 @@
+use std::fs::File;
+use pango2_sys_examples::cairo;
+
+fn main() {
+    let surface = cairo::CairoSurface::new_image_surface(64, 32).unwrap();
+    let cairo_context = cairo::CairoContext::create(&surface);
+    cairo_context.set_source_rgb(0.0, 0.5, 1.0);
+    cairo_context.paint();
+
+    let mut png = Vec::new();
+    surface.write_png(&mut png).unwrap();
+    println!("Encoded PNG in memory: {} bytes", png.len());
+
+    let decoded = cairo::CairoSurface::read_png(png.as_slice()).unwrap();
+    println!("Decoded PNG: {}x{} pixels", decoded.width(), decoded.height());
+
+    let output = "png_stream_test.png";
+    println!("Write decoded image to file: {}", output);
+    decoded.write_png(File::create(output).unwrap()).unwrap();
+}
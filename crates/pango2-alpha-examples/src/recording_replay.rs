@@ -0,0 +1,52 @@
+use std::{env, path::Path, process};
+use pango2_sys_examples::{freetype, harfbuzz, pango2, cairo};
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    let filename = if args.len() > 1 {
+        &args[1]
+    } else {
+        "./fonts/NotoSerifDisplay/NotoSerifDisplay-VariableFont_wdth,wght.ttf"
+    };
+
+    if !Path::new(filename).exists() {
+        eprintln!("Error: provided font file '{}' does not exist, specify the location to the font as an argument", filename);
+        process::exit(1);
+    }
+
+    let lib = freetype::Library::init().unwrap();
+    let face = lib.face_from_file(filename, 0).unwrap();
+    let hb_face = harfbuzz::Face::from_ft(&face);
+    let font_map = pango2::Pango2FontMap::new();
+    font_map.add_face(&pango2::Pango2HbFace::from_hb_face(&hb_face, -2));
+    let pango_context = pango2::Pango2Context::from_font_map(&font_map);
+
+    let layout = pango2::Pango2Layout::new(&pango_context);
+    layout.set_text("Hola, Pango2!");
+    layout.set_font_description_string(format!("{} Regular 32", face.face_name()).as_str());
+
+    let recording = layout.record().unwrap();
+    println!("Ink extents of the recording: {:?}", recording.ink_extents());
+    for scale in [1.0, 2.5] {
+        println!("Exact ink extents at scale {}: {:?}", scale, recording.exact_ink_extents(scale).unwrap());
+    }
+
+    // draw the label a few times at different positions and scales
+    let (width, height) = (650, 300);
+    let cairo_surface = cairo::CairoSurface::new_image_surface(width, height).unwrap();
+    let cairo_context = cairo::CairoContext::create(&cairo_surface);
+    cairo_context.set_source_rgb(1.0, 1.0, 1.0);
+    cairo_context.paint();
+    for (i, scale) in [0.5, 1.0, 1.5].iter().enumerate() {
+        cairo_context.save();
+        cairo_context.translate(20.0 + 60.0 * i as f32, 10.0 + 80.0 * i as f32);
+        cairo_context.scale(*scale, *scale);
+        cairo_context.set_source_rgb(0.0, 0.3 * i as f32, 0.8);
+        cairo_context.mask_surface(&recording, 0.0, 0.0);
+        cairo_context.restore();
+    }
+
+    let output = "recording_replay_test.png";
+    println!("Write replayed labels to file: {}", output);
+    cairo_surface.write_to_png(output).unwrap();
+}
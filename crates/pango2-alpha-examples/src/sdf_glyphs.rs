@@ -0,0 +1,68 @@
+use std::{env, path::Path, process};
+use pango2_sys_examples::{atlas, freetype, harfbuzz, pango2, sdf};
+
+/// FNV-1a, the checksums of the bundled font are compared with golden ones
+/// in tests/sdf_glyphs.rs.
+fn checksum(data: &[u8]) -> u64 {
+    data.iter().fold(0xcbf29ce484222325, |hash, byte| (hash ^ *byte as u64).wrapping_mul(0x100000001b3))
+}
+
+fn build_atlas(hb_face: &harfbuzz::Face, field_type: sdf::FieldType, glyphs: &[u32]) -> atlas::GlyphAtlas {
+    let generator = sdf::SdfGenerator::new(field_type, 4.0);
+    let mut glyph_atlas = atlas::GlyphAtlas::with_distance_field(hb_face, 32.0, generator, 128);
+    for glyph in glyphs {
+        glyph_atlas.glyph(*glyph, 0.0).unwrap();
+    }
+    glyph_atlas
+}
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    let filename = if args.len() > 1 {
+        &args[1]
+    } else {
+        "./fonts/NotoSerifDisplay/NotoSerifDisplay-VariableFont_wdth,wght.ttf"
+    };
+
+    if !Path::new(filename).exists() {
+        eprintln!("Error: provided font file '{}' does not exist, specify the location to the font as an argument", filename);
+        process::exit(1);
+    }
+
+    let lib = freetype::Library::init().unwrap();
+    let face = lib.face_from_file(filename, 0).unwrap();
+    let hb_face = harfbuzz::Face::from_ft(&face);
+    let font_map = pango2::Pango2FontMap::new();
+    font_map.add_face(&pango2::Pango2HbFace::from_hb_face(&hb_face, -2));
+    let pango_context = pango2::Pango2Context::from_font_map(&font_map);
+
+    let layout = pango2::Pango2Layout::new(&pango_context);
+    layout.set_text("Ag&");
+    layout.set_font_description_string(format!("{} Regular 32px", face.face_name()).as_str());
+    let glyphs: Vec<u32> = layout
+        .glyph_runs()
+        .iter()
+        .flat_map(|run| run.glyphs.iter().filter(|info| info.is_font_glyph()).map(|info| info.glyph))
+        .collect();
+
+    let font = atlas::scaled_font(&hb_face, 32.0);
+    let sdf_generator = sdf::SdfGenerator::new(sdf::FieldType::Sdf, 4.0);
+    let msdf_generator = sdf::SdfGenerator::new(sdf::FieldType::Msdf, 4.0);
+    for glyph in &glyphs {
+        let field = sdf_generator.generate(&font, *glyph, 0.0);
+        let multi = msdf_generator.generate(&font, *glyph, 0.0);
+        println!(
+            "Glyph {}: {}x{} at {},{}, SDF checksum {:016x}, MSDF checksum {:016x}",
+            glyph, field.width, field.height, field.bearing_x, field.bearing_y, checksum(&field.data), checksum(&multi.data)
+        );
+    }
+
+    for (field_type, output) in [(sdf::FieldType::Sdf, "sdf_atlas_test.png"), (sdf::FieldType::Msdf, "msdf_atlas_test.png")] {
+        let mut glyph_atlas = build_atlas(&hb_face, field_type, &glyphs);
+        let page_count = glyph_atlas.page_count();
+        let data = glyph_atlas.page_data(0).unwrap();
+        println!("{:?} atlas: {} pages, first page checksum {:016x}", field_type, page_count, checksum(data));
+        println!("Write first atlas page to file: {}", output);
+        glyph_atlas.page(0).unwrap().write_to_png(output).unwrap();
+    }
+}
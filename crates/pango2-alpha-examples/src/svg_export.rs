@@ -0,0 +1,48 @@
+use std::{fs, path::Path, process};
+use pango2_sys_examples::{freetype, harfbuzz, pango2};
+
+fn main() {
+    let fonts = [
+        "./fonts/NotoSerifDisplay/NotoSerifDisplay-VariableFont_wdth,wght.ttf",
+        "./fonts/Caveat/Caveat-VariableFont_wght.ttf",
+    ];
+
+    for filename in fonts {
+        if !Path::new(filename).exists() {
+            eprintln!("Error: font file '{}' does not exist, run the example from the crate directory", filename);
+            process::exit(1);
+        }
+    }
+
+    let lib = freetype::Library::init().unwrap();
+    let font_map = pango2::Pango2FontMap::new();
+    let mut families = Vec::new();
+    for filename in fonts {
+        println!("Loading font file: {}", filename);
+        let face = lib.face_from_file(filename, 0).unwrap();
+        let hb_face = harfbuzz::Face::from_ft(&face);
+        font_map.add_face(&pango2::Pango2HbFace::from_hb_face(&hb_face, -2));
+        families.push(face.face_name());
+    }
+
+    let pango_context = pango2::Pango2Context::from_font_map(&font_map);
+    let layout = pango2::Pango2Layout::new(&pango_context);
+
+    let text = "Outlined text, Caveat";
+    layout.set_text(text);
+    layout.set_font_description_string(format!("{} Regular 48", families[0]).as_str());
+
+    let caveat_start = text.find("Caveat").unwrap();
+    let attributes = pango2::AttrList::new();
+    attributes.insert(pango2::Attribute::foreground(pango2::Color::parse("#1a5fb4").unwrap()).with_range(0, caveat_start));
+    attributes.insert(pango2::Attribute::family(&families[1]).with_range(caveat_start, text.len()));
+    attributes.insert(pango2::Attribute::foreground(pango2::Color::parse("#c01c28").unwrap()).with_range(caveat_start, text.len()));
+    layout.set_attributes(&attributes);
+
+    let document = layout.to_svg_document();
+    println!("Path data: {}", layout.to_svg_path_data());
+
+    let output = "svg_export_test.svg";
+    println!("Write outlined text to file: {}", output);
+    fs::write(output, &document).unwrap();
+}
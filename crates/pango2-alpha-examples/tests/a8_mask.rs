@@ -0,0 +1,74 @@
+use pango2_sys_examples::{cairo, freetype, harfbuzz, pango2};
+
+const FONT: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/fonts/NotoSerifDisplay/NotoSerifDisplay-VariableFont_wdth,wght.ttf"
+);
+
+#[test]
+fn image_surfaces_have_the_requested_format() {
+    let formats = [
+        cairo::Format::Argb32,
+        cairo::Format::Rgb24,
+        cairo::Format::A8,
+        cairo::Format::A1,
+        cairo::Format::Rgb16_565,
+        cairo::Format::Rgb30,
+        cairo::Format::Rgb96f,
+        cairo::Format::Rgba128f,
+    ];
+    for format in formats {
+        let surface = cairo::CairoSurface::new_image_surface_with_format(format, 13, 7).unwrap();
+        assert_eq!(surface.format(), format);
+        assert_eq!(Some(surface.stride()), format.stride_for_width(13));
+    }
+    assert_eq!(cairo::Format::A8.stride_for_width(13), Some(16));
+    assert_eq!(cairo::Format::Invalid.stride_for_width(13), None);
+}
+
+#[test]
+fn text_renders_into_an_a8_mask() {
+    let lib = freetype::Library::init().unwrap();
+    let face = lib.face_from_file(FONT, 0).unwrap();
+    let hb_face = harfbuzz::Face::from_ft(&face);
+    let font_map = pango2::Pango2FontMap::new();
+    font_map.add_face(&pango2::Pango2HbFace::from_hb_face(&hb_face, -2));
+    let pango_context = pango2::Pango2Context::from_font_map(&font_map);
+
+    let (width, height) = (650, 150);
+    let mut surface =
+        cairo::CairoSurface::new_image_surface_with_format(cairo::Format::A8, width, height)
+            .unwrap();
+    let cairo_context = cairo::CairoContext::create(&surface);
+    pango_context.update_cairo_context(&cairo_context);
+
+    let layout = pango2::Pango2Layout::new(&pango_context);
+    layout.set_text("Hola, Pango2!");
+    layout.set_font_description_string(format!("{} Regular 64", face.face_name()).as_str());
+    layout.paint(&cairo_context);
+
+    // the context still references the surface, so its pixels can't be read
+    assert!(surface.data().is_none());
+    drop(cairo_context);
+
+    let stride = surface.stride();
+    let data = surface.data().unwrap();
+    assert_eq!(data.len(), stride * height as usize);
+    let coverage: Vec<u8> = data
+        .chunks(stride)
+        .flat_map(|row| row[..width as usize].iter().copied())
+        .collect();
+    let covered = coverage.iter().filter(|alpha| **alpha > 0).count();
+    let solid = coverage.iter().filter(|alpha| **alpha == 0xff).count();
+
+    // the text covers a part of the surface, with solid stems and
+    // antialiased edges
+    let ratio = covered as f32 / coverage.len() as f32;
+    assert!(
+        (0.02..0.6).contains(&ratio),
+        "covered {:.1}% of the surface",
+        ratio * 100.0
+    );
+    assert!(solid > 0, "no pixel is fully covered");
+    assert!(solid < covered, "no pixel is antialiased");
+}
@@ -0,0 +1,76 @@
+mod common;
+
+use common::TestFont;
+use pango2_sys_examples::pango2;
+
+/// The "Face" entry of the "Light" and "Dark" palettes of the test font.
+const LIGHT_FACE: u32 = 0xffcc33;
+const DARK_FACE: u32 = 0xe0a800;
+
+/// Counts the pixels that are neither transparent nor a shade of gray,
+/// i.e. the pixels that have been drawn in color.
+fn count_color_pixels(pixels: &[u32]) -> usize {
+    pixels
+        .iter()
+        .filter(|pixel| {
+            let (a, r, g, b) = (
+                *pixel >> 24,
+                (*pixel >> 16) & 0xff,
+                (*pixel >> 8) & 0xff,
+                *pixel & 0xff,
+            );
+            a > 0 && (r != g || g != b)
+        })
+        .count()
+}
+
+/// Renders the emoji of the test font with the palette `palette` selected.
+fn render_emoji(palette: u32) -> Vec<u32> {
+    let font = TestFont::load_with(common::COLOR_TEST, |face| {
+        face.set_palette_name("selected", palette);
+    });
+    font.context.set_palette("selected");
+    font.context
+        .set_emoji_presentation(pango2::EmojiPresentation::Emoji);
+    let layout = font.layout("😀🎉🌈", "64");
+    let mut surface = common::render(&font.context, &layout, 400, 150);
+    common::pixels(&mut surface)
+}
+
+#[test]
+fn test_font_has_named_palettes() {
+    let font = TestFont::load(common::COLOR_TEST);
+    assert!(font.hb_face.has_color_layers());
+    let palettes = font.hb_face.palettes();
+    let names: Vec<_> = palettes
+        .iter()
+        .map(|palette| palette.name.as_deref())
+        .collect();
+    assert_eq!(names, [Some("Light"), Some("Dark")]);
+    assert!(palettes[0].usable_with_light_background && !palettes[0].usable_with_dark_background);
+    assert!(palettes[1].usable_with_dark_background && !palettes[1].usable_with_light_background);
+}
+
+#[test]
+fn color_glyphs_render_in_color() {
+    // the text is drawn in black, so any colored pixel comes from the font
+    let pixels = render_emoji(0);
+    assert!(
+        count_color_pixels(&pixels) > 0,
+        "the emoji were not rendered in color"
+    );
+}
+
+#[test]
+fn selected_palette_is_used() {
+    let light = render_emoji(0);
+    let dark = render_emoji(1);
+    let count = |pixels: &[u32], rgb| {
+        pixels
+            .iter()
+            .filter(|pixel| common::is_opaque(**pixel, rgb))
+            .count()
+    };
+    assert!(count(&light, LIGHT_FACE) > 0 && count(&light, DARK_FACE) == 0);
+    assert!(count(&dark, DARK_FACE) > 0 && count(&dark, LIGHT_FACE) == 0);
+}
@@ -0,0 +1,96 @@
+//! Fonts and pixel helpers shared by the integration tests.
+#![allow(dead_code)]
+
+use pango2_sys_examples::{cairo, freetype, harfbuzz, pango2};
+
+pub const NOTO_SERIF_DISPLAY: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/fonts/NotoSerifDisplay/NotoSerifDisplay-VariableFont_wdth,wght.ttf"
+);
+pub const CAVEAT: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/fonts/Caveat/Caveat-VariableFont_wght.ttf"
+);
+pub const COLOR_TEST: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/fonts/ColorTest/ColorTest-Regular.ttf"
+);
+
+/// A font file added to a font map of its own. The fields are dropped in
+/// declaration order, so the FreeType library outlives the face.
+pub struct TestFont {
+    pub context: pango2::Pango2Context,
+    pub font_map: pango2::Pango2FontMap,
+    pub hb_face: harfbuzz::Face,
+    pub face: freetype::Face,
+    library: freetype::Library,
+}
+
+impl TestFont {
+    pub fn load(path: &str) -> Self {
+        Self::load_with(path, |_| {})
+    }
+
+    /// Loads the font and lets `configure` set up the Pango face before it
+    /// is added to the font map, e.g. to name palettes.
+    pub fn load_with(path: &str, configure: impl FnOnce(&pango2::Pango2HbFace)) -> Self {
+        let library = freetype::Library::init().unwrap();
+        let face = library.face_from_file(path, 0).unwrap();
+        let hb_face = harfbuzz::Face::from_ft(&face);
+        let pango_face = pango2::Pango2HbFace::from_hb_face(&hb_face, -2);
+        configure(&pango_face);
+        let font_map = pango2::Pango2FontMap::new();
+        font_map.add_face(&pango_face);
+        let context = pango2::Pango2Context::from_font_map(&font_map);
+        TestFont {
+            context,
+            font_map,
+            hb_face,
+            face,
+            library,
+        }
+    }
+
+    /// A layout of `text` in this font, `style` is the rest of the font
+    /// description, e.g. "Regular 32".
+    pub fn layout(&self, text: &str, style: &str) -> pango2::Pango2Layout {
+        let layout = pango2::Pango2Layout::new(&self.context);
+        layout.set_text(text);
+        layout.set_font_description_string(format!("{} {}", self.face.face_name(), style).as_str());
+        layout
+    }
+}
+
+/// Paints `layout` in black onto a transparent `Argb32` surface.
+pub fn render(
+    context: &pango2::Pango2Context,
+    layout: &pango2::Pango2Layout,
+    width: i32,
+    height: i32,
+) -> cairo::CairoSurface {
+    let surface = cairo::CairoSurface::new_image_surface(width, height).unwrap();
+    let cairo_context = cairo::CairoContext::create(&surface);
+    context.update_cairo_context(&cairo_context);
+    cairo_context.set_source_rgb(0.0, 0.0, 0.0);
+    layout.paint(&cairo_context);
+    surface
+}
+
+/// The premultiplied, native-endian pixels of an `Argb32` surface without
+/// row padding.
+pub fn pixels(surface: &mut cairo::CairoSurface) -> Vec<u32> {
+    let (width, stride) = (surface.width() as usize, surface.stride());
+    let data = surface.data().unwrap();
+    data.chunks(stride)
+        .flat_map(|row| {
+            row[..width * 4]
+                .chunks_exact(4)
+                .map(|pixel| u32::from_ne_bytes([pixel[0], pixel[1], pixel[2], pixel[3]]))
+        })
+        .collect()
+}
+
+/// Returns true for an opaque pixel of the color `rgb`, e.g. `0xe63946`.
+pub fn is_opaque(pixel: u32, rgb: u32) -> bool {
+    pixel == 0xff00_0000 | rgb
+}
@@ -0,0 +1,131 @@
+mod common;
+
+use common::TestFont;
+use pango2_sys_examples::{cairo, harfbuzz, pango2};
+
+/// The color replacing palette entry 2 ("Accent") of the test font.
+const BRAND_BLUE: harfbuzz::Color = harfbuzz::Color {
+    red: 0x00,
+    green: 0x52,
+    blue: 0xcc,
+    alpha: 0xff,
+};
+
+fn rgb(color: harfbuzz::Color) -> u32 {
+    u32::from_be_bytes([0, color.red, color.green, color.blue])
+}
+
+/// Counts the opaque pixels of the color in the columns `columns` of a
+/// surface `width` pixels wide.
+fn count(
+    pixels: &[u32],
+    width: usize,
+    columns: std::ops::Range<usize>,
+    color: harfbuzz::Color,
+) -> usize {
+    pixels
+        .iter()
+        .enumerate()
+        .filter(|(index, pixel)| {
+            columns.contains(&(index % width)) && common::is_opaque(**pixel, rgb(color))
+        })
+        .count()
+}
+
+#[test]
+fn context_override_replaces_the_palette_entry() {
+    let font = TestFont::load(common::COLOR_TEST);
+    let accent = font.hb_face.palette_colors(0)[2];
+
+    let layout = font.layout("🌈", "64");
+    let mut surface = common::render(&font.context, &layout, 100, 100);
+    let pixels = common::pixels(&mut surface);
+    assert!(count(&pixels, 100, 0..100, accent) > 0);
+    assert_eq!(count(&pixels, 100, 0..100, BRAND_BLUE), 0);
+
+    let options = cairo::FontOptions::new();
+    options.set_custom_palette_color(
+        2,
+        BRAND_BLUE.red as f32 / 255.0,
+        BRAND_BLUE.green as f32 / 255.0,
+        BRAND_BLUE.blue as f32 / 255.0,
+        1.0,
+    );
+    font.context.set_font_options(&options);
+    let layout = font.layout("🌈", "64");
+    let mut surface = common::render(&font.context, &layout, 100, 100);
+    let pixels = common::pixels(&mut surface);
+    assert!(
+        count(&pixels, 100, 0..100, BRAND_BLUE) > 0,
+        "the override was not drawn"
+    );
+    assert_eq!(count(&pixels, 100, 0..100, accent), 0);
+}
+
+#[test]
+fn custom_palette_applies_to_its_range() {
+    let font = TestFont::load(common::COLOR_TEST);
+    let accent = font.hb_face.palette_colors(0)[2];
+
+    // a copy of the first palette with entry 2 in the brand color
+    let brand_palette = font.hb_face.palettes().len() as u32;
+    let themed_face = font
+        .hb_face
+        .with_custom_palettes(&[harfbuzz::CustomPalette {
+            base: 0,
+            colors: vec![(2, BRAND_BLUE)],
+        }])
+        .unwrap();
+    assert_eq!(themed_face.palettes().len() as u32, brand_palette + 1);
+    assert_eq!(themed_face.palette_colors(brand_palette)[2], BRAND_BLUE);
+    assert_eq!(
+        themed_face.palette_colors(0),
+        font.hb_face.palette_colors(0)
+    );
+
+    let pango_face = pango2::Pango2HbFace::from_hb_face(&themed_face, -2);
+    pango_face.set_palette_name("brand", brand_palette);
+    let font_map = pango2::Pango2FontMap::new();
+    font_map.add_face(&pango_face);
+    let context = pango2::Pango2Context::from_font_map(&font_map);
+
+    // only the second rainbow, bytes 4 to 8, uses the brand palette
+    let layout = pango2::Pango2Layout::new(&context);
+    layout.set_text("🌈🌈");
+    layout.set_font_description_string(format!("{} 64", font.face.face_name()).as_str());
+    let attributes = pango2::AttrList::new();
+    attributes.insert(pango2::Attribute::palette("brand").with_range(4, 8));
+    layout.set_attributes(&attributes);
+    let mut surface = common::render(&context, &layout, 200, 100);
+    let pixels = common::pixels(&mut surface);
+
+    // the first glyph is drawn at x 0..64, the second one at x 64..128
+    assert!(count(&pixels, 200, 0..64, accent) > 0);
+    assert_eq!(count(&pixels, 200, 0..64, BRAND_BLUE), 0);
+    assert_eq!(count(&pixels, 200, 64..128, accent), 0);
+    assert!(
+        count(&pixels, 200, 64..128, BRAND_BLUE) > 0,
+        "the custom palette was not drawn"
+    );
+}
+
+#[test]
+fn custom_palettes_are_validated() {
+    let font = TestFont::load(common::COLOR_TEST);
+    let entries = font.hb_face.palette_colors(0).len() as u32;
+    let palette = |base, index| harfbuzz::CustomPalette {
+        base,
+        colors: vec![(index, BRAND_BLUE)],
+    };
+    assert!(font
+        .hb_face
+        .with_custom_palettes(&[palette(2, 0)])
+        .is_none());
+    assert!(font
+        .hb_face
+        .with_custom_palettes(&[palette(0, entries)])
+        .is_none());
+    // the palettes and colors of a CPAL table are counted in 16 bits
+    let too_many = vec![palette(0, 0); 65535 / entries as usize];
+    assert!(font.hb_face.with_custom_palettes(&too_many).is_none());
+}
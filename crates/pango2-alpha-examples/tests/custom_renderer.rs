@@ -0,0 +1,220 @@
+mod common;
+
+use std::panic::{self, AssertUnwindSafe};
+
+use common::TestFont;
+use pango2_sys_examples::{harfbuzz, pango2};
+
+/// A call received by the recording renderer.
+#[derive(Debug, Clone, PartialEq)]
+enum Call {
+    PrepareRun {
+        foreground: Option<pango2::Color>,
+        glyphs: usize,
+    },
+    DrawGlyphs {
+        glyphs: Vec<pango2::GlyphInfo>,
+        x: i32,
+        y: i32,
+    },
+    DrawRectangle {
+        part: pango2::RenderPart,
+        rect: pango2::Rectangle,
+    },
+    DrawStyledLine {
+        part: pango2::RenderPart,
+        style: pango2::LineStyle,
+        rect: pango2::Rectangle,
+    },
+    DrawTrapezoid {
+        part: pango2::RenderPart,
+        trapezoid: pango2::Trapezoid,
+    },
+}
+
+/// Records every call, panics when drawing glyphs if `panic_on_glyphs` is
+/// set.
+#[derive(Default)]
+struct RecordingRenderer {
+    calls: Vec<Call>,
+    panic_on_glyphs: bool,
+}
+
+impl pango2::Renderer for RecordingRenderer {
+    fn prepare_run(&mut self, run: &pango2::RenderRun<'_>) {
+        self.calls.push(Call::PrepareRun {
+            foreground: run.color(pango2::RenderPart::Foreground),
+            glyphs: run.glyphs().len(),
+        });
+    }
+
+    fn draw_glyphs(
+        &mut self,
+        _font: &harfbuzz::Font,
+        glyphs: &[pango2::GlyphInfo],
+        x: i32,
+        y: i32,
+    ) {
+        self.calls.push(Call::DrawGlyphs {
+            glyphs: glyphs.to_vec(),
+            x,
+            y,
+        });
+        if self.panic_on_glyphs {
+            panic!("renderer panic");
+        }
+    }
+
+    fn draw_rectangle(&mut self, part: pango2::RenderPart, rect: pango2::Rectangle) {
+        self.calls.push(Call::DrawRectangle { part, rect });
+    }
+
+    fn draw_styled_line(
+        &mut self,
+        part: pango2::RenderPart,
+        style: pango2::LineStyle,
+        rect: pango2::Rectangle,
+    ) {
+        self.calls.push(Call::DrawStyledLine { part, style, rect });
+    }
+
+    fn draw_trapezoid(&mut self, part: pango2::RenderPart, trapezoid: pango2::Trapezoid) {
+        self.calls.push(Call::DrawTrapezoid { part, trapezoid });
+    }
+}
+
+const RED: &str = "#c01c28";
+
+/// An underlined "Hola", a red "Pango2" on a yellow background.
+fn styled_layout(font: &TestFont) -> pango2::Pango2Layout {
+    let layout = font.layout("Hola, Pango2!", "Regular 32");
+    let attributes = pango2::AttrList::new();
+    attributes.insert(pango2::Attribute::underline(pango2::LineStyle::Solid).with_range(0, 4));
+    attributes.insert(
+        pango2::Attribute::foreground(pango2::Color::parse(RED).unwrap()).with_range(6, 12),
+    );
+    attributes.insert(
+        pango2::Attribute::background(pango2::Color::parse("#f6d32d").unwrap()).with_range(6, 12),
+    );
+    layout.set_attributes(&attributes);
+    layout
+}
+
+fn first_glyphs(calls: &[Call]) -> Option<(i32, i32)> {
+    calls.iter().find_map(|call| match call {
+        Call::DrawGlyphs { x, y, .. } => Some((*x, *y)),
+        _ => None,
+    })
+}
+
+#[test]
+fn runs_are_prepared_before_their_glyphs() {
+    let font = TestFont::load(common::NOTO_SERIF_DISPLAY);
+    let layout = styled_layout(&font);
+    let mut renderer = pango2::CustomRenderer::new(RecordingRenderer::default());
+    renderer.draw_layout(&layout, 0, 0);
+    let calls = &renderer.renderer().calls;
+
+    assert!(
+        matches!(calls.first(), Some(Call::PrepareRun { .. })),
+        "{calls:?}"
+    );
+    let mut prepared = None;
+    for call in calls {
+        match call {
+            Call::PrepareRun { glyphs, .. } => prepared = Some(*glyphs),
+            Call::DrawGlyphs { glyphs, .. } => assert_eq!(prepared, Some(glyphs.len())),
+            _ => {}
+        }
+    }
+
+    let drawn: usize = calls
+        .iter()
+        .map(|call| match call {
+            Call::DrawGlyphs { glyphs, .. } => glyphs.len(),
+            _ => 0,
+        })
+        .sum();
+    let shaped: usize = layout.glyph_runs().iter().map(|run| run.glyphs.len()).sum();
+    assert_eq!(drawn, shaped);
+}
+
+#[test]
+fn colors_and_decorations_are_drawn() {
+    let font = TestFont::load(common::NOTO_SERIF_DISPLAY);
+    let layout = styled_layout(&font);
+    let mut renderer = pango2::CustomRenderer::new(RecordingRenderer::default());
+    renderer.draw_layout(&layout, 0, 0);
+    let calls = &renderer.renderer().calls;
+
+    let red = pango2::Color::parse(RED).unwrap();
+    assert!(
+        calls.iter().any(|call| matches!(
+            call,
+            Call::PrepareRun { foreground: Some(color), .. } if *color == red
+        )),
+        "no run has the red foreground: {calls:?}"
+    );
+    assert!(
+        calls.iter().any(|call| matches!(
+            call,
+            Call::DrawRectangle {
+                part: pango2::RenderPart::Background,
+                ..
+            }
+        )),
+        "the background was not drawn: {calls:?}"
+    );
+    assert!(
+        calls.iter().any(|call| matches!(
+            call,
+            Call::DrawStyledLine {
+                part: pango2::RenderPart::Underline,
+                style: pango2::LineStyle::Solid,
+                ..
+            } | Call::DrawRectangle {
+                part: pango2::RenderPart::Underline,
+                ..
+            }
+        )),
+        "the underline was not drawn: {calls:?}"
+    );
+}
+
+#[test]
+fn offset_moves_the_glyphs() {
+    let font = TestFont::load(common::NOTO_SERIF_DISPLAY);
+    let layout = styled_layout(&font);
+    let mut renderer = pango2::CustomRenderer::new(RecordingRenderer::default());
+    renderer.draw_layout(&layout, 0, 0);
+    let (x, y) = first_glyphs(&renderer.renderer().calls).unwrap();
+
+    renderer.renderer_mut().calls.clear();
+    renderer.draw_layout(&layout, 10 * pango2::SCALE, 5 * pango2::SCALE);
+    assert_eq!(
+        first_glyphs(&renderer.renderer().calls),
+        Some((x + 10 * pango2::SCALE, y + 5 * pango2::SCALE))
+    );
+}
+
+#[test]
+fn renderer_panic_is_resumed_after_drawing() {
+    let font = TestFont::load(common::NOTO_SERIF_DISPLAY);
+    let layout = styled_layout(&font);
+    let mut renderer = pango2::CustomRenderer::new(RecordingRenderer {
+        panic_on_glyphs: true,
+        ..RecordingRenderer::default()
+    });
+    let result = panic::catch_unwind(AssertUnwindSafe(|| renderer.draw_layout(&layout, 0, 0)));
+    let payload = result.unwrap_err();
+    assert_eq!(payload.downcast_ref::<&str>(), Some(&"renderer panic"));
+    // the callbacks after the panic are skipped
+    let calls = &renderer.renderer().calls;
+    assert!(matches!(calls.last(), Some(Call::DrawGlyphs { .. })));
+
+    // the renderer can draw again afterwards
+    renderer.renderer_mut().panic_on_glyphs = false;
+    renderer.renderer_mut().calls.clear();
+    renderer.draw_layout(&layout, 0, 0);
+    assert!(first_glyphs(&renderer.renderer().calls).is_some());
+}
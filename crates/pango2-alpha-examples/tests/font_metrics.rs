@@ -0,0 +1,57 @@
+mod common;
+
+use common::TestFont;
+use pango2_sys_examples::pango2;
+
+fn description(font: &TestFont) -> pango2::FontDescription {
+    pango2::FontDescription::from_string(&format!("{} Regular 24", font.face.face_name()))
+}
+
+#[test]
+fn metrics_have_the_expected_signs() {
+    let font = TestFont::load(common::NOTO_SERIF_DISPLAY);
+    let metrics = font
+        .context
+        .metrics(&description(&font), pango2::Language::from_string("en"));
+    assert!(metrics.ascent > 0 && metrics.descent > 0, "{metrics:?}");
+    assert!(metrics.height >= metrics.ascent, "{metrics:?}");
+    assert!(metrics.approximate_char_width > 0 && metrics.approximate_digit_width > 0);
+    // underlines sit below the baseline, strikethroughs above it
+    assert!(metrics.underline_position < 0, "{metrics:?}");
+    assert!(metrics.strikethrough_position > 0, "{metrics:?}");
+    assert!(metrics.underline_thickness > 0 && metrics.strikethrough_thickness > 0);
+}
+
+#[test]
+fn loaded_font_matches_the_context() {
+    let font = TestFont::load(common::NOTO_SERIF_DISPLAY);
+    let description = description(&font);
+    let loaded = font.context.load_font(&description).unwrap();
+    assert_eq!(loaded.size(), 24 * pango2::SCALE);
+    // the font map has a single face, so the font has the metrics of the
+    // fontset
+    let metrics = font
+        .context
+        .metrics(&description, pango2::Language::from_string("en"));
+    let font_metrics = loaded.metrics(None);
+    assert_eq!(
+        (font_metrics.ascent, font_metrics.descent),
+        (metrics.ascent, metrics.descent)
+    );
+}
+
+#[test]
+fn capitals_stand_on_the_baseline() {
+    let font = TestFont::load(common::NOTO_SERIF_DISPLAY);
+    let description = description(&font);
+    let loaded = font.context.load_font(&description).unwrap();
+    let metrics = loaded.metrics(None);
+    let layout = font.layout("H", "Regular 24");
+    let runs = layout.glyph_runs();
+    let glyph = runs[0].glyphs[0].glyph;
+
+    let (ink, logical) = loaded.glyph_extents(glyph);
+    assert!(ink.y < 0 && -ink.y <= metrics.ascent, "{ink:?}");
+    assert!((ink.y + ink.height).abs() <= pango2::SCALE, "{ink:?}");
+    assert!(logical.width > 0, "{logical:?}");
+}
@@ -0,0 +1,152 @@
+mod common;
+
+use common::TestFont;
+use pango2_sys_examples::atlas::{AtlasGlyph, GlyphAtlas, UvRect};
+use pango2_sys_examples::cairo::Format;
+
+/// Glyph IDs of the color test font, see fonts/build_test_fonts.py.
+const NOTDEF: u32 = 0;
+const RAINBOW: u32 = 2;
+
+const SIZE: f32 = 32.0;
+const PAGE_SIZE: i32 = 64;
+
+fn uv(x: i32, y: i32, width: i32, height: i32) -> UvRect {
+    let size = PAGE_SIZE as f32;
+    UvRect {
+        u0: x as f32 / size,
+        v0: y as f32 / size,
+        u1: (x + width) as f32 / size,
+        v1: (y + height) as f32 / size,
+    }
+}
+
+/// The top left corner of a glyph on its page in pixels.
+fn position(glyph: &AtlasGlyph) -> (i32, i32) {
+    let size = PAGE_SIZE as f32;
+    (
+        (glyph.uv.u0 * size).round() as i32,
+        (glyph.uv.v0 * size).round() as i32,
+    )
+}
+
+#[test]
+fn glyphs_are_packed_on_shelves() {
+    let font = TestFont::load(common::COLOR_TEST);
+    let mut atlas = GlyphAtlas::new(&font.hb_face, SIZE, Format::A8, PAGE_SIZE).unwrap();
+
+    // the .notdef box spans 128..896 by -64..832 of 1024 units per em,
+    // 4..28 by -2..26 pixels at 32 pixels per em
+    let notdef = atlas.glyph(NOTDEF, 0.0).unwrap();
+    assert_eq!(
+        notdef,
+        AtlasGlyph {
+            page: 0,
+            uv: uv(1, 1, 24, 28),
+            width: 24,
+            height: 28,
+            bearing_x: 4,
+            bearing_y: -26,
+        }
+    );
+    assert_eq!(atlas.glyph(NOTDEF, 0.0).unwrap(), notdef);
+    assert_eq!(atlas.page_count(), 1);
+
+    // every subpixel position is rasterized again and is a pixel wider
+    atlas.set_subpixel_positions(4);
+    let positions: Vec<_> = [0.5, 0.75, 0.25]
+        .iter()
+        .map(|&offset| {
+            let glyph = atlas.glyph(NOTDEF, offset).unwrap();
+            assert_eq!((glyph.page, glyph.width, glyph.height), (0, 25, 28));
+            let (x, y) = position(&glyph);
+            assert_eq!(glyph.uv, uv(x, y, 25, 28));
+            (x, y)
+        })
+        .collect();
+    // the second one starts a new shelf, the third one fits next to it
+    assert_eq!(positions, [(27, 1), (1, 31), (28, 31)]);
+
+    // the page is full, the rainbow opens a new one
+    let rainbow = atlas.glyph(RAINBOW, 0.0).unwrap();
+    assert_eq!(rainbow.page, 1);
+    assert_eq!(position(&rainbow), (1, 1));
+    assert_eq!(rainbow.uv, uv(1, 1, rainbow.width, rainbow.height));
+    assert_eq!(atlas.page_count(), 2);
+}
+
+#[test]
+fn coverage_stays_inside_the_padding() {
+    let font = TestFont::load(common::COLOR_TEST);
+    let mut atlas = GlyphAtlas::new(&font.hb_face, SIZE, Format::A8, PAGE_SIZE).unwrap();
+    let notdef = atlas.glyph(NOTDEF, 0.0).unwrap();
+    let (x, y) = position(&notdef);
+    let stride = atlas.page(0).unwrap().stride();
+    let data = atlas.page_data(0).unwrap();
+    let coverage = |column: i32, row: i32| data[row as usize * stride + column as usize];
+
+    for column in 0..PAGE_SIZE {
+        for row in 0..PAGE_SIZE {
+            let inside =
+                (x..x + notdef.width).contains(&column) && (y..y + notdef.height).contains(&row);
+            if !inside {
+                assert_eq!(coverage(column, row), 0, "ink at {column}, {row}");
+            }
+        }
+    }
+    // the box is two pixels wide around a hole, both on whole pixels
+    assert_eq!(coverage(x, y), 255);
+    assert_eq!(coverage(x + 1, y + 1), 255);
+    assert_eq!(coverage(x + 2, y + 2), 0);
+}
+
+#[test]
+fn color_glyphs_keep_their_colors() {
+    let font = TestFont::load(common::COLOR_TEST);
+    let mut atlas = GlyphAtlas::new(&font.hb_face, SIZE, Format::Argb32, PAGE_SIZE).unwrap();
+    let rainbow = atlas.glyph(RAINBOW, 0.0).unwrap();
+    let (x, y) = position(&rainbow);
+    let stride = atlas.page(0).unwrap().stride();
+    let data = atlas.page_data(0).unwrap();
+    let palette = font.hb_face.palette_colors(0);
+
+    let mut colors = Vec::new();
+    for row in y..y + rainbow.height {
+        for column in x..x + rainbow.width {
+            let start = row as usize * stride + column as usize * 4;
+            let pixel = u32::from_ne_bytes(data[start..start + 4].try_into().unwrap());
+            for color in &palette {
+                let rgb = u32::from_be_bytes([0, color.red, color.green, color.blue]);
+                if common::is_opaque(pixel, rgb) && !colors.contains(&rgb) {
+                    colors.push(rgb);
+                }
+            }
+        }
+    }
+    // the arcs in the entries Accent, Face, Green and Blue
+    colors.sort_unstable();
+    let mut expected: Vec<_> = [2, 0, 3, 4]
+        .iter()
+        .map(|&index| {
+            let color = palette[index];
+            u32::from_be_bytes([0, color.red, color.green, color.blue])
+        })
+        .collect();
+    expected.sort_unstable();
+    assert_eq!(colors, expected);
+}
+
+#[test]
+fn layout_quads_place_every_glyph() {
+    let font = TestFont::load(common::COLOR_TEST);
+    let mut atlas = GlyphAtlas::new(&font.hb_face, SIZE, Format::Argb32, 256).unwrap();
+    let layout = font.layout("😀🎉🌈", "32px");
+    let quads = atlas.layout_quads(&layout).unwrap();
+    assert_eq!(quads.len(), 3);
+    assert_eq!(atlas.page_count(), 1);
+    // the glyphs are an em apart and use their own spots on the page
+    for (quad, next) in quads.iter().zip(&quads[1..]) {
+        assert!(next.x > quad.x);
+        assert_ne!(quad.uv, next.uv);
+    }
+}
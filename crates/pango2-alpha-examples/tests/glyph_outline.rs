@@ -0,0 +1,91 @@
+mod common;
+
+use std::panic::{self, AssertUnwindSafe};
+
+use common::TestFont;
+use pango2_sys_examples::harfbuzz::{self, OutlineSink, PathBuilder, PathCommand};
+
+/// The .notdef glyph of the color test font, a box with a hole, see
+/// fonts/build_test_fonts.py.
+const NOTDEF: u32 = 0;
+
+fn notdef_commands(scale: f32) -> Vec<PathCommand> {
+    let point = |x: f32, y: f32| (x * scale, y * scale);
+    let mut commands = Vec::new();
+    for contour in [
+        [
+            (128.0, -64.0),
+            (128.0, 832.0),
+            (896.0, 832.0),
+            (896.0, -64.0),
+        ],
+        [(192.0, 0.0), (832.0, 0.0), (832.0, 768.0), (192.0, 768.0)],
+    ] {
+        let (x, y) = point(contour[0].0, contour[0].1);
+        commands.push(PathCommand::MoveTo(x, y));
+        // HarfBuzz closes the contour with a line back to its start
+        for &(x, y) in contour[1..].iter().chain(&contour[..1]) {
+            let (x, y) = point(x, y);
+            commands.push(PathCommand::LineTo(x, y));
+        }
+        commands.push(PathCommand::ClosePath);
+    }
+    commands
+}
+
+#[test]
+fn path_builder_collects_the_outline() {
+    let font = TestFont::load(common::COLOR_TEST);
+    let hb_font = harfbuzz::Font::new(&font.hb_face);
+
+    let mut builder = PathBuilder::new();
+    hb_font.draw_glyph(NOTDEF, &mut builder);
+    assert_eq!(builder.commands(), notdef_commands(1.0));
+
+    // the outline is scaled from 1024 units per em
+    hb_font.set_scale(2048, 2048);
+    let mut builder = PathBuilder::new();
+    hb_font.draw_glyph(NOTDEF, &mut builder);
+    assert_eq!(builder.into_commands(), notdef_commands(2.0));
+}
+
+/// Panics on the first line.
+#[derive(Default)]
+struct PanickingSink {
+    calls: usize,
+}
+
+impl OutlineSink for PanickingSink {
+    fn move_to(&mut self, _x: f32, _y: f32) {
+        self.calls += 1;
+    }
+
+    fn line_to(&mut self, _x: f32, _y: f32) {
+        self.calls += 1;
+        panic!("sink panic");
+    }
+
+    fn quadratic_to(&mut self, _control: (f32, f32), _to: (f32, f32)) {
+        self.calls += 1;
+    }
+
+    fn cubic_to(&mut self, _control1: (f32, f32), _control2: (f32, f32), _to: (f32, f32)) {
+        self.calls += 1;
+    }
+
+    fn close_path(&mut self) {
+        self.calls += 1;
+    }
+}
+
+#[test]
+fn sink_panic_is_resumed_after_drawing() {
+    let font = TestFont::load(common::COLOR_TEST);
+    let hb_font = harfbuzz::Font::new(&font.hb_face);
+    let mut sink = PanickingSink::default();
+    let result = panic::catch_unwind(AssertUnwindSafe(|| hb_font.draw_glyph(NOTDEF, &mut sink)));
+    let payload = result.unwrap_err();
+    assert_eq!(payload.downcast_ref::<&str>(), Some(&"sink panic"));
+    // the callbacks after the panic are skipped
+    assert_eq!(sink.calls, 2);
+}
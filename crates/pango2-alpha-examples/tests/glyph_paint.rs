@@ -0,0 +1,185 @@
+mod common;
+
+use std::panic::{self, AssertUnwindSafe};
+
+use common::TestFont;
+use pango2_sys_examples::harfbuzz::{self, Color, CompositeMode, GlyphPainter, Transform};
+
+/// Glyph IDs of the test font, see fonts/build_test_fonts.py: the color
+/// glyphs in code point order follow .notdef and space, then their layers.
+const NOTDEF: u32 = 0;
+const RAINBOW: u32 = 2;
+const RAINBOW_LAYERS: [u32; 4] = [5, 6, 7, 8];
+const GRINNING_FACE: u32 = 4;
+const GRINNING_FACE_LAYERS: [u32; 3] = [13, 14, 15];
+
+const FOREGROUND: Color = Color {
+    red: 0x12,
+    green: 0x34,
+    blue: 0x56,
+    alpha: 0xff,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+enum Call {
+    PushTransform(Transform),
+    PopTransform,
+    PushClipGlyph(u32),
+    PushClipRectangle,
+    PopClip,
+    Color(bool, Color),
+    Gradient,
+    PushGroup,
+    PopGroup(CompositeMode),
+}
+
+/// Records the calls, panics on the first color if `panic_on_color` is set.
+#[derive(Default)]
+struct Recorder {
+    calls: Vec<Call>,
+    panic_on_color: bool,
+}
+
+impl GlyphPainter for Recorder {
+    fn push_transform(&mut self, transform: Transform) {
+        self.calls.push(Call::PushTransform(transform));
+    }
+
+    fn pop_transform(&mut self) {
+        self.calls.push(Call::PopTransform);
+    }
+
+    fn push_clip_glyph(&mut self, glyph: u32) {
+        self.calls.push(Call::PushClipGlyph(glyph));
+    }
+
+    fn push_clip_rectangle(&mut self, _xmin: f32, _ymin: f32, _xmax: f32, _ymax: f32) {
+        self.calls.push(Call::PushClipRectangle);
+    }
+
+    fn pop_clip(&mut self) {
+        self.calls.push(Call::PopClip);
+    }
+
+    fn color(&mut self, is_foreground: bool, color: Color) {
+        self.calls.push(Call::Color(is_foreground, color));
+        if self.panic_on_color {
+            panic!("painter panic");
+        }
+    }
+
+    fn linear_gradient(
+        &mut self,
+        _color_line: &harfbuzz::ColorLine,
+        _p0: (f32, f32),
+        _p1: (f32, f32),
+        _p2: (f32, f32),
+    ) {
+        self.calls.push(Call::Gradient);
+    }
+
+    fn push_group(&mut self) {
+        self.calls.push(Call::PushGroup);
+    }
+
+    fn pop_group(&mut self, mode: CompositeMode) {
+        self.calls.push(Call::PopGroup(mode));
+    }
+}
+
+impl Recorder {
+    fn clip_glyphs(&self) -> Vec<u32> {
+        self.calls
+            .iter()
+            .filter_map(|call| match call {
+                Call::PushClipGlyph(glyph) => Some(*glyph),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn colors(&self) -> Vec<(bool, Color)> {
+        self.calls
+            .iter()
+            .filter_map(|call| match call {
+                Call::Color(is_foreground, color) => Some((*is_foreground, *color)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Asserts that every push has a matching pop of the same kind.
+    fn assert_balanced(&self) {
+        let mut stack = Vec::new();
+        for call in &self.calls {
+            match call {
+                Call::PushTransform(_) => stack.push("transform"),
+                Call::PushClipGlyph(_) | Call::PushClipRectangle => stack.push("clip"),
+                Call::PushGroup => stack.push("group"),
+                Call::PopTransform => assert_eq!(stack.pop(), Some("transform")),
+                Call::PopClip => assert_eq!(stack.pop(), Some("clip")),
+                Call::PopGroup(_) => assert_eq!(stack.pop(), Some("group")),
+                Call::Color(..) | Call::Gradient => {}
+            }
+        }
+        assert!(stack.is_empty(), "unbalanced calls: {:?}", self.calls);
+    }
+}
+
+fn paint(glyph: u32, palette: u32, recorder: &mut Recorder) -> Vec<Color> {
+    let font = TestFont::load(common::COLOR_TEST);
+    let hb_font = harfbuzz::Font::new(&font.hb_face);
+    hb_font.paint_glyph(glyph, recorder, palette, FOREGROUND);
+    font.hb_face.palette_colors(palette)
+}
+
+#[test]
+fn colr_v1_glyph_paints_its_layers() {
+    let mut recorder = Recorder::default();
+    let palette = paint(RAINBOW, 0, &mut recorder);
+    recorder.assert_balanced();
+    assert_eq!(recorder.clip_glyphs(), RAINBOW_LAYERS);
+    // the layers are painted in the entries Accent, Face, Green and Blue
+    let expected: Vec<_> = [2, 0, 3, 4]
+        .iter()
+        .map(|&index| (false, palette[index]))
+        .collect();
+    assert_eq!(recorder.colors(), expected);
+    assert!(recorder.calls.contains(&Call::PushGroup));
+}
+
+#[test]
+fn colr_v0_glyph_paints_its_layers() {
+    let mut recorder = Recorder::default();
+    let palette = paint(GRINNING_FACE, 1, &mut recorder);
+    recorder.assert_balanced();
+    assert_eq!(recorder.clip_glyphs(), GRINNING_FACE_LAYERS);
+    let expected: Vec<_> = [0, 1, 1]
+        .iter()
+        .map(|&index| (false, palette[index]))
+        .collect();
+    assert_eq!(recorder.colors(), expected);
+}
+
+#[test]
+fn glyph_without_color_is_painted_in_the_foreground() {
+    let mut recorder = Recorder::default();
+    paint(NOTDEF, 0, &mut recorder);
+    recorder.assert_balanced();
+    assert_eq!(recorder.clip_glyphs(), [NOTDEF]);
+    assert_eq!(recorder.colors(), [(true, FOREGROUND)]);
+}
+
+#[test]
+fn painter_panic_is_resumed_after_painting() {
+    let mut recorder = Recorder {
+        panic_on_color: true,
+        ..Recorder::default()
+    };
+    let result = panic::catch_unwind(AssertUnwindSafe(|| paint(RAINBOW, 0, &mut recorder)));
+    let payload = result.unwrap_err();
+    assert_eq!(payload.downcast_ref::<&str>(), Some(&"painter panic"));
+    // the callbacks after the panic are skipped
+    assert!(matches!(recorder.calls.last(), Some(Call::Color(..))));
+    assert_eq!(recorder.colors().len(), 1);
+}
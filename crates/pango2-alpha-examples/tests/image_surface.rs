@@ -0,0 +1,65 @@
+use pango2_sys_examples::cairo::{self, BorrowedImageSurface, CairoSurface, Format};
+
+#[test]
+fn borrowed_surface_draws_into_the_memory() {
+    // A8 pixels need no alignment, the stride of 8 pixels is 8 bytes
+    let mut data = vec![0u8; 8 * 4];
+    let mut surface = BorrowedImageSurface::new(&mut data, Format::A8, 8, 4, 8).unwrap();
+    surface.with_context(|context| {
+        context.rectangle(0.0, 0.0, 4.0, 4.0);
+        context.fill();
+    });
+    // the surface can be drawn on again
+    surface.with_context(|context| {
+        context.rectangle(0.0, 3.0, 8.0, 1.0);
+        context.fill();
+    });
+    drop(surface);
+    for (index, &alpha) in data.iter().enumerate() {
+        let (x, y) = (index % 8, index / 8);
+        let expected = if x < 4 || y == 3 { 0xff } else { 0 };
+        assert_eq!(alpha, expected, "pixel {x}, {y}");
+    }
+}
+
+#[test]
+fn borrowed_surface_checks_the_memory() {
+    let mut data = vec![0u8; 8 * 4];
+    // the stride is too small for the width
+    assert!(BorrowedImageSurface::new(&mut data, Format::A8, 8, 4, 4).is_err());
+    // the data is too short for the height
+    assert!(BorrowedImageSurface::new(&mut data, Format::A8, 8, 5, 8).is_err());
+}
+
+#[test]
+fn data_is_not_available_while_a_context_references_the_surface() {
+    let mut surface = CairoSurface::new_image_surface(2, 2).unwrap();
+    let context = cairo::CairoContext::create(&surface);
+    context.set_source_rgba(1.0, 0.0, 0.0, 0.5);
+    context.paint();
+    assert!(surface.data().is_none());
+    assert!(surface.data_mut().is_none());
+    // copying the pixels is fine, they are converted to straight alpha
+    assert_eq!(surface.to_rgba8().unwrap(), [255, 0, 0, 128].repeat(4));
+    drop(context);
+
+    let stride = surface.stride();
+    let data = surface.data().unwrap();
+    assert_eq!(data.len(), stride * 2);
+    let pixel = u32::from_ne_bytes([data[0], data[1], data[2], data[3]]);
+    assert_eq!(pixel, 0x8080_0000);
+}
+
+#[test]
+fn rgba8_conversion_checks_the_stride() {
+    // red at half opacity, premultiplied
+    let pixels = 0x8080_0000u32.to_ne_bytes().repeat(2);
+    assert_eq!(
+        cairo::argb32_to_rgba8(&pixels, 1, 2, 4).unwrap(),
+        [255, 0, 0, 128].repeat(2)
+    );
+    // a row doesn't fit into the stride
+    assert!(cairo::argb32_to_rgba8(&pixels, 2, 1, 4).is_none());
+    // the data is too short for the height
+    assert!(cairo::argb32_to_rgba8(&pixels, 1, 3, 4).is_none());
+}
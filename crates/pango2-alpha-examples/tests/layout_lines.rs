@@ -0,0 +1,26 @@
+mod common;
+
+use common::TestFont;
+use pango2_sys_examples::pango2;
+
+#[test]
+fn layout_lines_are_a_copy() {
+    let font = TestFont::load(common::NOTO_SERIF_DISPLAY);
+    let layout = font.layout("Hola,\nPango2!", "Regular 32");
+    let lines = layout.lines();
+    assert_eq!(lines.line_count(), 2);
+    let positions: Vec<_> = lines.lines().iter().map(|(_, x, y)| (*x, *y)).collect();
+    assert!(positions[1].1 > positions[0].1);
+    assert_eq!(lines.extents(), layout.extents());
+
+    // adding to the copy leaves the layout alone
+    let (line, x, y) = lines.lines().remove(0);
+    lines.add_line(line, x, y + 100 * pango2::SCALE);
+    assert_eq!(lines.line_count(), 3);
+    assert_eq!(layout.lines().line_count(), 2);
+
+    // and the copy keeps its lines when the layout changes
+    layout.set_text("Hola, Pango2!");
+    assert_eq!(layout.lines().line_count(), 1);
+    assert_eq!(lines.line_count(), 3);
+}
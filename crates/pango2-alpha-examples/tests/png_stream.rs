@@ -0,0 +1,96 @@
+use std::{io, panic};
+
+use pango2_sys_examples::cairo::{self, CairoError, CairoSurface};
+
+/// A writer that fails after accepting `remaining` bytes.
+struct FailingWriter {
+    remaining: usize,
+    panics: bool,
+}
+
+impl io::Write for FailingWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.remaining < buf.len() {
+            if self.panics {
+                panic!("writer panicked");
+            }
+            return Err(io::Error::other("disk full"));
+        }
+        self.remaining -= buf.len();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+fn blue_surface() -> CairoSurface {
+    let surface = CairoSurface::new_image_surface(64, 32).unwrap();
+    let cairo_context = cairo::CairoContext::create(&surface);
+    cairo_context.set_source_rgb(0.0, 0.5, 1.0);
+    cairo_context.paint();
+    surface
+}
+
+fn encode(surface: &CairoSurface) -> Vec<u8> {
+    let mut png = Vec::new();
+    surface.write_png(&mut png).unwrap();
+    png
+}
+
+#[test]
+fn png_round_trips_through_memory() {
+    let surface = blue_surface();
+    let png = encode(&surface);
+    assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+
+    let decoded = CairoSurface::read_png(png.as_slice()).unwrap();
+    assert_eq!((decoded.width(), decoded.height()), (64, 32));
+    assert_eq!(encode(&decoded), png);
+}
+
+#[test]
+fn truncated_png_is_an_io_error() {
+    let png = encode(&blue_surface());
+    let result = CairoSurface::read_png(&png[..png.len() / 2]);
+    assert!(matches!(result, Err(CairoError::IoError(_))));
+}
+
+#[test]
+fn writer_error_is_returned() {
+    let writer = FailingWriter {
+        remaining: 16,
+        panics: false,
+    };
+    match blue_surface().write_png(writer) {
+        Err(CairoError::IoError(error)) => assert_eq!(error.to_string(), "disk full"),
+        other => panic!("expected the error of the writer, got {:?}", other),
+    }
+}
+
+#[test]
+fn writer_panic_reaches_the_caller() {
+    let surface = blue_surface();
+    let writer = FailingWriter {
+        remaining: 16,
+        panics: true,
+    };
+    let payload = panic::catch_unwind(|| surface.write_png(writer)).unwrap_err();
+    assert_eq!(payload.downcast_ref::<&str>(), Some(&"writer panicked"));
+}
+
+#[test]
+fn reader_panic_reaches_the_caller() {
+    struct PanickingReader;
+
+    impl io::Read for PanickingReader {
+        fn read(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
+            panic!("reader panicked");
+        }
+    }
+
+    let payload =
+        panic::catch_unwind(|| CairoSurface::read_png(PanickingReader).map(|_| ())).unwrap_err();
+    assert_eq!(payload.downcast_ref::<&str>(), Some(&"reader panicked"));
+}
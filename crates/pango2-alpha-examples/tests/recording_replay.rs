@@ -0,0 +1,76 @@
+mod common;
+
+use common::TestFont;
+use pango2_sys_examples::cairo;
+
+fn alpha(surface: &mut cairo::CairoSurface) -> Vec<u8> {
+    common::pixels(surface)
+        .iter()
+        .map(|pixel| (pixel >> 24) as u8)
+        .collect()
+}
+
+#[test]
+fn exact_ink_extents_are_tight() {
+    let font = TestFont::load(common::NOTO_SERIF_DISPLAY);
+    let recording = font.layout("Hola, Pango2!", "Regular 32").record().unwrap();
+    assert_eq!(recording.extents(), None);
+    let ink = recording.ink_extents();
+    assert!(ink.width > 0.0 && ink.height > 0.0);
+
+    for scale in [1.0, 2.5] {
+        let exact = recording.exact_ink_extents(scale).unwrap();
+        // inside the conservative extents
+        assert!(exact.x >= (ink.x * scale).floor());
+        assert!(exact.y >= (ink.y * scale).floor());
+        assert!(exact.x + exact.width <= ((ink.x + ink.width) * scale).ceil());
+        assert!(exact.y + exact.height <= ((ink.y + ink.height) * scale).ceil());
+
+        let (mut image, left, top) = recording.to_image_surface(scale).unwrap();
+        let (width, height) = (image.width(), image.height());
+        assert_eq!(
+            (left as f32, top as f32, width as f32, height as f32),
+            (exact.x, exact.y, exact.width, exact.height)
+        );
+        // the ink touches all four sides
+        let alpha = alpha(&mut image);
+        let inked = |x: i32, y: i32| alpha[(y * width + x) as usize] > 0;
+        assert!((0..width).any(|x| inked(x, 0)));
+        assert!((0..width).any(|x| inked(x, height - 1)));
+        assert!((0..height).any(|y| inked(0, y)));
+        assert!((0..height).any(|y| inked(width - 1, y)));
+    }
+}
+
+#[test]
+fn replay_draws_the_cropped_pixels() {
+    let font = TestFont::load(common::NOTO_SERIF_DISPLAY);
+    let recording = font.layout("Hola, Pango2!", "Regular 32").record().unwrap();
+    let (mut image, left, top) = recording.to_image_surface(1.0).unwrap();
+    let (width, height) = (image.width(), image.height());
+    let mut target = cairo::CairoSurface::new_image_surface(width + 40, height + 30).unwrap();
+    let context = cairo::CairoContext::create(&target);
+    recording.replay(&context, (20 - left) as f32, (10 - top) as f32);
+    drop(context);
+
+    let (image_alpha, target_alpha) = (alpha(&mut image), alpha(&mut target));
+    for y in 0..height {
+        for x in 0..width {
+            let image_alpha = image_alpha[(y * width + x) as usize];
+            let target_alpha = target_alpha[((y + 10) * (width + 40) + x + 20) as usize];
+            assert!(
+                image_alpha.abs_diff(target_alpha) <= 1,
+                "pixel {x}, {y} differs"
+            );
+        }
+    }
+}
+
+#[test]
+fn empty_recording_has_no_ink() {
+    let recording = cairo::RecordingSurface::new(cairo::Content::ColorAlpha, None).unwrap();
+    let exact = recording.exact_ink_extents(1.0).unwrap();
+    assert_eq!((exact.width, exact.height), (0.0, 0.0));
+    let (image, ..) = recording.to_image_surface(1.0).unwrap();
+    assert_eq!((image.width(), image.height()), (0, 0));
+}
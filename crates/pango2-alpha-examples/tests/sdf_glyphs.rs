@@ -0,0 +1,144 @@
+mod common;
+
+use common::TestFont;
+use pango2_sys_examples::{atlas, sdf};
+
+const SIZE: f32 = 32.0;
+const SPREAD: f32 = 4.0;
+
+/// FNV-1a, stable across platforms so the value can be compared with a
+/// golden checksum.
+fn checksum(data: &[u8]) -> u64 {
+    data.iter().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+struct Golden {
+    glyph: u32,
+    size: (i32, i32),
+    bearing: (i32, i32),
+    sdf: u64,
+    msdf: u64,
+}
+
+/// The distance fields of "Ag&" in the bundled NotoSerifDisplay at 32
+/// pixels with a spread of 4. Recorded by the `sdf` module on the outlines
+/// of HarfBuzz 6.0.0's `hb_font_get_glyph_shape`, which HarfBuzz 7.0
+/// renamed to `hb_font_draw_glyph`.
+const GOLDEN: [Golden; 3] = [
+    Golden {
+        glyph: 36,
+        size: (31, 31),
+        bearing: (-4, -27),
+        sdf: 0xd1b09c34b3d01f06,
+        msdf: 0x44957b3423da9817,
+    },
+    Golden {
+        glyph: 74,
+        size: (25, 35),
+        bearing: (-4, -23),
+        sdf: 0x2d6952b2f5930389,
+        msdf: 0x0cc34cad8994758a,
+    },
+    Golden {
+        glyph: 9,
+        size: (31, 33),
+        bearing: (-3, -28),
+        sdf: 0xe194363bc55e66c2,
+        msdf: 0xa219b73bac6fa096,
+    },
+];
+
+fn glyphs(font: &TestFont) -> Vec<u32> {
+    font.layout("Ag&", "Regular 32px")
+        .glyph_runs()
+        .iter()
+        .flat_map(|run| {
+            run.glyphs
+                .iter()
+                .filter(|info| info.is_font_glyph())
+                .map(|info| info.glyph)
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+#[test]
+fn distance_fields_match_the_golden_checksums() {
+    let font = TestFont::load(common::NOTO_SERIF_DISPLAY);
+    let glyphs = glyphs(&font);
+    assert_eq!(glyphs, GOLDEN.map(|golden| golden.glyph));
+
+    let hb_font = atlas::scaled_font(&font.hb_face, SIZE);
+    let sdf_generator = sdf::SdfGenerator::new(sdf::FieldType::Sdf, SPREAD);
+    let msdf_generator = sdf::SdfGenerator::new(sdf::FieldType::Msdf, SPREAD);
+    for golden in GOLDEN {
+        let glyph = golden.glyph;
+        let field = sdf_generator.generate(&hb_font, glyph, 0.0);
+        let multi = msdf_generator.generate(&hb_font, glyph, 0.0);
+        assert_eq!((field.width, field.height), golden.size, "glyph {glyph}");
+        assert_eq!(
+            (field.bearing_x, field.bearing_y),
+            golden.bearing,
+            "glyph {glyph}"
+        );
+        assert_eq!(
+            (multi.width, multi.height, multi.channels),
+            (field.width, field.height, 3)
+        );
+        assert_eq!(
+            (checksum(&field.data), checksum(&multi.data)),
+            (golden.sdf, golden.msdf),
+            "glyph {glyph}"
+        );
+    }
+}
+
+#[test]
+fn msdf_agrees_with_sdf_on_the_inside() {
+    let font = TestFont::load(common::NOTO_SERIF_DISPLAY);
+    let hb_font = atlas::scaled_font(&font.hb_face, SIZE);
+    let sdf_generator = sdf::SdfGenerator::new(sdf::FieldType::Sdf, SPREAD);
+    let msdf_generator = sdf::SdfGenerator::new(sdf::FieldType::Msdf, SPREAD);
+    for glyph in glyphs(&font) {
+        let field = sdf_generator.generate(&hb_font, glyph, 0.0);
+        let multi = msdf_generator.generate(&hb_font, glyph, 0.0);
+
+        // the spread keeps the border outside, the stems are more than two
+        // pixels wide, so their centers lie a pixel or more inside
+        let width = field.width as usize;
+        let mut border = field.data[..width]
+            .iter()
+            .chain(&field.data[field.data.len() - width..]);
+        assert!(border.all(|value| *value < 128), "glyph {glyph}");
+        assert!(field.data.iter().any(|value| *value > 160), "glyph {glyph}");
+
+        // values rounded onto the edge aside
+        for (rgb, value) in multi.data.chunks(3).zip(&field.data) {
+            let median = rgb[0].min(rgb[1]).max(rgb[0].max(rgb[1]).min(rgb[2]));
+            let disagrees = (median > 128 && *value < 128) || (median < 128 && *value > 128);
+            assert!(!disagrees, "glyph {glyph}");
+        }
+    }
+}
+
+#[test]
+fn atlas_pages_are_deterministic() {
+    let font = TestFont::load(common::NOTO_SERIF_DISPLAY);
+    let glyphs = glyphs(&font);
+    for field_type in [sdf::FieldType::Sdf, sdf::FieldType::Msdf] {
+        let build = || {
+            let generator = sdf::SdfGenerator::new(field_type, SPREAD);
+            let mut glyph_atlas =
+                atlas::GlyphAtlas::with_distance_field(&font.hb_face, SIZE, generator, 128);
+            for glyph in &glyphs {
+                glyph_atlas.glyph(*glyph, 0.0).unwrap();
+            }
+            glyph_atlas
+        };
+        let (mut first, mut second) = (build(), build());
+        assert_eq!(first.page_count(), 1);
+        assert_eq!(first.page_data(0), second.page_data(0));
+    }
+}
@@ -0,0 +1,207 @@
+mod common;
+
+use pango2_sys_examples::{
+    cairo, freetype,
+    harfbuzz::{self, PathBuilder, PathCommand},
+    pango2,
+};
+
+const TEXT: &str = "Outlined text, Caveat";
+const BLUE: &str = "#1a5fb4";
+const RED: &str = "#c01c28";
+
+/// A layout of `TEXT` in NotoSerifDisplay with "Caveat" in Caveat. The
+/// fields are dropped in declaration order, so the FreeType faces outlive
+/// the layout.
+struct TestLayout {
+    layout: pango2::Pango2Layout,
+    _faces: Vec<freetype::Face>,
+    _library: freetype::Library,
+}
+
+impl TestLayout {
+    fn new() -> Self {
+        let library = freetype::Library::init().unwrap();
+        let font_map = pango2::Pango2FontMap::new();
+        let mut faces = Vec::new();
+        for path in [common::NOTO_SERIF_DISPLAY, common::CAVEAT] {
+            let face = library.face_from_file(path, 0).unwrap();
+            let hb_face = harfbuzz::Face::from_ft(&face);
+            font_map.add_face(&pango2::Pango2HbFace::from_hb_face(&hb_face, -2));
+            faces.push(face);
+        }
+        let context = pango2::Pango2Context::from_font_map(&font_map);
+        // unhinted outlines match the ones HarfBuzz draws
+        let options = cairo::FontOptions::new();
+        options.set_hint_style(cairo::HintStyle::None);
+        options.set_hint_metrics(cairo::HintMetrics::Off);
+        context.set_font_options(&options);
+
+        let layout = pango2::Pango2Layout::new(&context);
+        layout.set_text(TEXT);
+        layout.set_font_description_string(format!("{} Regular 48", faces[0].face_name()).as_str());
+        let caveat = TEXT.find("Caveat").unwrap();
+        let attributes = pango2::AttrList::new();
+        attributes.insert(
+            pango2::Attribute::foreground(pango2::Color::parse(BLUE).unwrap())
+                .with_range(0, caveat),
+        );
+        attributes.insert(
+            pango2::Attribute::family(&faces[1].face_name()).with_range(caveat, TEXT.len()),
+        );
+        attributes.insert(
+            pango2::Attribute::foreground(pango2::Color::parse(RED).unwrap())
+                .with_range(caveat, TEXT.len()),
+        );
+        layout.set_attributes(&attributes);
+        TestLayout {
+            layout,
+            _faces: faces,
+            _library: library,
+        }
+    }
+}
+
+/// Builds the path data of a run from the HarfBuzz outlines of its glyphs,
+/// the way cairo stores them: quadratic curves become cubic ones, the line
+/// closing a contour is dropped and the path ends at the start of its last
+/// contour.
+fn expected_run_path(run: &pango2::GlyphRun) -> String {
+    let scale = pango2::SCALE as f32;
+    let mut segments = Vec::new();
+    let mut start = (0.0, 0.0);
+    let mut x = run.x;
+    for glyph in &run.glyphs {
+        let origin = (
+            (x + glyph.x_offset) as f32,
+            (run.baseline + glyph.y_offset) as f32,
+        );
+        // the outline is in Pango units with y growing upwards
+        let point = |x: f32, y: f32| ((origin.0 + x) / scale, (origin.1 - y) / scale);
+        let mut builder = PathBuilder::new();
+        run.font().draw_glyph(glyph.glyph, &mut builder);
+        let commands = builder.into_commands();
+        let mut current = (0.0, 0.0);
+        for (index, command) in commands.iter().enumerate() {
+            match *command {
+                PathCommand::MoveTo(x, y) => {
+                    start = point(x, y);
+                    current = start;
+                    segments.push(cairo::PathSegment::MoveTo(start.0, start.1));
+                }
+                PathCommand::LineTo(x, y) => {
+                    let to = point(x, y);
+                    let closes = to == start
+                        && matches!(commands.get(index + 1), Some(PathCommand::ClosePath));
+                    if !closes {
+                        segments.push(cairo::PathSegment::LineTo(to.0, to.1));
+                    }
+                    current = to;
+                }
+                PathCommand::QuadraticTo(cx, cy, x, y) => {
+                    let (control, to) = (point(cx, cy), point(x, y));
+                    let c1 = (
+                        current.0 + 2.0 / 3.0 * (control.0 - current.0),
+                        current.1 + 2.0 / 3.0 * (control.1 - current.1),
+                    );
+                    let c2 = (
+                        to.0 + 2.0 / 3.0 * (control.0 - to.0),
+                        to.1 + 2.0 / 3.0 * (control.1 - to.1),
+                    );
+                    segments.push(cairo::PathSegment::CurveTo(
+                        c1.0, c1.1, c2.0, c2.1, to.0, to.1,
+                    ));
+                    current = to;
+                }
+                PathCommand::CubicTo(c1x, c1y, c2x, c2y, x, y) => {
+                    let (c1, c2, to) = (point(c1x, c1y), point(c2x, c2y), point(x, y));
+                    segments.push(cairo::PathSegment::CurveTo(
+                        c1.0, c1.1, c2.0, c2.1, to.0, to.1,
+                    ));
+                    current = to;
+                }
+                PathCommand::ClosePath => {
+                    segments.push(cairo::PathSegment::ClosePath);
+                    current = start;
+                }
+            }
+        }
+        x += glyph.width;
+    }
+    if matches!(segments.last(), Some(cairo::PathSegment::ClosePath)) {
+        segments.push(cairo::PathSegment::MoveTo(start.0, start.1));
+    }
+    segments
+        .iter()
+        .map(|segment| segment.to_string())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Asserts that both paths have the same commands and that their
+/// coordinates differ by at most 0.02, cairo stores them as fixed point
+/// numbers with 8 fractional bits.
+fn assert_paths_match(actual: &str, expected: &str) {
+    let actual: Vec<_> = actual.split(' ').collect();
+    let expected: Vec<_> = expected.split(' ').collect();
+    assert_eq!(actual.len(), expected.len(), "{actual:?} != {expected:?}");
+    for (a, e) in actual.iter().zip(&expected) {
+        match (a.parse::<f32>(), e.parse::<f32>()) {
+            (Ok(a), Ok(e)) => assert!((a - e).abs() <= 0.02, "{a} != {e}"),
+            _ => assert_eq!(a, e),
+        }
+    }
+}
+
+#[test]
+fn path_data_matches_the_glyph_outlines() {
+    let test = TestLayout::new();
+    let runs = test.layout.glyph_runs();
+    // NotoSerifDisplay for "Outlined text, " and Caveat for "Caveat"
+    assert_eq!(runs.len(), 2);
+    let expected = runs
+        .iter()
+        .map(expected_run_path)
+        .filter(|data| !data.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+    let path_data = test.layout.to_svg_path_data();
+    assert!(path_data.starts_with("M "));
+    assert_paths_match(&path_data, &expected);
+    assert_eq!(path_data, test.layout.to_svg_path_data());
+}
+
+#[test]
+fn document_has_a_path_per_run() {
+    let test = TestLayout::new();
+    let document = test.layout.to_svg_document();
+    assert_eq!(document, test.layout.to_svg_document());
+
+    let (_, logical) = test.layout.extents();
+    let width = (logical.width + pango2::SCALE - 1) / pango2::SCALE;
+    let height = (logical.height + pango2::SCALE - 1) / pango2::SCALE;
+    let header = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" viewBox=\"0 0 {width} {height}\">\n"
+    );
+    assert!(document.starts_with(&header));
+    assert!(document.ends_with("</svg>\n"));
+
+    let paths: Vec<_> = document
+        .lines()
+        .filter(|line| line.starts_with("  <path "))
+        .collect();
+    let runs: Vec<_> = test
+        .layout
+        .glyph_runs()
+        .iter()
+        .map(expected_run_path)
+        .collect();
+    assert_eq!(paths.len(), runs.len());
+    for ((path, expected), color) in paths.iter().zip(&runs).zip([BLUE, RED]) {
+        let data = path
+            .strip_prefix("  <path d=\"")
+            .and_then(|path| path.strip_suffix(&format!("\" fill=\"{color}\"/>")))
+            .unwrap_or_else(|| panic!("unexpected path element {path}"));
+        assert_paths_match(data, expected);
+    }
+}
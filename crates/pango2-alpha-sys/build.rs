@@ -50,6 +50,10 @@ fn main() {
         // included header files changed.
         .parse_callbacks(Box::new(bindgen::CargoCallbacks))
         .allowlist_function("pango2_.*")
+        // the class struct to subclass Pango2Renderer, its GObjectClass
+        // parent is only embedded
+        .allowlist_type("Pango2RendererClass")
+        .opaque_type("_GObjectClass")
         .blocklist_type("hb_.*")
         .blocklist_type("cairo_t")
         // .blocklist_var("HB_.*")
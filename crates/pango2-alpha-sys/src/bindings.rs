@@ -504,6 +504,7 @@ fn bindgen_test_layout__GTypeInstance() {
 }
 #[doc = " GObject:\n\n The base object type.\n\n All the fields in the `GObject` structure are private to the implementation\n and should never be accessed directly.\n\n Since GLib 2.72, all #GObjects are guaranteed to be aligned to at least the\n alignment of the largest basic GLib type (typically this is #guint64 or\n #gdouble). If you need larger alignment for an element in a #GObject, you\n should allocate it on the heap (aligned), or arrange for your #GObject to be\n appropriately padded. This guarantee applies to the #GObject (or derived)\n struct, the #GObjectClass (or derived) struct, and any private data allocated\n by G_ADD_PRIVATE()."]
 pub type GObject = _GObject;
+pub type GObjectClass = _GObjectClass;
 #[doc = " GObject:\n\n The base object type.\n\n All the fields in the `GObject` structure are private to the implementation\n and should never be accessed directly.\n\n Since GLib 2.72, all #GObjects are guaranteed to be aligned to at least the\n alignment of the largest basic GLib type (typically this is #guint64 or\n #gdouble). If you need larger alignment for an element in a #GObject, you\n should allocate it on the heap (aligned), or arrange for your #GObject to be\n appropriately padded. This guarantee applies to the #GObject (or derived)\n struct, the #GObjectClass (or derived) struct, and any private data allocated\n by G_ADD_PRIVATE()."]
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
@@ -557,6 +558,24 @@ fn bindgen_test_layout__GObject() {
         )
     );
 }
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _GObjectClass {
+    pub _bindgen_opaque_blob: [u64; 17usize],
+}
+#[test]
+fn bindgen_test_layout__GObjectClass() {
+    assert_eq!(
+        ::std::mem::size_of::<_GObjectClass>(),
+        136usize,
+        concat!("Size of: ", stringify!(_GObjectClass))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<_GObjectClass>(),
+        8usize,
+        concat!("Alignment of ", stringify!(_GObjectClass))
+    );
+}
 #[doc = " Pango2LogAttr:\n @is_line_break: if set, can break line in front of character\n @is_mandatory_break: if set, must break line in front of character\n @is_char_break: if set, can break here when doing character wrapping\n @is_white: is whitespace character\n @is_cursor_position: if set, cursor can appear in front of character.\n   i.e. this is a grapheme boundary, or the first character in the text.\n   This flag implements Unicode's\n   [Grapheme Cluster Boundaries](http://www.unicode.org/reports/tr29/)\n   semantics.\n @is_word_start: is first character in a word\n @is_word_end: is first non-word char after a word\n   Note that in degenerate cases, you could have both @is_word_start\n   and @is_word_end set for some character.\n @is_sentence_boundary: is a sentence boundary.\n   There are two ways to divide sentences. The first assigns all\n   inter-sentence whitespace/control/format chars to some sentence,\n   so all chars are in some sentence; @is_sentence_boundary denotes\n   the boundaries there. The second way doesn't assign\n   between-sentence spaces, etc. to any sentence, so\n   @is_sentence_start/@is_sentence_end mark the boundaries of those sentences.\n @is_sentence_start: is first character in a sentence\n @is_sentence_end: is first char after a sentence.\n   Note that in degenerate cases, you could have both @is_sentence_start\n   and @is_sentence_end set for some character. (e.g. no space after a\n   period, so the next sentence starts right away)\n @backspace_deletes_character: if set, backspace deletes one character\n   rather than the entire grapheme cluster. This field is only meaningful\n   on grapheme boundaries (where @is_cursor_position is set). In some languages,\n   the full grapheme (e.g. letter + diacritics) is considered a unit, while in\n   others, each decomposed character in the grapheme is a unit. In the default\n   implementation of [func@default_break], this bit is set on all grapheme\n   boundaries except those following Latin, Cyrillic or Greek base characters.\n @is_expandable_space: is a whitespace character that can possibly be\n   expanded for justification purposes.\n @is_word_boundary: is a word boundary, as defined by UAX#29.\n   More specifically, means that this is not a position in the middle of a word.\n   For example, both sides of a punctuation mark are considered word boundaries.\n   This flag is particularly useful when selecting text word-by-word. This flag\n   implements Unicode's [Word Boundaries](http://www.unicode.org/reports/tr29/)\n   semantics.\n @break_inserts_hyphen: when breaking lines before this char, insert a hyphen.\n @break_removes_preceding: when breaking lines before this char, remove the\n   preceding char.\n\n The `Pango2LogAttr` structure stores information about the attributes of a\n single character."]
 pub type Pango2LogAttr = _Pango2LogAttr;
 #[repr(C)]
@@ -3838,6 +3857,7 @@ extern "C" {
 }
 #[doc = " Pango2Renderer:\n\n `Pango2Renderer` is a base class for objects that can render text\n provided as `Pango2GlyphString` or `Pango2Layout`.\n\n By subclassing `Pango2Renderer` and overriding operations such as\n @draw_glyphs and @draw_rectangle, renderers for particular font\n backends and destinations can be created."]
 pub type Pango2Renderer = _Pango2Renderer;
+pub type Pango2RendererClass = _Pango2RendererClass;
 pub const Pango2RenderPart_PANGO2_RENDER_PART_FOREGROUND: Pango2RenderPart = 0;
 pub const Pango2RenderPart_PANGO2_RENDER_PART_BACKGROUND: Pango2RenderPart = 1;
 pub const Pango2RenderPart_PANGO2_RENDER_PART_UNDERLINE: Pango2RenderPart = 2;
@@ -3875,6 +3895,215 @@ fn bindgen_test_layout__Pango2Renderer() {
         )
     );
 }
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _Pango2RendererClass {
+    pub parent_class: GObjectClass,
+    pub draw_glyphs: ::std::option::Option<
+        unsafe extern "C" fn(
+            renderer: *mut Pango2Renderer,
+            font: *mut Pango2Font,
+            glyphs: *mut Pango2GlyphString,
+            x: ::std::os::raw::c_int,
+            y: ::std::os::raw::c_int,
+        ),
+    >,
+    pub draw_rectangle: ::std::option::Option<
+        unsafe extern "C" fn(
+            renderer: *mut Pango2Renderer,
+            part: Pango2RenderPart,
+            x: ::std::os::raw::c_int,
+            y: ::std::os::raw::c_int,
+            width: ::std::os::raw::c_int,
+            height: ::std::os::raw::c_int,
+        ),
+    >,
+    pub draw_styled_line: ::std::option::Option<
+        unsafe extern "C" fn(
+            renderer: *mut Pango2Renderer,
+            part: Pango2RenderPart,
+            style: Pango2LineStyle,
+            x: ::std::os::raw::c_int,
+            y: ::std::os::raw::c_int,
+            width: ::std::os::raw::c_int,
+            height: ::std::os::raw::c_int,
+        ),
+    >,
+    pub draw_trapezoid: ::std::option::Option<
+        unsafe extern "C" fn(
+            renderer: *mut Pango2Renderer,
+            part: Pango2RenderPart,
+            y1_: f64,
+            x11: f64,
+            x21: f64,
+            y2: f64,
+            x12: f64,
+            x22: f64,
+        ),
+    >,
+    pub draw_glyph: ::std::option::Option<
+        unsafe extern "C" fn(
+            renderer: *mut Pango2Renderer,
+            font: *mut Pango2Font,
+            glyph: Pango2Glyph,
+            x: f64,
+            y: f64,
+        ),
+    >,
+    pub part_changed: ::std::option::Option<
+        unsafe extern "C" fn(renderer: *mut Pango2Renderer, part: Pango2RenderPart),
+    >,
+    pub begin: ::std::option::Option<unsafe extern "C" fn(renderer: *mut Pango2Renderer)>,
+    pub end: ::std::option::Option<unsafe extern "C" fn(renderer: *mut Pango2Renderer)>,
+    pub prepare_run: ::std::option::Option<
+        unsafe extern "C" fn(renderer: *mut Pango2Renderer, run: *mut Pango2Run),
+    >,
+    pub draw_run: ::std::option::Option<
+        unsafe extern "C" fn(
+            renderer: *mut Pango2Renderer,
+            text: *const ::std::os::raw::c_char,
+            run: *mut Pango2Run,
+            x: ::std::os::raw::c_int,
+            y: ::std::os::raw::c_int,
+        ),
+    >,
+    pub _pango2_reserved: [gpointer; 8usize],
+}
+#[test]
+fn bindgen_test_layout__Pango2RendererClass() {
+    const UNINIT: ::std::mem::MaybeUninit<_Pango2RendererClass> = ::std::mem::MaybeUninit::uninit();
+    let ptr = UNINIT.as_ptr();
+    assert_eq!(
+        ::std::mem::size_of::<_Pango2RendererClass>(),
+        280usize,
+        concat!("Size of: ", stringify!(_Pango2RendererClass))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<_Pango2RendererClass>(),
+        8usize,
+        concat!("Alignment of ", stringify!(_Pango2RendererClass))
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).parent_class) as usize - ptr as usize },
+        0usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(_Pango2RendererClass),
+            "::",
+            stringify!(parent_class)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).draw_glyphs) as usize - ptr as usize },
+        136usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(_Pango2RendererClass),
+            "::",
+            stringify!(draw_glyphs)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).draw_rectangle) as usize - ptr as usize },
+        144usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(_Pango2RendererClass),
+            "::",
+            stringify!(draw_rectangle)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).draw_styled_line) as usize - ptr as usize },
+        152usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(_Pango2RendererClass),
+            "::",
+            stringify!(draw_styled_line)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).draw_trapezoid) as usize - ptr as usize },
+        160usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(_Pango2RendererClass),
+            "::",
+            stringify!(draw_trapezoid)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).draw_glyph) as usize - ptr as usize },
+        168usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(_Pango2RendererClass),
+            "::",
+            stringify!(draw_glyph)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).part_changed) as usize - ptr as usize },
+        176usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(_Pango2RendererClass),
+            "::",
+            stringify!(part_changed)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).begin) as usize - ptr as usize },
+        184usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(_Pango2RendererClass),
+            "::",
+            stringify!(begin)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).end) as usize - ptr as usize },
+        192usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(_Pango2RendererClass),
+            "::",
+            stringify!(end)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).prepare_run) as usize - ptr as usize },
+        200usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(_Pango2RendererClass),
+            "::",
+            stringify!(prepare_run)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).draw_run) as usize - ptr as usize },
+        208usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(_Pango2RendererClass),
+            "::",
+            stringify!(draw_run)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr)._pango2_reserved) as usize - ptr as usize },
+        216usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(_Pango2RendererClass),
+            "::",
+            stringify!(_pango2_reserved)
+        )
+    );
+}
 extern "C" {
     pub fn pango2_renderer_get_type() -> GType;
 }